@@ -1,62 +1,274 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use ::metrics::{
     HealthChecker,
     prometheus::{self, Encoder, Registry, TextEncoder},
 };
 use actix_web::{
-    App, HttpResponse, HttpServer, Responder, error::InternalError, get, http::StatusCode, web,
+    App, HttpRequest, HttpResponse, HttpServer, Responder,
+    body::MessageBody,
+    dev::{Server, ServiceRequest, ServiceResponse},
+    error::{ErrorUnauthorized, InternalError},
+    get,
+    http::{StatusCode, header},
+    middleware::{Next, from_fn},
+    post, web,
 };
-use serde::Deserialize;
+use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse};
+use clock::SystemClock;
+use serde::{Deserialize, Serialize};
 use services::{
-    cost_reporter::service::CostReporter, health_reporter::service::HealthReporter,
-    status_reporter::service::StatusReporter,
+    admin::service::{Admin, AdminAction},
+    block_reporter::service::{BlockReport, BlockReporter},
+    bundle_reporter::service::{BundleDetails, BundleReporter, BundlesPage},
+    cost_reporter::{
+        port::{CostBucket, CostFilter},
+        service::{CostAnalytics, CostReporter},
+    },
+    events::service::{Event, EventBus, EventFeed, RecordedEvent},
+    health_reporter::service::{HealthReport, HealthReporter, RunnerHealth},
+    runner_control::RunnerControls,
+    state_committer::service::{FeeDecisions, ForcedActions},
+    status_reporter::service::{StatusReport, StatusReporter},
+    types::{BundleCost, DateTime, HeightRange, Utc},
+    wallet_balance_tracker::service::WalletBalances,
+};
+use utoipa::{
+    IntoParams, Modify, OpenApi, ToSchema,
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
 };
 
 use crate::{
-    Database,
+    Database, FuelApi,
     config::{Config, Internal},
     errors::{Error, Result},
+    graphql::{self, CommitterSchema},
 };
 
-pub async fn launch_api_server(
+/// Describes the HTTP API, served at `/openapi.json`. The GraphQL API describes itself at
+/// `/graphql/schema`.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Fuel Block Committer API",
+        version = "1",
+        description = "Progress of the fuel blocks on their way to L1, and the cost of posting them."
+    ),
+    paths(
+        health,
+        live,
+        ready,
+        status,
+        metrics,
+        costs,
+        cost_summary,
+        bundles,
+        bundle,
+        block,
+        event_stream,
+        pause_runner,
+        resume_runner,
+        force_submission,
+        force_gas_bump,
+        trigger_pruning,
+        acknowledge_foreign_commits,
+    ),
+    modifiers(&AdminTokenSecurity)
+)]
+pub struct ApiDoc;
+
+/// The admin endpoints expect the token configured in `app.admin_token`.
+struct AdminTokenSecurity;
+
+impl Modify for AdminTokenSecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_default()
+            .add_security_scheme(
+                "admin_token",
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+            );
+    }
+}
+
+/// Builds the API server. Signal handling is left to the caller so that the server is stopped as
+/// part of the committer's own graceful shutdown.
+#[allow(clippy::too_many_arguments)]
+pub fn launch_api_server(
     config: &Config,
     internal_config: &Internal,
     metrics_registry: Registry,
-    storage: impl services::status_reporter::port::Storage + Clone + 'static,
+    storage: Database,
+    fuel_api: FuelApi,
     fuel_health_check: HealthChecker,
-    eth_health_check: HealthChecker,
-) -> Result<()> {
+    fuel_endpoint_health_checks: Vec<(String, HealthChecker)>,
+    eth_health_check: Option<HealthChecker>,
+    commit_watcher_health_check: Option<HealthChecker>,
+    runners_health: Vec<RunnerHealth>,
+    fee_decisions: FeeDecisions,
+    wallet_balances: WalletBalances,
+    runner_controls: RunnerControls,
+    forced_actions: ForcedActions,
+    events: EventBus,
+) -> Result<Server> {
     let metrics_registry = Arc::new(metrics_registry);
-    let status_reporter = Arc::new(StatusReporter::new(storage.clone()));
-    let health_reporter = Arc::new(HealthReporter::new(fuel_health_check, eth_health_check));
+    let status_reporter = Arc::new(
+        StatusReporter::new(
+            storage.clone(),
+            fuel_api.clone(),
+            SystemClock,
+            config.app.bundle.block_height_lookback,
+        )
+        .with_fee_decisions(fee_decisions)
+        .with_wallet_balances(wallet_balances),
+    );
+    let mut health_reporter = HealthReporter::new(
+        fuel_health_check,
+        eth_health_check,
+        storage.clone(),
+        runners_health,
+    )
+    .with_fuel_endpoints(fuel_endpoint_health_checks);
+    if let Some(health_check) = commit_watcher_health_check {
+        health_reporter = health_reporter.with_commit_watcher(health_check);
+    }
+    let health_reporter = Arc::new(health_reporter);
+    let bundle_reporter = Arc::new(BundleReporter::new(
+        storage.clone(),
+        internal_config.bundle_request_limit,
+    ));
+    let block_reporter = Arc::new(BlockReporter::new(
+        storage.clone(),
+        fuel_api,
+        config.app.bundle.block_height_lookback,
+    ));
     let cost_reporter = Arc::new(CostReporter::new(
-        storage,
+        storage.clone(),
         internal_config.cost_request_limit,
     ));
+    let event_feed = Arc::new(EventFeed::new(storage.clone(), events, EVENTS_PER_READ));
+    let admin = Arc::new(Admin::new(
+        storage,
+        SystemClock,
+        runner_controls,
+        forced_actions,
+    ));
+    let admin_token = config.app.admin_token.clone().map(AdminToken);
+    let graphql_schema = graphql::schema(
+        Arc::clone(&bundle_reporter),
+        Arc::clone(&block_reporter),
+        Arc::clone(&cost_reporter),
+        Arc::clone(&status_reporter),
+        internal_config.bundle_request_limit,
+    );
     HttpServer::new(move || {
-        App::new()
+        let app = App::new()
             .app_data(web::Data::new(Arc::clone(&metrics_registry)))
             .app_data(web::Data::new(Arc::clone(&status_reporter)))
             .app_data(web::Data::new(Arc::clone(&health_reporter)))
             .app_data(web::Data::new(Arc::clone(&cost_reporter)))
+            .app_data(web::Data::new(Arc::clone(&bundle_reporter)))
+            .app_data(web::Data::new(Arc::clone(&block_reporter)))
+            .app_data(web::Data::new(Arc::clone(&event_feed)))
+            .app_data(web::Data::new(graphql_schema.clone()))
             .service(status)
             .service(metrics)
             .service(health)
+            .service(live)
+            .service(ready)
             .service(costs)
+            .service(cost_summary)
+            .service(bundles)
+            .service(bundle)
+            .service(block)
+            .service(event_stream)
+            .service(
+                web::resource("/graphql")
+                    .route(web::get().to(graphql))
+                    .route(web::post().to(graphql)),
+            )
+            .service(graphql_schema_sdl)
+            .service(openapi_json);
+
+        match &admin_token {
+            Some(token) => app.service(
+                web::scope("/admin")
+                    .app_data(web::Data::new(token.clone()))
+                    .app_data(web::Data::new(Arc::clone(&admin)))
+                    .wrap(from_fn(require_admin_token))
+                    .service(pause_runner)
+                    .service(resume_runner)
+                    .service(force_submission)
+                    .service(force_gas_bump)
+                    .service(trigger_pruning)
+                    .service(acknowledge_foreign_commits),
+            ),
+            None => app,
+        }
     })
+    .disable_signals()
+    .shutdown_timeout(config.app.shutdown_timeout.as_secs())
     .bind((config.app.host, config.app.port))
-    .map_err(|e| Error::Other(e.to_string()))?
-    .run()
-    .await
     .map_err(|e| Error::Other(e.to_string()))
+    .map(HttpServer::run)
 }
 
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses(
+        (status = 200, description = "Every service is live and every connection is up", body = HealthReport),
+        (status = 500, description = "A service or connection is unhealthy", body = HealthReport),
+    )
+)]
 #[get("/health")]
-async fn health(data: web::Data<Arc<HealthReporter>>) -> impl Responder {
-    let report = data.report();
+async fn health(data: web::Data<Arc<HealthReporter<Database>>>) -> impl Responder {
+    let report = data.report().await;
+    let healthy = report.healthy();
+
+    health_response(healthy, report)
+}
+
+/// Fails if a service is stuck or crashed, i.e. if the committer should be restarted.
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "health",
+    responses(
+        (status = 200, description = "Every service is making progress", body = HealthReport),
+        (status = 500, description = "A service is stuck or stopped", body = HealthReport),
+    )
+)]
+#[get("/health/live")]
+async fn live(data: web::Data<Arc<HealthReporter<Database>>>) -> impl Responder {
+    let report = data.report().await;
+    let live = report.live();
+
+    health_response(live, report)
+}
 
-    let mut response = if report.healthy() {
+/// Fails while the Fuel node, Ethereum or the database cannot be reached.
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "Every connection is up", body = HealthReport),
+        (status = 500, description = "A connection is down", body = HealthReport),
+    )
+)]
+#[get("/health/ready")]
+async fn ready(data: web::Data<Arc<HealthReporter<Database>>>) -> impl Responder {
+    let report = data.report().await;
+    let ready = report.ready();
+
+    health_response(ready, report)
+}
+
+fn health_response(healthy: bool, report: HealthReport) -> HttpResponse {
+    let mut response = if healthy {
         HttpResponse::Ok()
     } else {
         HttpResponse::InternalServerError()
@@ -65,13 +277,32 @@ async fn health(data: web::Data<Arc<HealthReporter>>) -> impl Responder {
     response.json(report)
 }
 
+#[utoipa::path(
+    get,
+    path = "/status",
+    tag = "status",
+    responses(
+        (status = 200, description = "Progress of the blocks on their way to L1", body = StatusReport),
+        (status = 500, description = "The status could not be determined", body = String, content_type = "text/plain"),
+    )
+)]
 #[get("/status")]
-async fn status(data: web::Data<Arc<StatusReporter<Database>>>) -> impl Responder {
+async fn status(
+    data: web::Data<Arc<StatusReporter<Database, FuelApi, SystemClock>>>,
+) -> impl Responder {
     let report = data.current_status().await?;
 
     Result::Ok(web::Json(report))
 }
 
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "status",
+    responses(
+        (status = 200, description = "Prometheus metrics in the text exposition format", body = String, content_type = "text/plain"),
+    )
+)]
 #[get("/metrics")]
 async fn metrics(registry: web::Data<Arc<Registry>>) -> impl Responder {
     let encoder = TextEncoder::new();
@@ -90,20 +321,34 @@ async fn metrics(registry: web::Data<Arc<Registry>>) -> impl Responder {
     std::result::Result::<_, InternalError<_>>::Ok(text)
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 enum HeightVariant {
     Latest,
     Specific,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct CostQueryParams {
+    /// Either the latest bundles, or the bundles from the height given by `value` on.
     variant: HeightVariant,
     value: Option<u32>,
+    /// Defaults to 100.
     limit: Option<usize>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/v1/costs",
+    tag = "costs",
+    params(CostQueryParams),
+    responses(
+        (status = 200, description = "Costs of the finalized bundles", body = Vec<BundleCost>),
+        (status = 400, description = "Invalid query, e.g. the limit is too high", body = String, content_type = "text/plain"),
+        (status = 500, description = "The costs could not be read", body = String, content_type = "text/plain"),
+    )
+)]
 #[get("/v1/costs")]
 async fn costs(
     data: web::Data<Arc<CostReporter<Database>>>,
@@ -130,6 +375,542 @@ async fn costs(
     }
 }
 
+#[derive(Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+enum Bucket {
+    Hour,
+    Day,
+    Week,
+}
+
+impl From<Bucket> for CostBucket {
+    fn from(bucket: Bucket) -> Self {
+        match bucket {
+            Bucket::Hour => Self::Hour,
+            Bucket::Day => Self::Day,
+            Bucket::Week => Self::Week,
+        }
+    }
+}
+
+#[derive(Deserialize, Default, ToSchema)]
+#[serde(rename_all = "lowercase")]
+enum Format {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct CostSummaryQueryParams {
+    /// RFC 3339 timestamps. Bundles finalized at or after `from` and before `to` are included.
+    from: Option<String>,
+    to: Option<String>,
+    from_da_height: Option<u64>,
+    to_da_height: Option<u64>,
+    /// Bundles with blocks at or above this height are included.
+    from_height: Option<u32>,
+    /// Bundles with blocks at or below this height are included.
+    to_height: Option<u32>,
+    /// Also returns the totals per hour, day or week of finalization.
+    bucket: Option<Bucket>,
+    /// Defaults to `json`.
+    #[serde(default)]
+    format: Format,
+}
+
+fn parse_timestamp(value: Option<&str>) -> services::Result<Option<DateTime<Utc>>> {
+    value
+        .map(|value| {
+            DateTime::parse_from_rfc3339(value)
+                .map(|at| at.with_timezone(&Utc))
+                .map_err(|e| services::Error::Other(format!("invalid timestamp `{value}`: {e}")))
+        })
+        .transpose()
+}
+
+/// Totals of the finalized bundle costs, optionally per hour, day or week.
+#[utoipa::path(
+    get,
+    path = "/v1/costs/summary",
+    tag = "costs",
+    params(CostSummaryQueryParams),
+    responses(
+        (status = 200, description = "Aggregated costs of the finalized bundles", content(
+            (CostAnalytics = "application/json"),
+            (String = "text/csv"),
+        )),
+        (status = 400, description = "Invalid range, or too many buckets", body = String, content_type = "text/plain"),
+        (status = 500, description = "The costs could not be read", body = String, content_type = "text/plain"),
+    )
+)]
+#[get("/v1/costs/summary")]
+async fn cost_summary(
+    data: web::Data<Arc<CostReporter<Database>>>,
+    query: web::Query<CostSummaryQueryParams>,
+) -> impl Responder {
+    let query = query.into_inner();
+
+    let response = async {
+        let filter = CostFilter {
+            finalized_from: parse_timestamp(query.from.as_deref())?,
+            finalized_before: parse_timestamp(query.to.as_deref())?,
+            from_da_height: query.from_da_height,
+            to_da_height: query.to_da_height,
+            l2_heights: HeightRange {
+                from: query.from_height,
+                to: query.to_height,
+            },
+        };
+
+        data.analytics(filter, query.bucket.map(CostBucket::from))
+            .await
+    }
+    .await;
+
+    match (response, query.format) {
+        (Ok(analytics), Format::Json) => HttpResponse::Ok().json(analytics),
+        (Ok(analytics), Format::Csv) => HttpResponse::Ok()
+            .content_type("text/csv")
+            .body(analytics.to_csv()),
+        (Err(services::Error::Other(e)), _) => {
+            HttpResponse::from_error(InternalError::new(e, StatusCode::BAD_REQUEST))
+        }
+        (Err(e), _) => HttpResponse::from_error(map_to_internal_err(e)),
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct BundleQueryParams {
+    /// Only bundles with a lower id are returned, as given by `next_before` of the previous page.
+    before: Option<u32>,
+    /// Only bundles with blocks at or above this height are returned.
+    from_height: Option<u32>,
+    /// Only bundles with blocks at or below this height are returned.
+    to_height: Option<u32>,
+    /// Defaults to 100.
+    limit: Option<usize>,
+}
+
+/// Bundles, newest first.
+#[utoipa::path(
+    get,
+    path = "/v1/bundles",
+    tag = "bundles",
+    params(BundleQueryParams),
+    responses(
+        (status = 200, description = "A page of bundles", body = BundlesPage),
+        (status = 400, description = "Invalid query, e.g. the limit is too high", body = String, content_type = "text/plain"),
+        (status = 500, description = "The bundles could not be read", body = String, content_type = "text/plain"),
+    )
+)]
+#[get("/v1/bundles")]
+async fn bundles(
+    data: web::Data<Arc<BundleReporter<Database>>>,
+    query: web::Query<BundleQueryParams>,
+) -> impl Responder {
+    let limit = query.limit.unwrap_or(100);
+
+    let heights = HeightRange {
+        from: query.from_height,
+        to: query.to_height,
+    };
+
+    match data.bundles(query.before, heights, limit).await {
+        Ok(page) => HttpResponse::Ok().json(page),
+        Err(services::Error::Other(e)) => {
+            HttpResponse::from_error(InternalError::new(e, StatusCode::BAD_REQUEST))
+        }
+        Err(e) => HttpResponse::from_error(map_to_internal_err(e)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/bundles/{id}",
+    tag = "bundles",
+    params(("id" = u32, Path, description = "Id of the bundle")),
+    responses(
+        (status = 200, description = "The bundle with its fragments and their transactions", body = BundleDetails),
+        (status = 404, description = "No such bundle"),
+        (status = 500, description = "The bundle could not be read", body = String, content_type = "text/plain"),
+    )
+)]
+#[get("/v1/bundles/{id}")]
+async fn bundle(
+    data: web::Data<Arc<BundleReporter<Database>>>,
+    id: web::Path<u32>,
+) -> impl Responder {
+    match data.bundle(id.into_inner()).await {
+        Ok(Some(bundle)) => HttpResponse::Ok().json(bundle),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(e) => HttpResponse::from_error(map_to_internal_err(e)),
+    }
+}
+
+/// Where the fuel block is on its way to L1, for both the state submission and the contract
+/// commit.
+#[utoipa::path(
+    get,
+    path = "/v1/blocks/{height}",
+    tag = "blocks",
+    params(("height" = u32, Path, description = "Height of the fuel block")),
+    responses(
+        (status = 200, description = "Where the block is on its way to L1", body = BlockReport),
+        (status = 500, description = "The block could not be looked up", body = String, content_type = "text/plain"),
+    )
+)]
+#[get("/v1/blocks/{height}")]
+async fn block(
+    data: web::Data<Arc<BlockReporter<Database, FuelApi>>>,
+    height: web::Path<u32>,
+) -> impl Responder {
+    match data.block(height.into_inner()).await {
+        Ok(report) => HttpResponse::Ok().json(report),
+        Err(e) => HttpResponse::from_error(map_to_internal_err(e)),
+    }
+}
+
+/// Number of events read from the database at once by an `/v1/events` stream.
+const EVENTS_PER_READ: usize = 100;
+/// How long an `/v1/events` stream stays silent before polling the database and sending a comment
+/// that keeps proxies from closing the connection. Polling picks up events recorded by other
+/// replicas.
+const EVENTS_KEEP_ALIVE: Duration = Duration::from_secs(5);
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct EventQueryParams {
+    /// For clients that cannot set the `Last-Event-ID` header.
+    last_event_id: Option<u64>,
+}
+
+/// Streams lifecycle events as Server-Sent Events. Clients resume after the id given in the
+/// `Last-Event-ID` header, or only receive new events if they don't give one.
+#[utoipa::path(
+    get,
+    path = "/v1/events",
+    tag = "events",
+    params(
+        EventQueryParams,
+        ("Last-Event-ID" = Option<u64>, Header, description = "Id of the last event received"),
+    ),
+    responses(
+        (status = 200, description = "Server-Sent Events named after the event type, with the event as JSON data", body = String, content_type = "text/event-stream"),
+        (status = 400, description = "Invalid `Last-Event-ID`", body = String, content_type = "text/plain"),
+        (status = 500, description = "The events could not be read", body = String, content_type = "text/plain"),
+    )
+)]
+#[get("/v1/events")]
+async fn event_stream(
+    feed: web::Data<Arc<EventFeed<Database>>>,
+    query: web::Query<EventQueryParams>,
+    req: HttpRequest,
+) -> impl Responder {
+    let last_event_id = match req.headers().get("Last-Event-ID") {
+        Some(value) => match value.to_str().ok().and_then(|id| id.trim().parse().ok()) {
+            Some(id) => Some(id),
+            None => {
+                return HttpResponse::from_error(InternalError::new(
+                    "invalid Last-Event-ID",
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        },
+        None => query.last_event_id,
+    };
+
+    let last_event_id = match last_event_id {
+        Some(id) => id,
+        None => match feed.latest_id().await {
+            Ok(id) => id,
+            Err(e) => return HttpResponse::from_error(map_to_internal_err(e)),
+        },
+    };
+
+    let feed = feed.into_inner();
+    let stream = futures::stream::unfold(last_event_id, move |last_event_id| {
+        let feed = Arc::clone(&feed);
+        async move {
+            let events = match feed.events_after(last_event_id).await {
+                Ok(events) => events,
+                Err(e) => {
+                    tracing::warn!("closing event stream: {e}");
+                    return None;
+                }
+            };
+
+            let Some(last) = events.last() else {
+                let _ = tokio::time::timeout(EVENTS_KEEP_ALIVE, feed.new_events()).await;
+                let keep_alive = web::Bytes::from_static(b": keep-alive\n\n");
+                return Some((Ok::<_, actix_web::Error>(keep_alive), last_event_id));
+            };
+
+            let last_event_id = last.id;
+            let frames = events.iter().map(sse_frame).collect::<String>();
+
+            Some((Ok(web::Bytes::from(frames)), last_event_id))
+        }
+    });
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(stream)
+}
+
+#[derive(Serialize)]
+struct EventData<'a> {
+    recorded_at: String,
+    #[serde(flatten)]
+    event: &'a Event,
+}
+
+fn sse_frame(recorded: &RecordedEvent) -> String {
+    let data = EventData {
+        recorded_at: recorded.recorded_at.to_rfc3339(),
+        event: &recorded.event,
+    };
+    let data = serde_json::to_string(&data).expect("events to serialize");
+
+    format!(
+        "id: {}\nevent: {}\ndata: {data}\n\n",
+        recorded.id,
+        recorded.event.name()
+    )
+}
+
+async fn graphql(schema: web::Data<CommitterSchema>, req: GraphQLRequest) -> GraphQLResponse {
+    schema.execute(req.into_inner()).await.into()
+}
+
+/// The GraphQL schema in SDL, for generating clients.
+#[get("/graphql/schema")]
+async fn graphql_schema_sdl(schema: web::Data<CommitterSchema>) -> impl Responder {
+    schema.sdl()
+}
+
+#[get("/openapi.json")]
+async fn openapi_json() -> impl Responder {
+    web::Json(ApiDoc::openapi())
+}
+
+#[derive(Clone)]
+struct AdminToken(String);
+
+impl AdminToken {
+    /// Takes the same time wherever the first mismatch is, so the token cannot be guessed byte by
+    /// byte.
+    fn matches(&self, candidate: &str) -> bool {
+        let expected = self.0.as_bytes();
+        let candidate = candidate.as_bytes();
+
+        expected.len() == candidate.len()
+            && expected
+                .iter()
+                .zip(candidate)
+                .fold(0, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+}
+
+async fn require_admin_token(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> actix_web::Result<ServiceResponse<impl MessageBody>> {
+    let authorized = req
+        .app_data::<web::Data<AdminToken>>()
+        .zip(
+            req.headers()
+                .get(header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer ")),
+        )
+        .is_some_and(|(token, candidate)| token.matches(candidate.trim()));
+
+    if !authorized {
+        return Err(ErrorUnauthorized("missing or invalid admin token"));
+    }
+
+    next.call(req).await
+}
+
+type AdminService = Admin<Database, SystemClock>;
+
+#[utoipa::path(
+    post,
+    path = "/admin/runners/{name}/pause",
+    tag = "admin",
+    params(("name" = String, Path, description = "Name of the runner, e.g. `state-committer`")),
+    security(("admin_token" = [])),
+    responses(
+        (status = 204, description = "The action was recorded and applied"),
+        (status = 400, description = "The action cannot be applied, e.g. unknown runner", body = String, content_type = "text/plain"),
+        (status = 401, description = "Missing or invalid admin token", body = String, content_type = "text/plain"),
+        (status = 500, description = "The action could not be recorded", body = String, content_type = "text/plain"),
+    )
+)]
+#[post("/runners/{name}/pause")]
+async fn pause_runner(
+    admin: web::Data<Arc<AdminService>>,
+    name: web::Path<String>,
+    req: HttpRequest,
+) -> impl Responder {
+    perform(&admin, AdminAction::PauseRunner(name.into_inner()), &req).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/runners/{name}/resume",
+    tag = "admin",
+    params(("name" = String, Path, description = "Name of the runner, e.g. `state-committer`")),
+    security(("admin_token" = [])),
+    responses(
+        (status = 204, description = "The action was recorded and applied"),
+        (status = 400, description = "The action cannot be applied, e.g. unknown runner", body = String, content_type = "text/plain"),
+        (status = 401, description = "Missing or invalid admin token", body = String, content_type = "text/plain"),
+        (status = 500, description = "The action could not be recorded", body = String, content_type = "text/plain"),
+    )
+)]
+#[post("/runners/{name}/resume")]
+async fn resume_runner(
+    admin: web::Data<Arc<AdminService>>,
+    name: web::Path<String>,
+    req: HttpRequest,
+) -> impl Responder {
+    perform(&admin, AdminAction::ResumeRunner(name.into_inner()), &req).await
+}
+
+/// Submits on the next run of the state committer, bypassing the fee algorithm.
+#[utoipa::path(
+    post,
+    path = "/admin/state-committer/submit",
+    tag = "admin",
+    security(("admin_token" = [])),
+    responses(
+        (status = 204, description = "The action was recorded and applied"),
+        (status = 400, description = "The action cannot be applied, e.g. unknown runner", body = String, content_type = "text/plain"),
+        (status = 401, description = "Missing or invalid admin token", body = String, content_type = "text/plain"),
+        (status = 500, description = "The action could not be recorded", body = String, content_type = "text/plain"),
+        (status = 503, description = "This instance is a standby, send the action to the leader", body = String, content_type = "text/plain"),
+    )
+)]
+#[post("/state-committer/submit")]
+async fn force_submission(admin: web::Data<Arc<AdminService>>, req: HttpRequest) -> impl Responder {
+    perform(&admin, AdminAction::ForceSubmission, &req).await
+}
+
+/// Replaces the pending blob transaction on the next run of the state committer.
+#[utoipa::path(
+    post,
+    path = "/admin/state-committer/bump-gas",
+    tag = "admin",
+    security(("admin_token" = [])),
+    responses(
+        (status = 204, description = "The action was recorded and applied"),
+        (status = 400, description = "The action cannot be applied, e.g. unknown runner", body = String, content_type = "text/plain"),
+        (status = 401, description = "Missing or invalid admin token", body = String, content_type = "text/plain"),
+        (status = 500, description = "The action could not be recorded", body = String, content_type = "text/plain"),
+        (status = 503, description = "This instance is a standby, send the action to the leader", body = String, content_type = "text/plain"),
+    )
+)]
+#[post("/state-committer/bump-gas")]
+async fn force_gas_bump(admin: web::Data<Arc<AdminService>>, req: HttpRequest) -> impl Responder {
+    perform(&admin, AdminAction::ForceGasBump, &req).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/state-pruner/run",
+    tag = "admin",
+    security(("admin_token" = [])),
+    responses(
+        (status = 204, description = "The action was recorded and applied"),
+        (status = 400, description = "The action cannot be applied, e.g. unknown runner", body = String, content_type = "text/plain"),
+        (status = 401, description = "Missing or invalid admin token", body = String, content_type = "text/plain"),
+        (status = 500, description = "The action could not be recorded", body = String, content_type = "text/plain"),
+        (status = 503, description = "This instance is a standby, send the action to the leader", body = String, content_type = "text/plain"),
+    )
+)]
+#[post("/state-pruner/run")]
+async fn trigger_pruning(admin: web::Data<Arc<AdminService>>, req: HttpRequest) -> impl Responder {
+    perform(&admin, AdminAction::TriggerPruning, &req).await
+}
+
+/// Stops the foreign commits observed so far from failing `/health` on every instance.
+#[utoipa::path(
+    post,
+    path = "/admin/commit-watcher/acknowledge",
+    tag = "admin",
+    security(("admin_token" = [])),
+    responses(
+        (status = 204, description = "The action was recorded and applied"),
+        (status = 400, description = "The action cannot be applied, e.g. unknown runner", body = String, content_type = "text/plain"),
+        (status = 401, description = "Missing or invalid admin token", body = String, content_type = "text/plain"),
+        (status = 500, description = "The action could not be recorded", body = String, content_type = "text/plain"),
+    )
+)]
+#[post("/commit-watcher/acknowledge")]
+async fn acknowledge_foreign_commits(
+    admin: web::Data<Arc<AdminService>>,
+    req: HttpRequest,
+) -> impl Responder {
+    perform(&admin, AdminAction::AcknowledgeForeignCommits, &req).await
+}
+
+async fn perform(admin: &AdminService, action: AdminAction, req: &HttpRequest) -> HttpResponse {
+    // Forwarding headers can be set by anyone, so only the address of the peer is trusted.
+    let requested_by = req.peer_addr().map(|addr| addr.ip().to_string());
+
+    match admin.perform(action, requested_by).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e @ services::Error::NotLeader) => HttpResponse::from_error(InternalError::new(
+            e.to_string(),
+            StatusCode::SERVICE_UNAVAILABLE,
+        )),
+        Err(services::Error::Other(e)) => {
+            HttpResponse::from_error(InternalError::new(e, StatusCode::BAD_REQUEST))
+        }
+        Err(e) => HttpResponse::from_error(map_to_internal_err(e)),
+    }
+}
+
 fn map_to_internal_err(error: impl std::error::Error) -> InternalError<String> {
     InternalError::new(error.to_string(), StatusCode::INTERNAL_SERVER_ERROR)
 }
+
+#[cfg(test)]
+mod tests {
+    use utoipa::OpenApi;
+
+    use super::ApiDoc;
+
+    const SNAPSHOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+
+    /// Run with `UPDATE_OPENAPI_SNAPSHOT=1` to accept the changes after checking that clients
+    /// won't break.
+    #[test]
+    fn openapi_spec_matches_snapshot() {
+        // given
+        let spec = ApiDoc::openapi()
+            .to_pretty_json()
+            .expect("spec to serialize")
+            + "\n";
+
+        if std::env::var_os("UPDATE_OPENAPI_SNAPSHOT").is_some() {
+            std::fs::write(SNAPSHOT, &spec).expect("snapshot to be writable");
+        }
+
+        // when
+        let snapshot = std::fs::read_to_string(SNAPSHOT).expect("snapshot to exist");
+
+        // then
+        assert!(
+            spec == snapshot,
+            "the OpenAPI spec no longer matches {SNAPSHOT}, rerun with UPDATE_OPENAPI_SNAPSHOT=1 \
+             if the change is intended"
+        );
+    }
+}
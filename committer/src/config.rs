@@ -1,24 +1,35 @@
 use std::{
+    collections::{BTreeMap, BTreeSet},
     net::Ipv4Addr,
     num::{NonZeroU32, NonZeroU64, NonZeroUsize},
+    path::{Path, PathBuf},
     str::FromStr,
     time::Duration,
 };
 
 use byte_unit::Byte;
-use clap::{Parser, command};
-use eth::{Address, L1Keys};
+use clap::Parser;
+use config::{Source, ValueKind};
+use eth::{AcceptablePriorityFeePercentages, Address, L1Key, L1Keys, Signers};
 use fuel_block_committer_encoding::bundle::CompressionLevel;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize, Serializer, ser::SerializeStruct};
 use services::state_committer::{AlgoConfig, FeeMultiplierRange, FeeThresholds, SmaPeriods};
 use storage::DbConfig;
+use tracing_subscriber::EnvFilter;
 use url::Url;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
-    pub eth: Eth,
+    /// Only required by roles that interact with Ethereum.
+    #[serde(default)]
+    pub eth: Option<Eth>,
     pub fuel: Fuel,
     pub app: App,
+    #[serde(default)]
+    pub internal: Internal,
+    /// Where each of the values above was loaded from.
+    #[serde(skip)]
+    pub sources: Sources,
 }
 
 impl Config {
@@ -27,34 +38,330 @@ impl Config {
             .expect("already validated via `validate` in main")
     }
 
+    /// The roles this instance runs. Unless configured explicitly, the block committer always runs
+    /// and all other roles run only if a blob key is configured.
+    pub fn roles(&self) -> BTreeSet<Role> {
+        match &self.app.roles {
+            Some(roles) => roles.iter().copied().collect(),
+            None if self
+                .eth
+                .as_ref()
+                .is_some_and(|eth| eth.l1_keys.blob.is_some()) =>
+            {
+                Role::ALL.into_iter().collect()
+            }
+            None => BTreeSet::from([Role::BlockCommitter]),
+        }
+    }
+
     pub fn validate(&self) -> crate::errors::Result<()> {
-        let keys = &self.eth.l1_keys;
-        if keys
-            .blob
-            .as_ref()
-            .is_some_and(|blob_key| blob_key == &keys.main)
+        let roles = self.roles();
+        if roles.is_empty() {
+            return Err(crate::errors::Error::Other(format!(
+                "At least one role must be configured ({})",
+                self.sources.describe(&["app.roles"])
+            )));
+        }
+
+        for role in roles {
+            self.validate_role(role)?;
+        }
+
+        if let Some(eth) = &self.eth {
+            let keys = &eth.l1_keys;
+            if keys
+                .blob
+                .as_ref()
+                .is_some_and(|blob_key| blob_key == &keys.main)
+            {
+                return Err(crate::errors::Error::Other(format!(
+                    "Wallet key and blob pool wallet key must be different ({})",
+                    self.sources
+                        .describe(&["eth.l1_keys.main", "eth.l1_keys.blob"])
+                )));
+            }
+
+            if let Err(e) = AcceptablePriorityFeePercentages::new(
+                self.app.tx_fees.min_reward_perc,
+                self.app.tx_fees.max_reward_perc,
+            ) {
+                return Err(crate::errors::Error::Other(format!(
+                    "Invalid tx fees config: {e} ({})",
+                    self.sources
+                        .describe(&["app.tx_fees.min_reward_perc", "app.tx_fees.max_reward_perc"])
+                )));
+            }
+        }
+
+        self.validate_block_id_quorum()?;
+        self.validate_fuel_request_timeout()?;
+        self.validate_leader_election()?;
+        self.validate_logging()?;
+        self.validate_admin_token()?;
+        self.validate_cost_feed()?;
+        self.validate_internal()?;
+
+        Ok(())
+    }
+
+    /// Resolves key files and keystores, catching unreadable files, wrong passwords and main and
+    /// blob keys of the same address before startup. KMS keys need AWS access and are only
+    /// resolved on startup.
+    async fn validate_l1_keys(&self) -> crate::errors::Result<()> {
+        let Some(eth) = &self.eth else {
+            return Ok(());
+        };
+        if eth.l1_keys.uses_aws() {
+            return Ok(());
+        }
+
+        if let Err(e) = Signers::for_keys(eth.l1_keys.clone()).await {
+            return Err(crate::errors::Error::Other(format!(
+                "Invalid L1 keys: {e} ({})",
+                self.sources
+                    .describe(&["eth.l1_keys.main", "eth.l1_keys.blob"])
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn validate_block_id_quorum(&self) -> crate::errors::Result<()> {
+        let num_nodes = self.fuel.additional_graphql_endpoints.len() + 1;
+        if self.fuel.block_id_quorum.get() > num_nodes {
+            return Err(crate::errors::Error::Other(format!(
+                "Block id quorum of {} cannot be reached with {num_nodes} Fuel node(s) ({})",
+                self.fuel.block_id_quorum,
+                self.sources
+                    .describe(&["fuel.block_id_quorum", "fuel.additional_graphql_endpoints"])
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn validate_fuel_request_timeout(&self) -> crate::errors::Result<()> {
+        if self.fuel.request_timeout.is_zero() {
+            return Err(crate::errors::Error::Other(format!(
+                "Fuel request_timeout must be greater than 0 ({})",
+                self.sources.describe(&["fuel.request_timeout"])
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn validate_leader_election(&self) -> crate::errors::Result<()> {
+        let leader_election = &self.app.leader_election;
+        if !leader_election.enabled {
+            return Ok(());
+        }
+
+        if leader_election.candidate_id.trim().is_empty() {
+            return Err(crate::errors::Error::Other(format!(
+                "Leader election candidate id must not be empty ({})",
+                self.sources.describe(&["app.leader_election.candidate_id"])
+            )));
+        }
+
+        if leader_election.renewal_interval.is_zero()
+            || leader_election.renewal_interval >= leader_election.lease_duration
         {
-            return Err(crate::errors::Error::Other(
-                "Wallet key and blob pool wallet key must be different".to_string(),
-            ));
+            return Err(crate::errors::Error::Other(format!(
+                "Leader election renewal_interval must be > 0 and < lease_duration ({})",
+                self.sources.describe(&[
+                    "app.leader_election.renewal_interval",
+                    "app.leader_election.lease_duration"
+                ])
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn validate_logging(&self) -> crate::errors::Result<()> {
+        let logging = &self.app.logging;
+        if let Some(filter) = &logging.filter {
+            if let Err(e) = EnvFilter::try_new(filter) {
+                return Err(crate::errors::Error::Other(format!(
+                    "Invalid log filter: {e} ({})",
+                    self.sources.describe(&["app.logging.filter"])
+                )));
+            }
+        }
+
+        if let Some(endpoint) = &logging.otlp_endpoint {
+            if let Err(e) = Url::parse(endpoint) {
+                return Err(crate::errors::Error::Other(format!(
+                    "Invalid OTLP endpoint: {e} ({})",
+                    self.sources.describe(&["app.logging.otlp_endpoint"])
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_admin_token(&self) -> crate::errors::Result<()> {
+        if let Some(token) = &self.app.admin_token {
+            if token.trim().len() < MIN_ADMIN_TOKEN_LEN {
+                return Err(crate::errors::Error::Other(format!(
+                    "Admin token must be at least {MIN_ADMIN_TOKEN_LEN} characters long ({})",
+                    self.sources.describe(&["app.admin_token"])
+                )));
+            }
         }
 
-        if self.app.bundle.fragments_to_accumulate.get() > 6 {
-            return Err(crate::errors::Error::Other(
-                "Fragments to accumulate must be <= 6".to_string(),
-            ));
+        Ok(())
+    }
+
+    fn validate_cost_feed(&self) -> crate::errors::Result<()> {
+        let cost_feed = &self.app.cost_feed;
+        let Some(url) = &cost_feed.url else {
+            return Ok(());
+        };
+
+        match Url::parse(url) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+            Ok(url) => {
+                return Err(crate::errors::Error::Other(format!(
+                    "Cost feed url must be http or https, got `{}` ({})",
+                    url.scheme(),
+                    self.sources.describe(&["app.cost_feed.url"])
+                )));
+            }
+            Err(e) => {
+                return Err(crate::errors::Error::Other(format!(
+                    "Invalid cost feed url: {e} ({})",
+                    self.sources.describe(&["app.cost_feed.url"])
+                )));
+            }
         }
 
-        if self.app.bundle.block_height_lookback < self.app.bundle.blocks_to_accumulate.get() as u32
+        if cost_feed.batch_size == 0
+            || cost_feed.check_interval.is_zero()
+            || cost_feed.request_timeout.is_zero()
         {
-            return Err(crate::errors::Error::Other(
-                "block_height_lookback must be >= blocks_to_accumulate".to_string(),
-            ));
+            return Err(crate::errors::Error::Other(format!(
+                "Cost feed batch_size, check_interval and request_timeout must be greater than 0 ({})",
+                self.sources.describe(&[
+                    "app.cost_feed.batch_size",
+                    "app.cost_feed.check_interval",
+                    "app.cost_feed.request_timeout"
+                ])
+            )));
         }
 
-        if let Err(e) = self.validated_fee_algo_config() {
+        Ok(())
+    }
+
+    fn validate_role(&self, role: Role) -> crate::errors::Result<()> {
+        if role.requires_l1() && self.eth.is_none() {
             return Err(crate::errors::Error::Other(format!(
-                "Invalid fee algo config: {e}",
+                "Role `{role}` requires the `eth` configuration ({})",
+                self.sources.describe(&["app.roles"])
+            )));
+        }
+
+        match role {
+            Role::StateCommitter => {
+                if self
+                    .eth
+                    .as_ref()
+                    .is_none_or(|eth| eth.l1_keys.blob.is_none())
+                {
+                    return Err(crate::errors::Error::Other(format!(
+                        "Role `{role}` requires a blob pool wallet key ({})",
+                        self.sources.describe(&["eth.l1_keys.blob"])
+                    )));
+                }
+
+                if self.app.bundle.fragments_to_accumulate.get() > 6 {
+                    return Err(crate::errors::Error::Other(format!(
+                        "Fragments to accumulate must be <= 6 ({})",
+                        self.sources
+                            .describe(&["app.bundle.fragments_to_accumulate"])
+                    )));
+                }
+
+                if let Err(e) = self.validated_fee_algo_config() {
+                    return Err(crate::errors::Error::Other(format!(
+                        "Invalid fee algo config: {e} ({})",
+                        self.sources.describe(&[
+                            "app.fee_algo.start_max_fee_multiplier",
+                            "app.fee_algo.end_max_fee_multiplier"
+                        ])
+                    )));
+                }
+            }
+            Role::BlockBundler => {
+                if self.app.bundle.block_height_lookback
+                    < self.app.bundle.blocks_to_accumulate.get() as u32
+                {
+                    return Err(crate::errors::Error::Other(format!(
+                        "block_height_lookback must be >= blocks_to_accumulate ({})",
+                        self.sources.describe(&[
+                            "app.bundle.block_height_lookback",
+                            "app.bundle.blocks_to_accumulate"
+                        ])
+                    )));
+                }
+            }
+            Role::BlockCommitter
+            | Role::BlockImporter
+            | Role::StateListener
+            | Role::StatePruner => {}
+        }
+
+        Ok(())
+    }
+
+    fn validate_internal(&self) -> crate::errors::Result<()> {
+        let internal = &self.internal;
+        let must_be_positive = [
+            (
+                "internal.fuel_errors_before_unhealthy",
+                internal.fuel_errors_before_unhealthy,
+            ),
+            (
+                "internal.eth_errors_before_unhealthy",
+                internal.eth_errors_before_unhealthy,
+            ),
+            (
+                "internal.balance_update_interval",
+                internal.balance_update_interval.as_millis() as usize,
+            ),
+            (
+                "internal.event_recording_interval",
+                internal.event_recording_interval.as_millis() as usize,
+            ),
+            (
+                "internal.pause_sync_interval",
+                internal.pause_sync_interval.as_millis() as usize,
+            ),
+            ("internal.cost_request_limit", internal.cost_request_limit),
+            (
+                "internal.bundle_request_limit",
+                internal.bundle_request_limit,
+            ),
+            (
+                "internal.l1_blocks_cached_for_fee_metrics_tracker",
+                internal.l1_blocks_cached_for_fee_metrics_tracker,
+            ),
+            (
+                "internal.import_batches.max_blocks",
+                internal.import_batches.max_blocks,
+            ),
+            (
+                "internal.import_batches.max_cumulative_size",
+                internal.import_batches.max_cumulative_size,
+            ),
+        ];
+
+        if let Some((key, _)) = must_be_positive.iter().find(|(_, value)| *value == 0) {
+            return Err(crate::errors::Error::Other(format!(
+                "{key} must be greater than 0 ({})",
+                self.sources.describe(&[*key])
             )));
         }
 
@@ -81,21 +388,49 @@ impl Config {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Fuel {
     /// Fuel-core GraphQL endpoint URL.
-    #[serde(deserialize_with = "parse_url")]
+    #[serde(deserialize_with = "parse_url", serialize_with = "serialize_url")]
     pub graphql_endpoint: Url,
+    /// Further Fuel-core GraphQL endpoints. Requests fail over to them, and they are used to
+    /// cross-check the id of a block before it is committed.
+    #[serde(
+        default,
+        deserialize_with = "parse_urls",
+        serialize_with = "serialize_urls"
+    )]
+    pub additional_graphql_endpoints: Vec<Url>,
+    /// Number of Fuel nodes, counting `graphql_endpoint`, that must agree on the id of a block
+    /// before it is committed.
+    #[serde(default = "default_block_id_quorum")]
+    pub block_id_quorum: NonZeroUsize,
     /// Number of concurrent requests.
     pub num_buffered_requests: NonZeroU32,
+    /// Time after which a request is retried on another endpoint.
+    #[serde(
+        default = "default_fuel_request_timeout",
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub request_timeout: Duration,
+}
+
+fn default_fuel_request_timeout() -> Duration {
+    Duration::from_secs(10)
 }
 
-#[derive(Debug, Clone, Deserialize)]
+fn default_block_id_quorum() -> NonZeroUsize {
+    NonZeroUsize::MIN
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Eth {
     /// L1 keys for state contract calls and postings.
+    #[serde(serialize_with = "serialize_redacted_l1_keys")]
     pub l1_keys: L1Keys,
     /// Ethereum RPC endpoint URL.
-    #[serde(deserialize_with = "parse_url")]
+    #[serde(deserialize_with = "parse_url", serialize_with = "serialize_url")]
     pub rpc: Url,
     /// Ethereum address of the fuel chain state contract.
     pub state_contract_address: Address,
@@ -112,47 +447,348 @@ where
     })
 }
 
+/// Accepts both a list (config files) and a comma separated string (env vars).
+fn parse_urls<'de, D>(deserializer: D) -> Result<Vec<Url>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Urls {
+        List(Vec<String>),
+        CommaSeparated(String),
+    }
+
+    let urls = match Urls::deserialize(deserializer)? {
+        Urls::List(urls) => urls,
+        Urls::CommaSeparated(urls) => urls.split(',').map(str::to_owned).collect(),
+    };
+
+    urls.iter()
+        .map(|url| url.trim())
+        .filter(|url| !url.is_empty())
+        .map(|url| {
+            Url::from_str(url)
+                .map_err(|e| serde::de::Error::custom(format!("Failed to parse URL '{url}': {e};")))
+        })
+        .collect()
+}
+
 #[allow(dead_code)]
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct App {
     /// Server port.
     pub port: u16,
     /// Server IPv4 address.
     pub host: Ipv4Addr,
     /// Postgres database configuration.
+    #[serde(serialize_with = "serialize_redacted_db")]
     pub db: DbConfig,
     /// Interval for checking new fuel blocks.
-    #[serde(deserialize_with = "human_readable_duration")]
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub block_check_interval: Duration,
     /// Interval for checking finalized L1 transactions.
-    #[serde(deserialize_with = "human_readable_duration")]
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub tx_finalization_check_interval: Duration,
     /// Interval for checking L1 fees.
-    #[serde(deserialize_with = "human_readable_duration")]
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub l1_fee_check_interval: Duration,
     /// Number of L1 blocks to wait before finalizing a transaction.
     pub num_blocks_to_finalize_tx: u64,
     /// Timeout after which a pending transaction is bumped.
-    #[serde(deserialize_with = "human_readable_duration")]
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub gas_bump_timeout: Duration,
     /// Settings for L1 transaction fees.
     pub tx_fees: TxFeesConfig,
     /// Settings for bundling blocks.
     pub bundle: BundleConfig,
     /// Timeout for sending transaction requests.
-    #[serde(deserialize_with = "human_readable_duration")]
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub send_tx_request_timeout: Duration,
     /// Retention period for state pruner.
-    #[serde(deserialize_with = "human_readable_duration")]
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub state_pruner_retention: Duration,
     /// Interval for running the state pruner.
-    #[serde(deserialize_with = "human_readable_duration")]
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub state_pruner_run_interval: Duration,
     /// Configuration for the fee tracking algorithm.
     pub fee_algo: FeeAlgoConfig,
+    /// Leader election between replicas sharing the same database.
+    #[serde(default)]
+    pub leader_election: LeaderElectionConfig,
+    /// Log output and trace export.
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    /// Services run by this instance. See [`Config::roles`] for the default.
+    #[serde(default, deserialize_with = "parse_roles")]
+    pub roles: Option<Vec<Role>>,
+    /// How long to wait on shutdown for the services to finish their current run.
+    #[serde(
+        default = "default_shutdown_timeout",
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub shutdown_timeout: Duration,
+    /// Upper bound for the delay between runs of a service that keeps failing.
+    #[serde(
+        default = "default_max_error_backoff",
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub max_error_backoff: Duration,
+    /// A service is reported as not live if it didn't succeed within this many polling intervals.
+    #[serde(default = "default_runner_unhealthy_after_intervals")]
+    pub runner_unhealthy_after_intervals: NonZeroU32,
+    /// Bearer token required by the `/admin` endpoints. They are disabled when not set.
+    #[serde(default, serialize_with = "serialize_redacted_token")]
+    pub admin_token: Option<String>,
+    /// Pushes the cost of every finalized bundle to a webhook.
+    #[serde(default)]
+    pub cost_feed: CostFeedConfig,
+    /// Settings for the block commit transactions.
+    #[serde(default)]
+    pub block_commit: BlockCommitConfig,
+}
+
+fn default_shutdown_timeout() -> Duration {
+    Duration::from_secs(25)
+}
+
+fn default_max_error_backoff() -> Duration {
+    Duration::from_secs(5 * 60)
+}
+
+fn default_runner_unhealthy_after_intervals() -> NonZeroU32 {
+    NonZeroU32::new(10).expect("not zero")
+}
+
+/// With leader election enabled, only the replica holding a role's lease runs the role's services
+/// that submit to L1, bundle or prune. Every role has its own lease, so the roles can be spread over
+/// several replicas. The others stay on standby, serve the API and take over once the lease expires.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LeaderElectionConfig {
+    pub enabled: bool,
+    /// Unique identifier of this replica. Defaults to the `HOSTNAME` env var.
+    pub candidate_id: String,
+    /// How long a lease stays valid without being renewed.
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub lease_duration: Duration,
+    /// How often the leases are acquired or renewed. Must be shorter than `lease_duration`.
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub renewal_interval: Duration,
 }
 
-#[derive(Debug, Clone, Deserialize, Copy)]
+impl Default for LeaderElectionConfig {
+    fn default() -> Self {
+        let candidate_id = std::env::var("HOSTNAME")
+            .unwrap_or_else(|_| format!("committer-{}", std::process::id()));
+
+        Self {
+            enabled: false,
+            candidate_id,
+            lease_duration: Duration::from_secs(30),
+            renewal_interval: Duration::from_secs(10),
+        }
+    }
+}
+
+/// The cost of every finalized bundle is POSTed as JSON to `url`, at least once and in the order of
+/// the bundles. Costs that cannot be delivered are retried until they are.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CostFeedConfig {
+    /// Webhook the costs are pushed to. The feed is disabled if unset.
+    pub url: Option<String>,
+    /// Sent as a bearer token, if set.
+    #[serde(serialize_with = "serialize_redacted_token")]
+    pub auth_token: Option<String>,
+    /// How often to check for costs to deliver.
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub check_interval: Duration,
+    /// How long to wait for the webhook to accept a cost.
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub request_timeout: Duration,
+    /// Maximum number of costs delivered per check.
+    pub batch_size: usize,
+}
+
+impl Default for CostFeedConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            auth_token: None,
+            check_interval: Duration::from_secs(2),
+            request_timeout: Duration::from_secs(10),
+            batch_size: 100,
+        }
+    }
+}
+
+/// Block commit txs that stay pending are replaced by ones with the same nonce and higher fees.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BlockCommitConfig {
+    /// Timeout after which a pending commit tx is bumped.
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub gas_bump_timeout: Duration,
+    /// Maximum fee, in wei, a commit tx replacing a pending one may cost. Unlimited if unset.
+    pub max_fee: Option<u64>,
+    /// How often recent commits are compared to the block hashes committed on L1. They are also
+    /// compared at startup.
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub reconciliation_interval: Duration,
+    /// Number of most recent commits compared to L1.
+    pub reconciliation_lookback: usize,
+    /// Whether to commit the current epoch again if its commit is missing or foreign on L1.
+    pub recommit_on_mismatch: bool,
+}
+
+impl Default for BlockCommitConfig {
+    fn default() -> Self {
+        Self {
+            gas_bump_timeout: Duration::from_secs(120),
+            max_fee: None,
+            reconciliation_interval: Duration::from_secs(10 * 60),
+            reconciliation_lookback: 10,
+            recommit_on_mismatch: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    pub format: LogFormat,
+    /// `EnvFilter` directives, e.g. `info,storage=debug`. Falls back to `RUST_LOG`, then `info`.
+    pub filter: Option<String>,
+    /// OTLP/HTTP endpoint traces are exported to. Traces are not exported if unset.
+    pub otlp_endpoint: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    #[default]
+    Json,
+    Pretty,
+    Compact,
+}
+
+/// A service that can be enabled per instance, allowing e.g. the CPU heavy bundling to run
+/// separately from the services holding the signing keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    BlockCommitter,
+    BlockImporter,
+    BlockBundler,
+    StateCommitter,
+    StateListener,
+    StatePruner,
+}
+
+impl Role {
+    pub const ALL: [Self; 6] = [
+        Self::BlockCommitter,
+        Self::BlockImporter,
+        Self::BlockBundler,
+        Self::StateCommitter,
+        Self::StateListener,
+        Self::StatePruner,
+    ];
+
+    /// As configured, also names the lease of the role's services if they only run on the leader.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::BlockCommitter => "block_committer",
+            Self::BlockImporter => "block_importer",
+            Self::BlockBundler => "block_bundler",
+            Self::StateCommitter => "state_committer",
+            Self::StateListener => "state_listener",
+            Self::StatePruner => "state_pruner",
+        }
+    }
+
+    /// Whether the role needs an Ethereum connection and therefore the `eth` configuration.
+    pub fn requires_l1(&self) -> bool {
+        matches!(
+            self,
+            Self::BlockCommitter | Self::StateCommitter | Self::StateListener
+        )
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Accepts both a list (config files) and a comma separated string (env vars).
+fn parse_roles<'de, D>(deserializer: D) -> Result<Option<Vec<Role>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Roles {
+        List(Vec<Role>),
+        CommaSeparated(String),
+    }
+
+    match Roles::deserialize(deserializer)? {
+        Roles::List(roles) => Ok(Some(roles)),
+        Roles::CommaSeparated(roles) => roles
+            .split(',')
+            .map(str::trim)
+            .filter(|role| !role.is_empty())
+            .map(|role| Role::deserialize(serde::de::value::StrDeserializer::<D::Error>::new(role)))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some),
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Copy)]
 pub struct TxFeesConfig {
     /// Maximum allowed gas fee in wei.
     pub max: u64,
@@ -163,7 +799,7 @@ pub struct TxFeesConfig {
 }
 
 /// Fee algorithm configuration for the StateCommitter.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct FeeAlgoConfig {
     /// Short-term SMA period (in blocks).
     pub short_sma_blocks: NonZeroU64,
@@ -182,19 +818,25 @@ pub struct FeeAlgoConfig {
 /// Bundling configuration for fuel block submission to L1.
 ///
 /// This configuration controls how blocks are accumulated and bundled.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct BundleConfig {
     /// Time to wait for additional blocks before starting bundling.
     ///
     /// This timeout starts from the last time a bundle was created or from app startup.
     /// Bundling will occur when this timeout expires, even if byte or block thresholds aren’t met.
-    #[serde(deserialize_with = "human_readable_duration")]
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub accumulation_timeout: Duration,
 
     /// Byte threshold to trigger bundling immediately.
     ///
     /// If this many bytes are accumulated before the timeout, bundling starts right away.
-    #[serde(deserialize_with = "human_readable_bytes")]
+    #[serde(
+        deserialize_with = "human_readable_bytes",
+        serialize_with = "serialize_bytes"
+    )]
     pub bytes_to_accumulate: NonZeroUsize,
 
     /// Block count threshold to trigger bundling if enough unbundled blocks are present.
@@ -206,7 +848,10 @@ pub struct BundleConfig {
     /// Maximum time to search for the optimal bundle size.
     ///
     /// When this duration expires, bundling proceeds with the best size found.
-    #[serde(deserialize_with = "human_readable_duration")]
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub optimization_timeout: Duration,
 
     /// Initial step size for the optimization search.
@@ -218,7 +863,10 @@ pub struct BundleConfig {
     /// Timeout to wait for additional fragments before submitting to L1.
     ///
     /// Starts from the last submitted fragment; if no new ones arrive, the accumulated fragments are sent.
-    #[serde(deserialize_with = "human_readable_duration")]
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub fragment_accumulation_timeout: Duration,
 
     /// Number of fragments to accumulate before submission.
@@ -248,10 +896,14 @@ pub struct BundleConfig {
     /// Options:
     /// - `"disabled"`: No compression.
     /// - `"min"` to `"max"`: Increasingly aggressive compression.
+    #[serde(serialize_with = "serialize_compression_level")]
     pub compression_level: CompressionLevel,
 
     /// Interval to check if a new bundle can be created.
-    #[serde(deserialize_with = "human_readable_duration")]
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub new_bundle_check_interval: Duration,
 }
 
@@ -286,19 +938,137 @@ where
     Ok(NonZeroUsize::new(num_bytes).expect("just checked"))
 }
 
-#[derive(Debug, Clone)]
+/// Guards against tokens short enough to be guessed.
+const MIN_ADMIN_TOKEN_LEN: usize = 16;
+
+/// Replaces secrets when the configuration is printed.
+const REDACTED: &str = "<redacted>";
+
+fn serialize_url<S>(url: &Url, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(url.as_str())
+}
+
+fn serialize_urls<S>(urls: &[Url], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_seq(urls.iter().map(Url::as_str))
+}
+
+fn serialize_duration<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&humantime::format_duration(*duration).to_string())
+}
+
+fn serialize_bytes<S>(num_bytes: &NonZeroUsize, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&format!("{num_bytes}B"))
+}
+
+fn serialize_compression_level<S>(
+    level: &CompressionLevel,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    // The `Debug` names match the values accepted by `CompressionLevel::from_str`.
+    serializer.serialize_str(&format!("{level:?}").to_lowercase())
+}
+
+fn serialize_redacted_l1_keys<S>(keys: &L1Keys, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let redact = |key: &L1Key| match key {
+        L1Key::Kms(arn) => format!("Kms({arn})"),
+        L1Key::Private(_) => format!("Private({REDACTED})"),
+        L1Key::PrivateFile(path) => format!("PrivateFile({})", path.display()),
+        L1Key::Keystore {
+            path,
+            password_file,
+        } => format!("Keystore({}, {})", path.display(), password_file.display()),
+    };
+
+    let mut state = serializer.serialize_struct("L1Keys", 2)?;
+    state.serialize_field("main", &redact(&keys.main))?;
+    state.serialize_field("blob", &keys.blob.as_ref().map(redact))?;
+    state.end()
+}
+
+fn serialize_redacted_token<S>(token: &Option<String>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match token {
+        Some(_) => serializer.serialize_some(REDACTED),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn serialize_redacted_db<S>(db: &DbConfig, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut state = serializer.serialize_struct("DbConfig", 7)?;
+    state.serialize_field("host", &db.host)?;
+    state.serialize_field("port", &db.port)?;
+    state.serialize_field("username", &db.username)?;
+    state.serialize_field("password", REDACTED)?;
+    state.serialize_field("database", &db.database)?;
+    state.serialize_field("max_connections", &db.max_connections)?;
+    state.serialize_field("use_ssl", &db.use_ssl)?;
+    state.end()
+}
+
+/// Tuning knobs that rarely need changing. Every field is optional and falls back to its default.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Internal {
+    /// Number of consecutive Fuel request failures before the connection is reported unhealthy.
     pub fuel_errors_before_unhealthy: usize,
+    /// Number of consecutive Ethereum request failures before the connection is reported unhealthy.
     pub eth_errors_before_unhealthy: usize,
+    /// Interval for updating the wallet balance metrics.
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub balance_update_interval: Duration,
+    /// Interval at which published lifecycle events are stored and pushed to `/v1/events`.
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub event_recording_interval: Duration,
+    /// Interval at which runners paused through the admin API, possibly by another instance, are
+    /// paused or resumed on this one.
+    #[serde(
+        deserialize_with = "human_readable_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub pause_sync_interval: Duration,
+    /// Maximum number of bundle costs returned by a single `/v1/costs` request.
     pub cost_request_limit: usize,
+    /// Maximum number of bundles returned by a single `/v1/bundles` request.
+    pub bundle_request_limit: usize,
+    /// Number of L1 blocks whose fees are cached for the fee metrics tracker and state committer.
     pub l1_blocks_cached_for_fee_metrics_tracker: usize,
+    /// Batching of imported fuel blocks.
     pub import_batches: ImportBatches,
 }
 
 /// Manages batching of incoming fuel blocks before importing them into the database, optimizing memory usage
 /// and reducing the number of generated logs.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
 pub struct ImportBatches {
     pub max_blocks: usize,
     pub max_cumulative_size: usize,
@@ -321,34 +1091,940 @@ impl Default for Internal {
             fuel_errors_before_unhealthy: 3,
             eth_errors_before_unhealthy: 3,
             balance_update_interval: Duration::from_secs(10),
+            event_recording_interval: Duration::from_secs(1),
+            pause_sync_interval: Duration::from_secs(5),
             cost_request_limit: 1000,
+            bundle_request_limit: 1000,
             l1_blocks_cached_for_fee_metrics_tracker: ETH_BLOCKS_PER_DAY,
             import_batches: ImportBatches::default(),
         }
     }
 }
 
+/// Origin the `config` crate assigns to values coming from environment variables.
+const ENV_ORIGIN: &str = "the environment";
+const ENV_PREFIX: &str = "COMMITTER";
+const ENV_SEPARATOR: &str = "__";
+
+/// Maps every loaded configuration key (e.g. `app.bundle.fragments_to_accumulate`) to the source it
+/// was read from, so that validation errors can point at the offending env var or file.
+#[derive(Debug, Clone, Default)]
+pub struct Sources {
+    origins: BTreeMap<String, Origin>,
+}
+
+#[derive(Debug, Clone)]
+enum Origin {
+    Env,
+    File(PathBuf),
+}
+
+impl Sources {
+    fn collect(config: &config::Config, file: Option<&Path>) -> crate::errors::Result<Self> {
+        let mut origins = BTreeMap::new();
+        for (key, value) in config.collect()? {
+            Self::record(key, value, file, &mut origins);
+        }
+
+        Ok(Self { origins })
+    }
+
+    fn record(
+        key: String,
+        value: config::Value,
+        file: Option<&Path>,
+        origins: &mut BTreeMap<String, Origin>,
+    ) {
+        // The `config` crate reports file origins relative to the working directory, so we
+        // use the path given by the user instead.
+        let origin = match (value.origin(), file) {
+            (Some(ENV_ORIGIN), _) => Some(Origin::Env),
+            (Some(_), Some(file)) => Some(Origin::File(file.to_path_buf())),
+            _ => None,
+        };
+        if let Some(origin) = origin {
+            origins.insert(key.clone(), origin);
+        }
+
+        if let ValueKind::Table(table) = value.kind {
+            for (child_key, child) in table {
+                Self::record(format!("{key}.{child_key}"), child, file, origins);
+            }
+        }
+    }
+
+    /// Renders the given keys together with the source they were loaded from.
+    pub fn describe(&self, keys: &[&str]) -> String {
+        keys.iter()
+            .map(|key| match self.origins.get(*key) {
+                Some(Origin::Env) => {
+                    let var = std::iter::once(ENV_PREFIX.to_string())
+                        .chain(key.split('.').map(str::to_uppercase))
+                        .collect::<Vec<_>>()
+                        .join(ENV_SEPARATOR);
+                    format!("`{key}` set via env var `{var}`")
+                }
+                Some(Origin::File(path)) => {
+                    format!("`{key}` set in file `{}`", path.display())
+                }
+                None => format!("`{key}` not set"),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 #[derive(Parser)]
 #[command(
     name = "fuel-block-committer",
     version,
     about,
-    propagate_version = true
+    propagate_version = true,
+    args_conflicts_with_subcommands = true
 )]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
     #[arg(
         value_name = "FILE",
-        help = "Path to the configuration file (currently unused, all configuration done via ENV)."
+        help = "Path to a TOML or YAML configuration file. Values set via `COMMITTER__` env vars take precedence."
     )]
-    config_path: Option<String>,
+    config_path: Option<PathBuf>,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Validates the configuration without connecting to any service and prints it as JSON with
+    /// secrets redacted.
+    CheckConfig {
+        #[arg(
+            value_name = "FILE",
+            help = "Path to a TOML or YAML configuration file. Values set via `COMMITTER__` env vars take precedence."
+        )]
+        config_path: Option<PathBuf>,
+        #[arg(
+            long,
+            help = "Also read the key files and keystores, checking that they hold valid keys and that the main and blob keys have different addresses."
+        )]
+        resolve_keys: bool,
+    },
+}
+
+/// What the binary was asked to do.
+pub enum Invocation {
+    Run(Config),
+    CheckConfig { config: Config, resolve_keys: bool },
+}
+
+pub fn parse() -> crate::errors::Result<Invocation> {
+    let cli = Cli::parse();
+    let env = config::Environment::with_prefix(ENV_PREFIX).separator(ENV_SEPARATOR);
+
+    let invocation = match cli.command {
+        Some(Command::CheckConfig {
+            config_path,
+            resolve_keys,
+        }) => Invocation::CheckConfig {
+            config: load(config_path.as_deref(), env)?,
+            resolve_keys,
+        },
+        None => Invocation::Run(load(cli.config_path.as_deref(), env)?),
+    };
+
+    Ok(invocation)
+}
+
+/// Validates the configuration and renders it as pretty JSON. Private keys and the database
+/// password are redacted. Key files are only read if `resolve_keys` is set, since they are usually
+/// not available where the configuration is checked.
+pub async fn check(config: &Config, resolve_keys: bool) -> crate::errors::Result<String> {
+    config.validate()?;
+    if resolve_keys {
+        config.validate_l1_keys().await?;
+    }
+
+    Ok(serde_json::to_string_pretty(config)?)
+}
+
+/// Loads the configuration file (if given) as the base layer and overrides it with values from the
+/// environment.
+fn load(config_path: Option<&Path>, env: config::Environment) -> crate::errors::Result<Config> {
+    let mut builder = config::Config::builder();
+    if let Some(path) = config_path {
+        builder = builder.add_source(config::File::from(path).required(true));
+    }
+
+    let raw = builder.add_source(env).build()?;
+    let sources = Sources::collect(&raw, config_path)?;
+
+    let mut config: Config = raw.try_deserialize()?;
+    config.sources = sources;
+
+    Ok(config)
 }
 
-pub fn parse() -> crate::errors::Result<Config> {
-    let _ = Cli::parse();
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const TOML_CONFIG: &str = r#"
+[eth]
+rpc = "ws://localhost:8545"
+state_contract_address = "0x0000000000000000000000000000000000000001"
+
+[eth.l1_keys]
+main = "Kms(main-key-arn)"
+blob = "Kms(blob-key-arn)"
+
+[fuel]
+graphql_endpoint = "http://localhost:4000/graphql"
+num_buffered_requests = 5
+
+[app]
+port = 8080
+host = "127.0.0.1"
+block_check_interval = "5s"
+tx_finalization_check_interval = "5s"
+l1_fee_check_interval = "10s"
+num_blocks_to_finalize_tx = 3
+gas_bump_timeout = "300s"
+send_tx_request_timeout = "10s"
+state_pruner_retention = "1h"
+state_pruner_run_interval = "30m"
+
+[app.db]
+host = "localhost"
+port = 5432
+username = "username"
+password = "password"
+database = "test"
+max_connections = 10
+use_ssl = false
+
+[app.tx_fees]
+max = 4000000000000000
+min_reward_perc = 20.0
+max_reward_perc = 30.0
+
+[app.bundle]
+accumulation_timeout = "30s"
+bytes_to_accumulate = "1MB"
+blocks_to_accumulate = 5
+max_fragments_per_bundle = 10
+optimization_timeout = "60s"
+optimization_step = 100
+fragment_accumulation_timeout = "30s"
+fragments_to_accumulate = 6
+block_height_lookback = 100
+compression_level = "min"
+new_bundle_check_interval = "15s"
+
+[app.fee_algo]
+short_sma_blocks = 25
+long_sma_blocks = 300
+max_l2_blocks_behind = 28800
+start_max_fee_multiplier = 0.8
+end_max_fee_multiplier = 1.2
+always_acceptable_fee = 1000000000000000
+"#;
+
+    fn write_config(dir: &TempDir, name: &str, contents: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn env(vars: &[(&str, &str)]) -> config::Environment {
+        let vars = vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        config::Environment::with_prefix(ENV_PREFIX)
+            .separator(ENV_SEPARATOR)
+            .source(Some(vars))
+    }
+
+    #[test]
+    fn env_vars_override_values_from_file() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+
+        // when
+        let config = load(Some(&path), env(&[("COMMITTER__APP__PORT", "9090")])).unwrap();
+
+        // then
+        config.validate().unwrap();
+        assert_eq!(config.app.port, 9090);
+        assert_eq!(config.app.bundle.block_height_lookback, 100);
+        assert_eq!(config.app.gas_bump_timeout, Duration::from_secs(300));
+    }
+
+    #[test]
+    fn yaml_files_are_supported() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let yaml = r#"
+eth:
+  rpc: "ws://localhost:8545"
+  state_contract_address: "0x0000000000000000000000000000000000000001"
+  l1_keys:
+    main: "Kms(main-key-arn)"
+fuel:
+  graphql_endpoint: "http://localhost:4000/graphql"
+  num_buffered_requests: 5
+"#;
+        let path = write_config(&dir, "committer.yaml", yaml);
+        let app_from_env = [
+            ("COMMITTER__APP__PORT", "8080"),
+            ("COMMITTER__APP__HOST", "127.0.0.1"),
+            ("COMMITTER__APP__BLOCK_CHECK_INTERVAL", "5s"),
+            ("COMMITTER__APP__TX_FINALIZATION_CHECK_INTERVAL", "5s"),
+            ("COMMITTER__APP__L1_FEE_CHECK_INTERVAL", "10s"),
+            ("COMMITTER__APP__NUM_BLOCKS_TO_FINALIZE_TX", "3"),
+            ("COMMITTER__APP__GAS_BUMP_TIMEOUT", "300s"),
+            ("COMMITTER__APP__SEND_TX_REQUEST_TIMEOUT", "10s"),
+            ("COMMITTER__APP__STATE_PRUNER_RETENTION", "1h"),
+            ("COMMITTER__APP__STATE_PRUNER_RUN_INTERVAL", "30m"),
+            ("COMMITTER__APP__DB__HOST", "localhost"),
+            ("COMMITTER__APP__DB__PORT", "5432"),
+            ("COMMITTER__APP__DB__USERNAME", "username"),
+            ("COMMITTER__APP__DB__PASSWORD", "password"),
+            ("COMMITTER__APP__DB__DATABASE", "test"),
+            ("COMMITTER__APP__DB__MAX_CONNECTIONS", "10"),
+            ("COMMITTER__APP__DB__USE_SSL", "false"),
+            ("COMMITTER__APP__TX_FEES__MAX", "4000000000000000"),
+            ("COMMITTER__APP__TX_FEES__MIN_REWARD_PERC", "20"),
+            ("COMMITTER__APP__TX_FEES__MAX_REWARD_PERC", "30"),
+            ("COMMITTER__APP__BUNDLE__ACCUMULATION_TIMEOUT", "30s"),
+            ("COMMITTER__APP__BUNDLE__BYTES_TO_ACCUMULATE", "1MB"),
+            ("COMMITTER__APP__BUNDLE__BLOCKS_TO_ACCUMULATE", "5"),
+            ("COMMITTER__APP__BUNDLE__MAX_FRAGMENTS_PER_BUNDLE", "10"),
+            ("COMMITTER__APP__BUNDLE__OPTIMIZATION_TIMEOUT", "60s"),
+            ("COMMITTER__APP__BUNDLE__OPTIMIZATION_STEP", "100"),
+            (
+                "COMMITTER__APP__BUNDLE__FRAGMENT_ACCUMULATION_TIMEOUT",
+                "30s",
+            ),
+            ("COMMITTER__APP__BUNDLE__FRAGMENTS_TO_ACCUMULATE", "6"),
+            ("COMMITTER__APP__BUNDLE__BLOCK_HEIGHT_LOOKBACK", "100"),
+            ("COMMITTER__APP__BUNDLE__COMPRESSION_LEVEL", "min"),
+            ("COMMITTER__APP__BUNDLE__NEW_BUNDLE_CHECK_INTERVAL", "15s"),
+            ("COMMITTER__APP__FEE_ALGO__SHORT_SMA_BLOCKS", "25"),
+            ("COMMITTER__APP__FEE_ALGO__LONG_SMA_BLOCKS", "300"),
+            ("COMMITTER__APP__FEE_ALGO__MAX_L2_BLOCKS_BEHIND", "28800"),
+            ("COMMITTER__APP__FEE_ALGO__START_MAX_FEE_MULTIPLIER", "0.8"),
+            ("COMMITTER__APP__FEE_ALGO__END_MAX_FEE_MULTIPLIER", "1.2"),
+            (
+                "COMMITTER__APP__FEE_ALGO__ALWAYS_ACCEPTABLE_FEE",
+                "1000000000000000",
+            ),
+        ];
+
+        // when
+        let config = load(Some(&path), env(&app_from_env)).unwrap();
+
+        // then
+        config.validate().unwrap();
+        assert_eq!(config.fuel.num_buffered_requests.get(), 5);
+        assert!(config.eth.unwrap().l1_keys.blob.is_none());
+    }
+
+    #[test]
+    fn validation_error_names_the_env_var_that_set_the_value() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+        let config = load(
+            Some(&path),
+            env(&[("COMMITTER__APP__BUNDLE__FRAGMENTS_TO_ACCUMULATE", "7")]),
+        )
+        .unwrap();
+
+        // when
+        let err = config.validate().unwrap_err();
+
+        // then
+        let msg = err.to_string();
+        assert!(
+            msg.contains("set via env var `COMMITTER__APP__BUNDLE__FRAGMENTS_TO_ACCUMULATE`"),
+            "{msg}"
+        );
+    }
+
+    #[test]
+    fn validation_error_names_the_file_that_set_the_value() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let toml = TOML_CONFIG.replace("block_height_lookback = 100", "block_height_lookback = 1");
+        let path = write_config(&dir, "committer.toml", &toml);
+        let config = load(Some(&path), env(&[])).unwrap();
+
+        // when
+        let err = config.validate().unwrap_err();
+
+        // then
+        let msg = err.to_string();
+        let expected = format!(
+            "`app.bundle.block_height_lookback` set in file `{}`",
+            path.display()
+        );
+        assert!(msg.contains(&expected), "{msg}");
+    }
+
+    #[tokio::test]
+    async fn check_redacts_secrets() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let toml = TOML_CONFIG
+            .replace("Kms(main-key-arn)", "Private(0xdeadbeef)")
+            .replace(r#"password = "password""#, r#"password = "hunter2""#);
+        let path = write_config(&dir, "committer.toml", &toml);
+        let config = load(Some(&path), env(&[])).unwrap();
 
-    let config = config::Config::builder()
-        .add_source(config::Environment::with_prefix("COMMITTER").separator("__"))
-        .build()?;
+        // when
+        let rendered = check(&config, false).await.unwrap();
 
-    Ok(config.try_deserialize()?)
+        // then
+        assert!(!rendered.contains("deadbeef"), "{rendered}");
+        assert!(!rendered.contains("hunter2"), "{rendered}");
+
+        let json: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(json["eth"]["l1_keys"]["main"], "Private(<redacted>)");
+        assert_eq!(json["eth"]["l1_keys"]["blob"], "Kms(blob-key-arn)");
+        assert_eq!(json["app"]["db"]["password"], "<redacted>");
+        assert_eq!(json["app"]["db"]["username"], "username");
+        assert_eq!(json["app"]["gas_bump_timeout"], "5m");
+        assert_eq!(json["app"]["bundle"]["bytes_to_accumulate"], "1000000B");
+        assert_eq!(json["app"]["bundle"]["compression_level"], "min");
+    }
+
+    #[tokio::test]
+    async fn admin_token_is_redacted_and_must_not_be_short() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+        let with_token = |token: &str| {
+            load(Some(&path), env(&[("COMMITTER__APP__ADMIN_TOKEN", token)])).unwrap()
+        };
+
+        // when
+        let rendered = check(&with_token("a-long-enough-admin-token"), false)
+            .await
+            .unwrap();
+        let err = check(&with_token("short"), false).await.unwrap_err();
+
+        // then
+        assert!(
+            !rendered.contains("a-long-enough-admin-token"),
+            "{rendered}"
+        );
+        let json: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(json["app"]["admin_token"], "<redacted>");
+
+        let msg = err.to_string();
+        assert!(msg.contains("Admin token must be at least"), "{msg}");
+        assert!(
+            msg.contains("set via env var `COMMITTER__APP__ADMIN_TOKEN`"),
+            "{msg}"
+        );
+    }
+
+    #[tokio::test]
+    async fn check_rejects_invalid_reward_percentages() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+        let config = load(
+            Some(&path),
+            env(&[("COMMITTER__APP__TX_FEES__MIN_REWARD_PERC", "40")]),
+        )
+        .unwrap();
+
+        // when
+        let err = check(&config, false).await.unwrap_err();
+
+        // then
+        let msg = err.to_string();
+        assert!(msg.contains("Invalid tx fees config"), "{msg}");
+        assert!(
+            msg.contains("set via env var `COMMITTER__APP__TX_FEES__MIN_REWARD_PERC`"),
+            "{msg}"
+        );
+    }
+
+    #[tokio::test]
+    async fn check_resolves_key_files_only_when_asked() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+        let key = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+        let main_key = dir.path().join("main");
+        fs::write(&main_key, key).unwrap();
+        let blob_key = dir.path().join("blob");
+        fs::write(&blob_key, format!("{key}\n")).unwrap();
+        let main = format!("PrivateFile({})", main_key.display());
+        let blob = format!("PrivateFile({})", blob_key.display());
+        let missing = format!("PrivateFile({})", dir.path().join("missing").display());
+
+        let same_key = load(
+            Some(&path),
+            env(&[
+                ("COMMITTER__ETH__L1_KEYS__MAIN", &main),
+                ("COMMITTER__ETH__L1_KEYS__BLOB", &blob),
+            ]),
+        )
+        .unwrap();
+        let unreadable_key = load(
+            Some(&path),
+            env(&[
+                ("COMMITTER__ETH__L1_KEYS__MAIN", &missing),
+                ("COMMITTER__ETH__L1_KEYS__BLOB", &blob),
+            ]),
+        )
+        .unwrap();
+
+        // when
+        let same_key_err = check(&same_key, true).await.unwrap_err();
+        let unreadable_key_err = check(&unreadable_key, true).await.unwrap_err();
+        let unresolved = check(&unreadable_key, false).await;
+
+        // then
+        assert!(unresolved.is_ok());
+        let msg = same_key_err.to_string();
+        assert!(msg.contains("same address"), "{msg}");
+        assert!(
+            msg.contains("set via env var `COMMITTER__ETH__L1_KEYS__MAIN`"),
+            "{msg}"
+        );
+        let msg = unreadable_key_err.to_string();
+        assert!(msg.contains("Could not read"), "{msg}");
+    }
+
+    #[test]
+    fn internal_settings_default_when_not_configured() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+
+        // when
+        let config = load(Some(&path), env(&[])).unwrap();
+
+        // then
+        let defaults = Internal::default();
+        assert_eq!(
+            config.internal.cost_request_limit,
+            defaults.cost_request_limit
+        );
+        assert_eq!(
+            config.internal.balance_update_interval,
+            defaults.balance_update_interval
+        );
+        assert_eq!(
+            config.internal.import_batches.max_blocks,
+            defaults.import_batches.max_blocks
+        );
+    }
+
+    #[test]
+    fn internal_settings_can_be_partially_overridden() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let toml = format!("{TOML_CONFIG}\n[internal.import_batches]\nmax_blocks = 100\n");
+        let path = write_config(&dir, "committer.toml", &toml);
+
+        // when
+        let config = load(
+            Some(&path),
+            env(&[("COMMITTER__INTERNAL__BALANCE_UPDATE_INTERVAL", "1m")]),
+        )
+        .unwrap();
+
+        // then
+        config.validate().unwrap();
+        assert_eq!(config.internal.import_batches.max_blocks, 100);
+        assert_eq!(
+            config.internal.import_batches.max_cumulative_size,
+            ImportBatches::default().max_cumulative_size
+        );
+        assert_eq!(
+            config.internal.balance_update_interval,
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            config.internal.cost_request_limit,
+            Internal::default().cost_request_limit
+        );
+    }
+
+    #[test]
+    fn zero_internal_settings_are_rejected() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+        let config = load(
+            Some(&path),
+            env(&[("COMMITTER__INTERNAL__COST_REQUEST_LIMIT", "0")]),
+        )
+        .unwrap();
+
+        // when
+        let err = config.validate().unwrap_err();
+
+        // then
+        let msg = err.to_string();
+        assert!(
+            msg.contains("internal.cost_request_limit must be greater than 0"),
+            "{msg}"
+        );
+    }
+
+    fn without_eth_section(toml: &str) -> String {
+        let start = toml.find("[eth]").unwrap();
+        let end = toml.find("[fuel]").unwrap();
+        format!("{}{}", &toml[..start], &toml[end..])
+    }
+
+    #[test]
+    fn all_roles_run_by_default_if_blob_key_is_configured() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+
+        // when
+        let config = load(Some(&path), env(&[])).unwrap();
+
+        // then
+        assert_eq!(config.roles(), Role::ALL.into_iter().collect());
+    }
+
+    #[test]
+    fn only_block_committer_runs_by_default_without_blob_key() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let toml = TOML_CONFIG.replace(r#"blob = "Kms(blob-key-arn)""#, "");
+        let path = write_config(&dir, "committer.toml", &toml);
+
+        // when
+        let config = load(Some(&path), env(&[])).unwrap();
+
+        // then
+        config.validate().unwrap();
+        assert_eq!(config.roles(), BTreeSet::from([Role::BlockCommitter]));
+    }
+
+    #[test]
+    fn roles_can_be_given_as_comma_separated_env_var() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+
+        // when
+        let config = load(
+            Some(&path),
+            env(&[("COMMITTER__APP__ROLES", "block_importer, block_bundler")]),
+        )
+        .unwrap();
+
+        // then
+        assert_eq!(
+            config.roles(),
+            BTreeSet::from([Role::BlockImporter, Role::BlockBundler])
+        );
+    }
+
+    #[test]
+    fn bundler_only_node_does_not_need_eth_config() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let toml = without_eth_section(TOML_CONFIG).replace(
+            "[app]\n",
+            "[app]\nroles = [\"block_importer\", \"block_bundler\"]\n",
+        );
+        let path = write_config(&dir, "committer.toml", &toml);
+
+        // when
+        let config = load(Some(&path), env(&[])).unwrap();
+
+        // then
+        config.validate().unwrap();
+        assert!(config.eth.is_none());
+        assert_eq!(
+            config.roles(),
+            BTreeSet::from([Role::BlockImporter, Role::BlockBundler])
+        );
+    }
+
+    #[test]
+    fn roles_talking_to_l1_require_eth_config() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", &without_eth_section(TOML_CONFIG));
+        let config = load(
+            Some(&path),
+            env(&[("COMMITTER__APP__ROLES", "block_bundler,state_listener")]),
+        )
+        .unwrap();
+
+        // when
+        let err = config.validate().unwrap_err();
+
+        // then
+        let msg = err.to_string();
+        assert!(
+            msg.contains("Role `state_listener` requires the `eth` configuration"),
+            "{msg}"
+        );
+    }
+
+    #[test]
+    fn state_committer_role_requires_blob_key() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let toml = TOML_CONFIG.replace(r#"blob = "Kms(blob-key-arn)""#, "");
+        let path = write_config(&dir, "committer.toml", &toml);
+        let config = load(
+            Some(&path),
+            env(&[("COMMITTER__APP__ROLES", "state_committer")]),
+        )
+        .unwrap();
+
+        // when
+        let err = config.validate().unwrap_err();
+
+        // then
+        let msg = err.to_string();
+        assert!(
+            msg.contains("Role `state_committer` requires a blob pool wallet key"),
+            "{msg}"
+        );
+    }
+
+    #[test]
+    fn unknown_roles_are_rejected() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+
+        // when
+        let result = load(
+            Some(&path),
+            env(&[("COMMITTER__APP__ROLES", "block_bundler,nonexistent")]),
+        );
+
+        // then
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn leader_election_renewal_must_be_shorter_than_lease() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+        let config = load(
+            Some(&path),
+            env(&[
+                ("COMMITTER__APP__LEADER_ELECTION__ENABLED", "true"),
+                (
+                    "COMMITTER__APP__LEADER_ELECTION__CANDIDATE_ID",
+                    "committer-0",
+                ),
+                ("COMMITTER__APP__LEADER_ELECTION__LEASE_DURATION", "10s"),
+                ("COMMITTER__APP__LEADER_ELECTION__RENEWAL_INTERVAL", "10s"),
+            ]),
+        )
+        .unwrap();
+
+        // when
+        let err = config.validate().unwrap_err();
+
+        // then
+        let msg = err.to_string();
+        assert!(
+            msg.contains("renewal_interval must be > 0 and < lease_duration"),
+            "{msg}"
+        );
+    }
+
+    #[test]
+    fn log_format_and_filter_can_be_configured() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+
+        // when
+        let config = load(
+            Some(&path),
+            env(&[
+                ("COMMITTER__APP__LOGGING__FORMAT", "pretty"),
+                ("COMMITTER__APP__LOGGING__FILTER", "info,storage=debug"),
+            ]),
+        )
+        .unwrap();
+
+        // then
+        config.validate().unwrap();
+        assert_eq!(config.app.logging.format, LogFormat::Pretty);
+        assert_eq!(
+            config.app.logging.filter.as_deref(),
+            Some("info,storage=debug")
+        );
+        assert!(config.app.logging.otlp_endpoint.is_none());
+    }
+
+    #[test]
+    fn invalid_log_filter_is_rejected() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+        let config = load(
+            Some(&path),
+            env(&[("COMMITTER__APP__LOGGING__FILTER", "storage=loud")]),
+        )
+        .unwrap();
+
+        // when
+        let err = config.validate().unwrap_err();
+
+        // then
+        let msg = err.to_string();
+        assert!(msg.contains("Invalid log filter"), "{msg}");
+        assert!(msg.contains("COMMITTER__APP__LOGGING__FILTER"), "{msg}");
+    }
+
+    #[tokio::test]
+    async fn cost_feed_token_is_redacted_and_url_must_be_http() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+        let with_url = |url: &str| {
+            load(
+                Some(&path),
+                env(&[
+                    ("COMMITTER__APP__COST_FEED__URL", url),
+                    ("COMMITTER__APP__COST_FEED__AUTH_TOKEN", "feed-secret"),
+                ]),
+            )
+            .unwrap()
+        };
+
+        // when
+        let rendered = check(&with_url("https://gas-price.fuel.network/costs"), false)
+            .await
+            .unwrap();
+        let err = check(&with_url("ftp://gas-price.fuel.network"), false)
+            .await
+            .unwrap_err();
+
+        // then
+        assert!(!rendered.contains("feed-secret"), "{rendered}");
+        let json: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(json["app"]["cost_feed"]["auth_token"], "<redacted>");
+        assert_eq!(json["app"]["cost_feed"]["batch_size"], 100);
+
+        let msg = err.to_string();
+        assert!(msg.contains("must be http or https"), "{msg}");
+        assert!(msg.contains("COMMITTER__APP__COST_FEED__URL"), "{msg}");
+    }
+
+    #[test]
+    fn block_commit_settings_default_and_can_be_overridden() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+
+        // when
+        let defaults = load(Some(&path), env(&[])).unwrap();
+        let overridden = load(
+            Some(&path),
+            env(&[
+                ("COMMITTER__APP__BLOCK_COMMIT__GAS_BUMP_TIMEOUT", "30s"),
+                ("COMMITTER__APP__BLOCK_COMMIT__MAX_FEE", "1000"),
+                ("COMMITTER__APP__BLOCK_COMMIT__RECOMMIT_ON_MISMATCH", "true"),
+            ]),
+        )
+        .unwrap();
+
+        // then
+        assert_eq!(
+            defaults.app.block_commit.gas_bump_timeout,
+            Duration::from_secs(120)
+        );
+        assert_eq!(defaults.app.block_commit.max_fee, None);
+        assert!(!defaults.app.block_commit.recommit_on_mismatch);
+        assert_eq!(
+            overridden.app.block_commit.gas_bump_timeout,
+            Duration::from_secs(30)
+        );
+        assert_eq!(overridden.app.block_commit.max_fee, Some(1000));
+        assert!(overridden.app.block_commit.recommit_on_mismatch);
+    }
+
+    #[test]
+    fn block_id_quorum_must_be_reachable_with_configured_fuel_endpoints() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+        let endpoints = (
+            "COMMITTER__FUEL__ADDITIONAL_GRAPHQL_ENDPOINTS",
+            "http://fuel-2:4000/graphql, http://fuel-3:4000/graphql",
+        );
+        let reachable = load(
+            Some(&path),
+            env(&[endpoints, ("COMMITTER__FUEL__BLOCK_ID_QUORUM", "3")]),
+        )
+        .unwrap();
+        let unreachable = load(
+            Some(&path),
+            env(&[endpoints, ("COMMITTER__FUEL__BLOCK_ID_QUORUM", "4")]),
+        )
+        .unwrap();
+
+        // when
+        let reachable_result = reachable.validate();
+        let unreachable_result = unreachable.validate();
+
+        // then
+        assert_eq!(reachable.fuel.additional_graphql_endpoints.len(), 2);
+        assert!(reachable_result.is_ok());
+        let msg = unreachable_result.unwrap_err().to_string();
+        assert!(msg.contains("COMMITTER__FUEL__BLOCK_ID_QUORUM"), "{msg}");
+    }
+
+    #[test]
+    fn zero_fuel_request_timeout_is_rejected() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = write_config(&dir, "committer.toml", TOML_CONFIG);
+        let config = load(
+            Some(&path),
+            env(&[("COMMITTER__FUEL__REQUEST_TIMEOUT", "0s")]),
+        )
+        .unwrap();
+
+        // when
+        let result = config.validate();
+
+        // then
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains("COMMITTER__FUEL__REQUEST_TIMEOUT"), "{msg}");
+    }
+
+    #[test]
+    fn missing_config_file_is_an_error() {
+        // given
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("does_not_exist.toml");
+
+        // when
+        let result = load(Some(&path), env(&[]));
+
+        // then
+        assert!(result.is_err());
+    }
 }
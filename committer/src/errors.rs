@@ -43,7 +43,10 @@ impl From<services::Error> for Error {
         match error {
             services::Error::Network(e) => Self::Network(e),
             services::Error::Storage(e) => Self::Storage(e),
-            services::Error::BlockValidation(e) | services::Error::Other(e) => Self::Other(e),
+            services::Error::BlockValidation(e)
+            | services::Error::NoQuorum(e)
+            | services::Error::Other(e) => Self::Other(e),
+            services::Error::NotLeader => Self::Other(error.to_string()),
         }
     }
 }
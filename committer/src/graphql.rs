@@ -0,0 +1,854 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Enum, InputObject, Json, Object, Result, Schema,
+    SchemaBuilder, SimpleObject,
+    connection::{Connection, CursorType, Edge},
+    dataloader::{DataLoader, Loader},
+};
+use clock::SystemClock;
+use services::{
+    block_reporter::service::{
+        BlockReport, BlockReporter, CommitTransactionReport, ContractCommitReport,
+    },
+    bundle_reporter::service::{
+        BundleReporter, BundleSummary, FragmentReport, TransactionReport, TransactionStatus,
+    },
+    cost_reporter::service::CostReporter,
+    status_reporter::service::{FeeDecisionReport, StatusReport, StatusReporter},
+    types::HeightRange,
+};
+
+use crate::{Database, FuelApi};
+
+pub type CommitterSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Page size used when `first` is not given, same as for the REST endpoints.
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// The relationships can be followed back and forth, e.g. from a bundle to its fragments and back,
+/// so the nesting and the number of fields of a query are bounded.
+const MAX_QUERY_DEPTH: usize = 10;
+const MAX_QUERY_COMPLEXITY: usize = 500;
+
+/// Upper bound for `first` on connections not bounded by their reporter.
+#[derive(Debug, Clone, Copy)]
+struct MaxPageSize(usize);
+
+fn builder() -> SchemaBuilder<QueryRoot, EmptyMutation, EmptySubscription> {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+}
+
+/// Serves the same data as the REST endpoints, with the relationships between bundles, fragments,
+/// transactions, costs and blocks resolved on demand.
+pub fn schema(
+    bundle_reporter: Arc<BundleReporter<Database>>,
+    block_reporter: Arc<BlockReporter<Database, FuelApi>>,
+    cost_reporter: Arc<CostReporter<Database>>,
+    status_reporter: Arc<StatusReporter<Database, FuelApi, SystemClock>>,
+    max_page_size: usize,
+) -> CommitterSchema {
+    builder()
+        .data(DataLoader::new(
+            BundleLoader(Arc::clone(&bundle_reporter)),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            FragmentLoader(Arc::clone(&bundle_reporter)),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            CarriedBundlesLoader(Arc::clone(&bundle_reporter)),
+            tokio::spawn,
+        ))
+        .data(bundle_reporter)
+        .data(block_reporter)
+        .data(cost_reporter)
+        .data(status_reporter)
+        .data(MaxPageSize(max_page_size))
+        .finish()
+}
+
+fn bundle_reporter<'a>(ctx: &Context<'a>) -> &'a BundleReporter<Database> {
+    ctx.data_unchecked::<Arc<BundleReporter<Database>>>()
+}
+
+fn block_reporter<'a>(ctx: &Context<'a>) -> &'a BlockReporter<Database, FuelApi> {
+    ctx.data_unchecked::<Arc<BlockReporter<Database, FuelApi>>>()
+}
+
+fn cost_reporter<'a>(ctx: &Context<'a>) -> &'a CostReporter<Database> {
+    ctx.data_unchecked::<Arc<CostReporter<Database>>>()
+}
+
+/// Looks up a bundle, batched with the other lookups of the same query.
+async fn load_bundle(ctx: &Context<'_>, id: u32) -> Result<Option<Bundle>> {
+    let bundle = ctx
+        .data_unchecked::<DataLoader<BundleLoader>>()
+        .load_one(id)
+        .await?;
+
+    Ok(bundle.map(Bundle))
+}
+
+/// Batches the lookups of bundles by id made while resolving a query.
+struct BundleLoader(Arc<BundleReporter<Database>>);
+
+impl Loader<u32> for BundleLoader {
+    type Value = BundleSummary;
+    type Error = Arc<services::Error>;
+
+    async fn load(
+        &self,
+        ids: &[u32],
+    ) -> std::result::Result<HashMap<u32, BundleSummary>, Self::Error> {
+        let bundles = self.0.bundles_by_id(ids.to_vec()).await.map_err(Arc::new)?;
+
+        Ok(bundles
+            .into_iter()
+            .map(|bundle| (bundle.overview.id, bundle))
+            .collect())
+    }
+}
+
+/// Batches the lookups of the fragments of bundles, keyed by bundle id.
+struct FragmentLoader(Arc<BundleReporter<Database>>);
+
+impl Loader<u32> for FragmentLoader {
+    type Value = Vec<FragmentReport>;
+    type Error = Arc<services::Error>;
+
+    async fn load(
+        &self,
+        bundle_ids: &[u32],
+    ) -> std::result::Result<HashMap<u32, Vec<FragmentReport>>, Self::Error> {
+        self.0
+            .fragments_of_bundles(bundle_ids.to_vec())
+            .await
+            .map_err(Arc::new)
+    }
+}
+
+/// Batches the lookups of the bundles carried by blob transactions, keyed by tx hash.
+struct CarriedBundlesLoader(Arc<BundleReporter<Database>>);
+
+impl Loader<[u8; 32]> for CarriedBundlesLoader {
+    type Value = Vec<u32>;
+    type Error = Arc<services::Error>;
+
+    async fn load(
+        &self,
+        tx_hashes: &[[u8; 32]],
+    ) -> std::result::Result<HashMap<[u8; 32], Vec<u32>>, Self::Error> {
+        self.0
+            .bundle_ids_carried_by(tx_hashes.to_vec())
+            .await
+            .map_err(Arc::new)
+    }
+}
+
+/// Inclusive range of fuel block heights, open on the sides left out.
+#[derive(Debug, Default, InputObject)]
+#[graphql(name = "HeightRange")]
+struct HeightRangeInput {
+    from: Option<u32>,
+    to: Option<u32>,
+}
+
+impl HeightRangeInput {
+    fn validated(input: Option<Self>) -> Result<HeightRange> {
+        let input = input.unwrap_or_default();
+        if let (Some(from), Some(to)) = (input.from, input.to) {
+            if from > to {
+                return Err(format!("height range is empty: {from} > {to}").into());
+            }
+        }
+
+        Ok(HeightRange {
+            from: input.from,
+            to: input.to,
+        })
+    }
+}
+
+/// Decodes the `after` cursor and checks `first` against `max`.
+fn page_args<C: CursorType>(
+    first: Option<i32>,
+    after: Option<String>,
+    max: usize,
+) -> Result<(usize, Option<C>)> {
+    let first = match first {
+        Some(first) => usize::try_from(first).map_err(|_| "`first` must not be negative")?,
+        None => DEFAULT_PAGE_SIZE.min(max),
+    };
+    if first > max {
+        return Err(format!("requested: {first} items, but limit is: {max}").into());
+    }
+
+    let after = after
+        .map(|cursor| C::decode_cursor(&cursor).map_err(|e| format!("invalid cursor: {e}")))
+        .transpose()?;
+
+    Ok((first, after))
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Progress of the committer, same as `/status`.
+    async fn status(&self, ctx: &Context<'_>) -> Result<Status> {
+        let reporter = ctx.data_unchecked::<Arc<StatusReporter<Database, FuelApi, SystemClock>>>();
+
+        Ok(Status(reporter.current_status().await?))
+    }
+
+    async fn bundle(&self, ctx: &Context<'_>, id: u32) -> Result<Option<Bundle>> {
+        load_bundle(ctx, id).await
+    }
+
+    /// Bundles from the newest to the oldest, restricted to the ones with blocks in `heights`.
+    async fn bundles(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+        heights: Option<HeightRangeInput>,
+    ) -> Result<Connection<u32, Bundle>> {
+        let heights = HeightRangeInput::validated(heights)?;
+        let (first, after) = page_args(first, after, usize::MAX)?;
+
+        let page = bundle_reporter(ctx).bundles(after, heights, first).await?;
+
+        let mut connection = Connection::new(after.is_some(), page.next_before.is_some());
+        connection.edges.extend(
+            page.bundles
+                .into_iter()
+                .map(|bundle| Edge::new(bundle.overview.id, Bundle(bundle))),
+        );
+
+        Ok(connection)
+    }
+
+    /// Blob transactions from the newest to the oldest, restricted to the ones carrying blocks in
+    /// `heights`.
+    async fn blob_transactions(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+        heights: Option<HeightRangeInput>,
+    ) -> Result<Connection<u64, BlobTransaction>> {
+        let heights = HeightRangeInput::validated(heights)?;
+        let (first, after) = page_args(first, after, usize::MAX)?;
+
+        let page = bundle_reporter(ctx)
+            .blob_transactions(after, heights, first)
+            .await?;
+
+        let mut connection = Connection::new(after.is_some(), page.next_before.is_some());
+        connection
+            .edges
+            .extend(page.transactions.into_iter().filter_map(|tx| {
+                let id = tx.id?;
+                Some(Edge::new(id, BlobTransaction(tx)))
+            }));
+
+        Ok(connection)
+    }
+
+    /// Where the fuel block is on its way to L1, same as `/v1/blocks/{height}`.
+    async fn block(&self, ctx: &Context<'_>, height: u32) -> Result<Block> {
+        Ok(Block(block_reporter(ctx).block(height).await?))
+    }
+
+    /// Contract commits from the highest block to the lowest, restricted to `heights`.
+    async fn contract_submissions(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+        heights: Option<HeightRangeInput>,
+    ) -> Result<Connection<u32, ContractSubmission>> {
+        let heights = HeightRangeInput::validated(heights)?;
+        let MaxPageSize(max) = *ctx.data_unchecked::<MaxPageSize>();
+        let (first, after) = page_args(first, after, max)?;
+
+        let commits = block_reporter(ctx)
+            .contract_commits(after, heights, first)
+            .await?;
+
+        let mut connection = Connection::new(after.is_some(), commits.len() == first);
+        connection.edges.extend(
+            commits
+                .into_iter()
+                .map(|commit| Edge::new(commit.block_height, ContractSubmission(commit))),
+        );
+
+        Ok(connection)
+    }
+
+    /// Costs of finalized bundles from the lowest blocks to the highest, restricted to the
+    /// bundles with blocks in `heights`. The cursor is the last block of the bundle.
+    async fn bundle_costs(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+        heights: Option<HeightRangeInput>,
+    ) -> Result<Connection<u64, BundleCost>> {
+        let heights = HeightRangeInput::validated(heights)?;
+        let (first, after) = page_args::<u64>(first, after, usize::MAX)?;
+
+        let after_height = after
+            .map(|height| u32::try_from(height.saturating_add(1)).unwrap_or(u32::MAX))
+            .unwrap_or_default();
+        let from_height = heights.from.unwrap_or_default().max(after_height);
+
+        let costs = cost_reporter(ctx).get_costs(from_height, first).await?;
+        let fetched = costs.len();
+
+        let costs: Vec<_> = costs
+            .into_iter()
+            .take_while(|cost| {
+                heights
+                    .to
+                    .is_none_or(|to| cost.start_height <= u64::from(to))
+            })
+            .collect();
+        let has_next_page = fetched == first && costs.len() == fetched;
+
+        let mut connection = Connection::new(after.is_some(), has_next_page);
+        connection.edges.extend(
+            costs
+                .into_iter()
+                .map(|cost| Edge::new(cost.end_height, BundleCost(cost))),
+        );
+
+        Ok(connection)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+#[graphql(name = "CommitStatus")]
+enum StatusKind {
+    Idle,
+    Committing,
+}
+
+#[derive(SimpleObject)]
+struct PendingBlobTransaction {
+    hash: String,
+    nonce: u32,
+    created_at: Option<String>,
+    pending_for_seconds: Option<u64>,
+}
+
+#[derive(SimpleObject)]
+struct WalletBalance {
+    wallet: String,
+    /// In wei, as a decimal string.
+    balance: String,
+}
+
+struct Status(StatusReport);
+
+#[Object]
+impl Status {
+    /// Whether a contract commit is in flight.
+    async fn status(&self) -> StatusKind {
+        match self.0.status {
+            services::status_reporter::service::Status::Idle => StatusKind::Idle,
+            services::status_reporter::service::Status::Committing => StatusKind::Committing,
+        }
+    }
+
+    /// `null` if the Fuel node couldn't be reached.
+    async fn latest_fuel_height(&self) -> Option<u32> {
+        self.0.latest_fuel_height
+    }
+
+    async fn latest_imported_height(&self) -> Option<u32> {
+        self.0.latest_imported_height
+    }
+
+    async fn latest_bundled_height(&self) -> Option<u32> {
+        self.0.latest_bundled_height
+    }
+
+    /// End of the highest bundle with a finalized fragment.
+    async fn latest_finalized_fragment_height(&self) -> Option<u32> {
+        self.0.latest_finalized_fragment_height
+    }
+
+    /// Counted within the lookback window, older blocks are no longer bundled.
+    async fn unbundled_blocks(&self) -> u64 {
+        self.0.unbundled_blocks
+    }
+
+    /// Fragments within the lookback window not carried by any non-failed transaction.
+    async fn unsubmitted_fragments(&self) -> u64 {
+        self.0.unsubmitted_fragments
+    }
+
+    async fn pending_blob_transaction(&self) -> Option<PendingBlobTransaction> {
+        self.0
+            .pending_blob_tx
+            .as_ref()
+            .map(|tx| PendingBlobTransaction {
+                hash: tx.hash.clone(),
+                nonce: tx.nonce,
+                created_at: tx.created_at.clone(),
+                pending_for_seconds: tx.pending_for_seconds,
+            })
+    }
+
+    /// Shaped as in `/status`, `null` until the state committer has checked the fees.
+    async fn last_fee_decision(&self) -> Option<Json<&FeeDecisionReport>> {
+        self.0.last_fee_decision.as_ref().map(Json)
+    }
+
+    async fn wallet_balances(&self) -> Vec<WalletBalance> {
+        self.0
+            .wallet_balances
+            .iter()
+            .map(|(wallet, balance)| WalletBalance {
+                wallet: wallet.clone(),
+                balance: balance.clone(),
+            })
+            .collect()
+    }
+}
+
+struct Bundle(BundleSummary);
+
+#[Object]
+impl Bundle {
+    async fn id(&self) -> u32 {
+        self.0.overview.id
+    }
+
+    async fn start_height(&self) -> u32 {
+        self.0.overview.start_height
+    }
+
+    /// Inclusive.
+    async fn end_height(&self) -> u32 {
+        self.0.overview.end_height
+    }
+
+    async fn num_fragments(&self) -> u32 {
+        self.0.overview.num_fragments
+    }
+
+    /// Fragments carried by a finalized transaction.
+    async fn num_finalized_fragments(&self) -> u32 {
+        self.0.overview.num_finalized_fragments
+    }
+
+    async fn compressed_size(&self) -> u64 {
+        self.0.overview.compressed_size
+    }
+
+    /// `null` once the blocks were pruned.
+    async fn uncompressed_size(&self) -> Option<u64> {
+        self.0.overview.uncompressed_size
+    }
+
+    async fn compression_ratio(&self) -> Option<f64> {
+        self.0.compression_ratio
+    }
+
+    /// Ordered by their index.
+    async fn fragments(&self, ctx: &Context<'_>) -> Result<Vec<Fragment>> {
+        let bundle_id = self.0.overview.id;
+        let fragments = ctx
+            .data_unchecked::<DataLoader<FragmentLoader>>()
+            .load_one(bundle_id)
+            .await?
+            .unwrap_or_default();
+
+        Ok(fragments
+            .into_iter()
+            .map(|fragment| Fragment {
+                fragment,
+                bundle_id,
+            })
+            .collect())
+    }
+
+    /// `null` until every transaction carrying the bundle is finalized.
+    async fn cost(&self, ctx: &Context<'_>) -> Result<Option<BundleCost>> {
+        let cost = cost_reporter(ctx).bundle_cost(self.0.overview.id).await?;
+
+        Ok(cost.map(BundleCost))
+    }
+}
+
+struct Fragment {
+    fragment: FragmentReport,
+    bundle_id: u32,
+}
+
+#[Object]
+impl Fragment {
+    async fn id(&self) -> u32 {
+        self.fragment.id
+    }
+
+    /// Position of the fragment within its bundle.
+    async fn idx(&self) -> u32 {
+        self.fragment.idx
+    }
+
+    async fn total_bytes(&self) -> u32 {
+        self.fragment.total_bytes
+    }
+
+    async fn unused_bytes(&self) -> u32 {
+        self.fragment.unused_bytes
+    }
+
+    /// Every transaction that carried the fragment, oldest first.
+    async fn transactions(&self) -> Vec<BlobTransaction> {
+        self.fragment
+            .transactions
+            .iter()
+            .map(|tx| BlobTransaction(tx.clone()))
+            .collect()
+    }
+
+    async fn bundle(&self, ctx: &Context<'_>) -> Result<Option<Bundle>> {
+        load_bundle(ctx, self.bundle_id).await
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+enum TransactionState {
+    Pending,
+    IncludedInBlock,
+    Finalized,
+    SqueezedOut,
+    Failed,
+}
+
+impl From<TransactionStatus> for TransactionState {
+    fn from(status: TransactionStatus) -> Self {
+        match status {
+            TransactionStatus::Pending => Self::Pending,
+            TransactionStatus::IncludedInBlock => Self::IncludedInBlock,
+            TransactionStatus::Finalized => Self::Finalized,
+            TransactionStatus::SqueezedOut => Self::SqueezedOut,
+            TransactionStatus::Failed => Self::Failed,
+        }
+    }
+}
+
+struct BlobTransaction(TransactionReport);
+
+#[Object]
+impl BlobTransaction {
+    async fn hash(&self) -> &str {
+        &self.0.hash
+    }
+
+    async fn nonce(&self) -> u32 {
+        self.0.nonce
+    }
+
+    async fn state(&self) -> TransactionState {
+        self.0.state.into()
+    }
+
+    /// In wei, as a decimal string.
+    async fn max_fee(&self) -> String {
+        self.0.max_fee.to_string()
+    }
+
+    /// In wei, as a decimal string.
+    async fn priority_fee(&self) -> String {
+        self.0.priority_fee.to_string()
+    }
+
+    /// In wei, as a decimal string.
+    async fn blob_fee(&self) -> String {
+        self.0.blob_fee.to_string()
+    }
+
+    async fn created_at(&self) -> Option<&str> {
+        self.0.created_at.as_deref()
+    }
+
+    async fn finalized_at(&self) -> Option<&str> {
+        self.0.finalized_at.as_deref()
+    }
+
+    /// Bundles with a fragment carried by the transaction.
+    async fn bundles(&self, ctx: &Context<'_>) -> Result<Vec<Bundle>> {
+        let mut hash = [0; 32];
+        hex::decode_to_slice(self.0.hash.trim_start_matches("0x"), &mut hash)?;
+
+        let ids = ctx
+            .data_unchecked::<DataLoader<CarriedBundlesLoader>>()
+            .load_one(hash)
+            .await?
+            .unwrap_or_default();
+        let mut bundles = ctx
+            .data_unchecked::<DataLoader<BundleLoader>>()
+            .load_many(ids.iter().copied())
+            .await?;
+
+        // keeps the newest first order
+        Ok(ids
+            .iter()
+            .filter_map(|id| bundles.remove(id))
+            .map(Bundle)
+            .collect())
+    }
+}
+
+struct BundleCost(services::types::BundleCost);
+
+#[Object]
+impl BundleCost {
+    async fn bundle_id(&self) -> u64 {
+        self.0.id
+    }
+
+    /// Total fees paid for the bundle in wei, as a decimal string.
+    async fn cost(&self) -> String {
+        self.0.cost.to_string()
+    }
+
+    /// Size of the data contained in the bundle.
+    async fn size(&self) -> u64 {
+        self.0.size
+    }
+
+    /// DA height of the final transaction carrying the bundle.
+    async fn da_block_height(&self) -> u64 {
+        self.0.da_block_height
+    }
+
+    async fn start_height(&self) -> u64 {
+        self.0.start_height
+    }
+
+    /// Inclusive.
+    async fn end_height(&self) -> u64 {
+        self.0.end_height
+    }
+
+    async fn bundle(&self, ctx: &Context<'_>) -> Result<Option<Bundle>> {
+        load_bundle(ctx, u32::try_from(self.0.id)?).await
+    }
+}
+
+struct Block(BlockReport);
+
+#[Object]
+impl Block {
+    async fn height(&self) -> u32 {
+        self.0.height
+    }
+
+    /// Whether the block was imported into the database.
+    async fn imported(&self) -> bool {
+        self.0.imported
+    }
+
+    /// Whether the block is recent enough to still be bundled.
+    async fn within_lookback_window(&self) -> bool {
+        self.0.within_lookback_window
+    }
+
+    /// The block fell out of the lookback window without being bundled, it won't be posted.
+    async fn given_up(&self) -> bool {
+        self.0.given_up
+    }
+
+    async fn bundle(&self, ctx: &Context<'_>) -> Result<Option<Bundle>> {
+        let Some(inclusion) = &self.0.bundle else {
+            return Ok(None);
+        };
+
+        load_bundle(ctx, inclusion.id).await
+    }
+
+    /// When the last fragment of the bundle was finalized, `null` until all of them are.
+    async fn bundle_finalized_at(&self) -> Option<&str> {
+        self.0.bundle.as_ref()?.finalized_at.as_deref()
+    }
+
+    /// DA height of the last transaction carrying the bundle, once finalized.
+    async fn da_block_height(&self) -> Option<u64> {
+        self.0.bundle.as_ref()?.da_block_height
+    }
+
+    async fn contract_submission(&self) -> Option<ContractSubmission> {
+        self.0.contract_commit.clone().map(ContractSubmission)
+    }
+}
+
+struct ContractSubmission(ContractCommitReport);
+
+#[Object]
+impl ContractSubmission {
+    async fn block_height(&self) -> u32 {
+        self.0.block_height
+    }
+
+    async fn block_hash(&self) -> &str {
+        &self.0.block_hash
+    }
+
+    async fn completed(&self) -> bool {
+        self.0.completed
+    }
+
+    /// Every transaction sent for the commit, oldest first.
+    async fn transactions(&self) -> Vec<ContractTransaction> {
+        self.0
+            .transactions
+            .iter()
+            .cloned()
+            .map(ContractTransaction)
+            .collect()
+    }
+
+    async fn block(&self, ctx: &Context<'_>) -> Result<Block> {
+        Ok(Block(block_reporter(ctx).block(self.0.block_height).await?))
+    }
+}
+
+struct ContractTransaction(CommitTransactionReport);
+
+#[Object]
+impl ContractTransaction {
+    async fn hash(&self) -> &str {
+        &self.0.hash
+    }
+
+    async fn nonce(&self) -> u32 {
+        self.0.nonce
+    }
+
+    async fn state(&self) -> TransactionState {
+        self.0.state.into()
+    }
+
+    /// In wei, as a decimal string.
+    async fn max_fee(&self) -> String {
+        self.0.max_fee.to_string()
+    }
+
+    /// In wei, as a decimal string.
+    async fn priority_fee(&self) -> String {
+        self.0.priority_fee.to_string()
+    }
+
+    async fn created_at(&self) -> Option<&str> {
+        self.0.created_at.as_deref()
+    }
+
+    async fn finalized_at(&self) -> Option<&str> {
+        self.0.finalized_at.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_links_the_committer_data() {
+        // given
+        let schema = builder().finish();
+
+        // when
+        let sdl = schema.sdl();
+
+        // then
+        for expected in [
+            "bundles(first: Int, after: String, heights: HeightRange): BundleConnection!",
+            "blobTransactions(first: Int, after: String, heights: HeightRange): BlobTransactionConnection!",
+            "contractSubmissions(first: Int, after: String, heights: HeightRange): ContractSubmissionConnection!",
+            "bundleCosts(first: Int, after: String, heights: HeightRange): BundleCostConnection!",
+            "fragments: [Fragment!]!",
+            "bundles: [Bundle!]!",
+            "contractSubmission: ContractSubmission",
+        ] {
+            assert!(sdl.contains(expected), "missing `{expected}` in:\n{sdl}");
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_queries_nested_too_deep() {
+        // given
+        let schema = builder().finish();
+        let query = "{ bundle(id: 1) { fragments { bundle { fragments { bundle { fragments { \
+            bundle { fragments { bundle { fragments { id } } } } } } } } } } }";
+
+        // when
+        let response = schema.execute(query).await;
+
+        // then
+        let errors = response
+            .errors
+            .iter()
+            .map(|e| e.message.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(errors, vec!["Query is nested too deep."]);
+    }
+
+    #[tokio::test]
+    async fn rejects_queries_that_are_too_complex() {
+        // given
+        let schema = builder().finish();
+        let fields = (0..MAX_QUERY_COMPLEXITY)
+            .map(|i| format!("b{i}: bundle(id: {i}) {{ id }}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        // when
+        let response = schema.execute(format!("{{ {fields} }}")).await;
+
+        // then
+        let errors = response
+            .errors
+            .iter()
+            .map(|e| e.message.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(errors, vec!["Query is too complex."]);
+    }
+
+    #[test]
+    fn rejects_empty_height_ranges() {
+        // given
+        let input = HeightRangeInput {
+            from: Some(10),
+            to: Some(9),
+        };
+
+        // when
+        let result = HeightRangeInput::validated(Some(input));
+
+        // then
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn page_size_is_bounded() {
+        // when
+        let too_big = page_args::<u32>(Some(11), None, 10);
+        let negative = page_args::<u32>(Some(-1), None, 10);
+        let defaulted = page_args::<u32>(None, Some("5".to_string()), 10).unwrap();
+
+        // then
+        assert!(too_big.is_err());
+        assert!(negative.is_err());
+        assert_eq!(defaulted, (10, Some(5)));
+    }
+}
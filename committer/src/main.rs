@@ -2,31 +2,54 @@
 mod api;
 mod config;
 mod errors;
+mod graphql;
+mod scheduler;
 mod setup;
 
 use api::launch_api_server;
+use config::Role;
 use errors::{Result, WithContext};
-use metrics::prometheus::Registry;
-use services::fees::cache::CachingApi;
+use metrics::{RegistersMetrics, prometheus::Registry};
+use scheduler::Scheduler;
+use services::{
+    critical_section::CriticalSections,
+    events::service::EventBus,
+    fees::cache::CachingApi,
+    state_committer::service::{FeeDecisions, ForcedActions},
+    wallet_balance_tracker::service::WalletBalances,
+};
 use setup::last_finalization_metric;
 use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
 
 use crate::setup::shut_down;
 
 pub type L1 = eth::WebsocketClient;
 pub type AwsClient = eth::AwsClient;
 pub type Database = storage::Postgres;
-pub type FuelApi = fuel::HttpClient;
+pub type FuelApi = fuel::MultiEndpointClient;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    setup::logger();
-
-    let config = config::parse().with_context(|| "failed to parse config")?;
+    let config = match config::parse().with_context(|| "failed to parse config")? {
+        config::Invocation::Run(config) => config,
+        config::Invocation::CheckConfig {
+            config,
+            resolve_keys,
+        } => {
+            let rendered = config::check(&config, resolve_keys)
+                .await
+                .with_context(|| "config validation failed")?;
+            println!("{rendered}");
+            return Ok(());
+        }
+    };
     config
         .validate()
         .with_context(|| "config validation failed")?;
 
+    let tracing = setup::logger(&config.app.logging).with_context(|| "failed to set up logging")?;
+
     let metrics_registry = Registry::default();
 
     let finalization_metric = last_finalization_metric();
@@ -34,111 +57,236 @@ async fn main() -> Result<()> {
         .await
         .with_context(|| "failed to connect to database")?;
 
-    let internal_config = config::Internal::default();
+    let internal_config = config.internal.clone();
     let cancel_token = CancellationToken::new();
 
     let (fuel_adapter, fuel_health_check) =
         setup::fuel_adapter(&config, &internal_config, &metrics_registry);
+    let fuel_endpoint_health_checks = fuel_adapter.endpoint_health_checkers();
+
+    let roles = config.roles();
+    info!("starting roles: {roles:?}");
 
-    let (ethereum_rpc, eth_health_check) =
-        setup::l1_adapter(&config, &internal_config, &metrics_registry)
+    let (ethereum_rpc, eth_health_check) = if roles.iter().any(Role::requires_l1) {
+        let (l1, health_check) = setup::l1_adapter(&config, &internal_config, &metrics_registry)
             .await
             .with_context(|| "could not setup l1 adapter")?;
+        (Some(l1), Some(health_check))
+    } else {
+        (None, None)
+    };
+    let l1 = || {
+        ethereum_rpc
+            .clone()
+            .expect("already validated via `validate` that the roles' l1 requirements are met")
+    };
 
-    let wallet_balance_tracker_handle = setup::wallet_balance_tracker(
-        &internal_config,
-        &metrics_registry,
-        ethereum_rpc.clone(),
+    let scheduler = Scheduler::new(
         cancel_token.clone(),
+        config.app.max_error_backoff,
+        config.app.runner_unhealthy_after_intervals,
     );
+    scheduler.register_metrics(&metrics_registry);
+
+    let mut handles = vec![];
+    let critical_sections = CriticalSections::new();
+    let fee_decisions = FeeDecisions::new();
+    let forced_actions = ForcedActions::new();
+    let wallet_balances = WalletBalances::new();
+    let events = EventBus::new();
 
-    let committer_handle = setup::block_committer(
-        ethereum_rpc.clone(),
+    // Every service only running on the leader gets its own lease, so that the roles can be
+    // spread over several instances.
+    let mut leader_election = setup::leader_election(
         storage.clone(),
-        fuel_adapter.clone(),
         &config,
-        cancel_token.clone(),
+        &metrics_registry,
+        forced_actions.clone(),
     );
 
-    let mut handles = vec![wallet_balance_tracker_handle, committer_handle];
+    handles.push(setup::pause_sync(storage.clone(), &scheduler, &config).await);
 
-    // If the blob pool wallet key is set, we need to start
-    // the state committer and state importer
-    if config.eth.l1_keys.blob.is_some() {
-        let block_bundler = setup::block_bundler(
-            fuel_adapter.clone(),
+    if ethereum_rpc.is_some() {
+        handles.push(setup::wallet_balance_tracker(
+            &internal_config,
+            &metrics_registry,
+            l1(),
+            &scheduler,
+            wallet_balances.clone(),
+        ));
+    }
+
+    let mut commit_watcher_health_check = None;
+    if roles.contains(&Role::BlockCommitter) {
+        let (handle, health_check) = setup::commit_watcher(
+            l1(),
             storage.clone(),
-            cancel_token.clone(),
+            fuel_adapter.clone(),
             &config,
+            &scheduler,
             &metrics_registry,
         );
+        handles.push(handle);
+        commit_watcher_health_check = Some(health_check);
+
+        handles.push(setup::block_committer(
+            l1(),
+            storage.clone(),
+            fuel_adapter.clone(),
+            &config,
+            &scheduler,
+            &metrics_registry,
+            setup::leadership(&mut leader_election, Role::BlockCommitter.name()).await,
+            critical_sections.clone(),
+            events.clone(),
+        ));
+    }
+
+    if roles.contains(&Role::BlockBundler) {
+        handles.push(setup::block_bundler(
+            fuel_adapter.clone(),
+            storage.clone(),
+            &scheduler,
+            &config,
+            &metrics_registry,
+            setup::leadership(&mut leader_election, Role::BlockBundler.name()).await,
+            events.clone(),
+        ));
+    }
 
+    if roles.contains(&Role::StateCommitter) {
         let fee_api = CachingApi::new(
-            ethereum_rpc.clone(),
+            l1(),
             internal_config.l1_blocks_cached_for_fee_metrics_tracker,
         );
 
-        let fee_metrics_updater_handle = setup::fee_metrics_tracker(
+        handles.push(setup::fee_metrics_tracker(
             fee_api.clone(),
-            cancel_token.clone(),
+            &scheduler,
             &config,
             &metrics_registry,
-        )?;
+        )?);
 
-        let state_committer_handle = setup::state_committer(
+        handles.push(setup::state_committer(
             fuel_adapter.clone(),
-            ethereum_rpc.clone(),
+            l1(),
             storage.clone(),
-            cancel_token.clone(),
+            &scheduler,
             &config,
             &metrics_registry,
             fee_api,
-        )?;
+            setup::leadership(&mut leader_election, Role::StateCommitter.name()).await,
+            critical_sections.clone(),
+            fee_decisions.clone(),
+            forced_actions.clone(),
+            events.clone(),
+        )?);
+    }
 
-        let state_importer_handle = setup::block_importer(
-            fuel_adapter,
+    if roles.contains(&Role::BlockImporter) {
+        handles.push(setup::block_importer(
+            fuel_adapter.clone(),
             storage.clone(),
-            cancel_token.clone(),
+            &scheduler,
             &config,
             &internal_config,
-        );
+        ));
+    }
 
-        let state_listener_handle = setup::state_listener(
-            ethereum_rpc,
+    if roles.contains(&Role::StateListener) {
+        handles.push(setup::state_listener(
+            l1(),
             storage.clone(),
-            cancel_token.clone(),
+            &scheduler,
             &metrics_registry,
             &config,
             finalization_metric,
-        );
+            setup::leadership(&mut leader_election, Role::StateListener.name()).await,
+            events.clone(),
+        ));
+    }
 
-        let state_pruner_handle = setup::state_pruner(
+    if roles.contains(&Role::StatePruner) {
+        handles.push(setup::state_pruner(
             storage.clone(),
-            cancel_token.clone(),
+            &scheduler,
             &metrics_registry,
             &config,
-        );
+            setup::leadership(&mut leader_election, Role::StatePruner.name()).await,
+        ));
+    }
 
-        handles.push(state_committer_handle);
-        handles.push(state_importer_handle);
-        handles.push(block_bundler);
-        handles.push(state_listener_handle);
-        handles.push(fee_metrics_updater_handle);
-        handles.push(state_pruner_handle);
+    if let Some(url) = &config.app.cost_feed.url {
+        handles.push(setup::cost_feed(
+            url,
+            storage.clone(),
+            &scheduler,
+            &metrics_registry,
+            &config,
+            setup::leadership(&mut leader_election, "cost_feed").await,
+        )?);
+    }
+
+    if let Some(leader_election) = &leader_election {
+        handles.push(setup::schedule_leader_election(
+            leader_election.clone(),
+            &scheduler,
+            &config,
+        ));
     }
 
-    launch_api_server(
+    handles.push(setup::event_recorder(
+        storage.clone(),
+        &scheduler,
+        &config,
+        events.clone(),
+    ));
+
+    let api_server = launch_api_server(
         &config,
         &internal_config,
         metrics_registry,
         storage.clone(),
+        fuel_adapter,
         fuel_health_check,
+        fuel_endpoint_health_checks,
         eth_health_check,
+        commit_watcher_health_check,
+        scheduler.runners_health(),
+        fee_decisions,
+        wallet_balances,
+        scheduler.runner_controls(),
+        forced_actions,
+        events,
     )
-    .await
     .with_context(|| "api server")?;
+    let api_server_handle = api_server.handle();
+    let mut api_server = tokio::spawn(api_server);
+
+    let api_server_result = tokio::select! {
+        signal = setup::shutdown_signal() => {
+            info!("received {}, shutting down", signal?);
+            api_server_handle.stop(true).await;
+            Ok(())
+        }
+        result = &mut api_server => {
+            error!("api server stopped unexpectedly, shutting down");
+            result?.map_err(Into::into)
+        }
+    };
+
+    shut_down(
+        cancel_token,
+        handles,
+        critical_sections,
+        config.app.shutdown_timeout,
+        leader_election,
+        storage,
+    )
+    .await?;
+    tracing.shut_down();
 
-    shut_down(cancel_token, handles, storage).await
+    api_server_result.with_context(|| "api server")
 }
 
 #[cfg(test)]
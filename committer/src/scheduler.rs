@@ -0,0 +1,363 @@
+use std::{
+    num::NonZeroU32,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use metrics::{
+    RegistersMetrics,
+    prometheus::{
+        HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, core::Collector,
+        exponential_buckets,
+    },
+};
+use rand::Rng;
+use services::{
+    Runner, health_reporter::service::RunnerHealth, leader_election::service::Leadership,
+    runner_control::RunnerControls,
+};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, debug, error, info, info_span, warn};
+
+/// Spawns the services' polling loops, backing off on consecutive errors and tracking each runner
+/// in the exported metrics and the health report. Runners can be paused or woken up early through
+/// [`Scheduler::runner_controls`].
+#[derive(Clone)]
+pub struct Scheduler {
+    cancel_token: CancellationToken,
+    max_error_backoff: Duration,
+    unhealthy_after_intervals: NonZeroU32,
+    runners_health: Arc<Mutex<Vec<RunnerHealth>>>,
+    runner_controls: RunnerControls,
+    metrics: Metrics,
+}
+
+/// A spawned runner, named so that the ones not stopping in time can be reported.
+pub struct RunnerHandle {
+    pub name: &'static str,
+    pub handle: JoinHandle<()>,
+}
+
+impl Scheduler {
+    pub fn new(
+        cancel_token: CancellationToken,
+        max_error_backoff: Duration,
+        unhealthy_after_intervals: NonZeroU32,
+    ) -> Self {
+        Self {
+            cancel_token,
+            max_error_backoff,
+            unhealthy_after_intervals,
+            runners_health: Arc::default(),
+            runner_controls: RunnerControls::default(),
+            metrics: Metrics::default(),
+        }
+    }
+
+    /// Health of every runner scheduled so far.
+    pub fn runners_health(&self) -> Vec<RunnerHealth> {
+        self.runners_health
+            .lock()
+            .expect("lock not poisoned")
+            .clone()
+    }
+
+    /// Controls of every runner scheduled so far, and of the ones scheduled later on.
+    pub fn runner_controls(&self) -> RunnerControls {
+        self.runner_controls.clone()
+    }
+
+    pub fn schedule(
+        &self,
+        polling_interval: Duration,
+        runner: impl Runner + 'static,
+        name: &'static str,
+    ) -> RunnerHandle {
+        self.schedule_for_leader(polling_interval, runner, name, Leadership::unconditional())
+    }
+
+    /// Runs `runner` only while this instance is the leader. A run that has already started is
+    /// allowed to finish even if the leadership is lost or a shutdown is requested in the meantime,
+    /// runners whose runs can outlast the lease have to check `leadership` again before writing.
+    pub fn schedule_for_leader(
+        &self,
+        polling_interval: Duration,
+        mut runner: impl Runner + 'static,
+        name: &'static str,
+        leadership: Leadership,
+    ) -> RunnerHandle {
+        let cancel_token = self.cancel_token.clone();
+        let max_error_backoff = self.max_error_backoff;
+        let metrics = self.metrics.clone();
+        metrics.initialize(name);
+
+        let health = RunnerHealth::new(
+            name,
+            polling_interval.saturating_mul(self.unhealthy_after_intervals.get()),
+        );
+        self.runners_health
+            .lock()
+            .expect("lock not poisoned")
+            .push(health.clone());
+        let control = self
+            .runner_controls
+            .register_for_leader(name, leadership.clone());
+
+        let handle = tokio::spawn(async move {
+            // Also marks the runner as stopped if it panics.
+            let health = MarkStoppedOnDrop(health);
+
+            let mut consecutive_failures = 0;
+            loop {
+                if control.is_paused() {
+                    debug!("{name} skipped, paused by an operator");
+                    health.0.record_success();
+                    consecutive_failures = 0;
+                } else if !leadership.is_leader() {
+                    debug!("{name} skipped, this instance is on standby");
+                    health.0.record_success();
+                    consecutive_failures = 0;
+                } else {
+                    let started = Instant::now();
+                    // Traced as a whole so that the spans of the calls made during the run are
+                    // grouped together.
+                    let result = runner
+                        .run()
+                        .instrument(info_span!("run", runner = name))
+                        .await;
+                    metrics.record_run(name, started.elapsed(), result.is_ok());
+
+                    match result {
+                        Ok(()) => {
+                            health.0.record_success();
+                            consecutive_failures = 0;
+                        }
+                        Err(e) => {
+                            consecutive_failures += 1;
+                            error!("{name} encountered an error: {e}");
+                        }
+                    }
+                }
+                metrics.consecutive_failures(name, consecutive_failures);
+
+                let delay = next_delay(polling_interval, max_error_backoff, consecutive_failures);
+                if consecutive_failures > 0 {
+                    warn!(
+                        "{name} failed {consecutive_failures} time(s) in a row, retrying in {}",
+                        humantime::format_duration(delay)
+                    );
+                }
+
+                tokio::select! {
+                    biased;
+                    _ = cancel_token.cancelled() => break,
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = control.woken() => debug!("{name} woken up early"),
+                }
+            }
+
+            info!("{name} stopped");
+        });
+
+        RunnerHandle { name, handle }
+    }
+}
+
+struct MarkStoppedOnDrop(RunnerHealth);
+
+impl Drop for MarkStoppedOnDrop {
+    fn drop(&mut self) {
+        self.0.mark_stopped();
+    }
+}
+
+/// Waits `polling_interval` after a success. After consecutive failures the wait doubles each time,
+/// up to `max_error_backoff`, with a random jitter so that failing runners don't retry in lockstep.
+fn next_delay(
+    polling_interval: Duration,
+    max_error_backoff: Duration,
+    consecutive_failures: u32,
+) -> Duration {
+    if consecutive_failures == 0 {
+        return polling_interval;
+    }
+
+    let backoff = polling_interval
+        .saturating_mul(2u32.saturating_pow(consecutive_failures))
+        .min(max_error_backoff);
+
+    let jittered = rand::thread_rng().gen_range(backoff / 2..=backoff);
+
+    jittered.max(polling_interval)
+}
+
+#[derive(Clone)]
+struct Metrics {
+    runs: IntCounterVec,
+    failures: IntCounterVec,
+    consecutive_failures: IntGaugeVec,
+    run_duration: HistogramVec,
+    last_success: IntGaugeVec,
+}
+
+impl Metrics {
+    fn initialize(&self, name: &str) {
+        self.runs.with_label_values(&[name]);
+        self.failures.with_label_values(&[name]);
+        self.consecutive_failures.with_label_values(&[name]);
+    }
+
+    fn record_run(&self, name: &str, duration: Duration, succeeded: bool) {
+        self.runs.with_label_values(&[name]).inc();
+        self.run_duration
+            .with_label_values(&[name])
+            .observe(duration.as_secs_f64());
+
+        if succeeded {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system time to be after the unix epoch");
+            self.last_success
+                .with_label_values(&[name])
+                .set(i64::try_from(now.as_secs()).unwrap_or(i64::MAX));
+        } else {
+            self.failures.with_label_values(&[name]).inc();
+        }
+    }
+
+    fn consecutive_failures(&self, name: &str, consecutive_failures: u32) {
+        self.consecutive_failures
+            .with_label_values(&[name])
+            .set(i64::from(consecutive_failures));
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        let runs = IntCounterVec::new(
+            Opts::new("runner_runs_total", "Number of runs per service."),
+            &["runner"],
+        )
+        .expect("runner_runs_total metric to be correctly configured");
+
+        let failures = IntCounterVec::new(
+            Opts::new(
+                "runner_failures_total",
+                "Number of failed runs per service.",
+            ),
+            &["runner"],
+        )
+        .expect("runner_failures_total metric to be correctly configured");
+
+        let consecutive_failures = IntGaugeVec::new(
+            Opts::new(
+                "runner_consecutive_failures",
+                "Number of runs that failed in a row since the last success.",
+            ),
+            &["runner"],
+        )
+        .expect("runner_consecutive_failures metric to be correctly configured");
+
+        let run_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "runner_run_duration_seconds",
+                "Duration of a single run per service.",
+            )
+            .buckets(exponential_buckets(0.01, 2.0, 16).expect("valid buckets")),
+            &["runner"],
+        )
+        .expect("runner_run_duration_seconds metric to be correctly configured");
+
+        let last_success = IntGaugeVec::new(
+            Opts::new(
+                "runner_last_success_timestamp_seconds",
+                "Unix timestamp of the last successful run per service.",
+            ),
+            &["runner"],
+        )
+        .expect("runner_last_success_timestamp_seconds metric to be correctly configured");
+
+        Self {
+            runs,
+            failures,
+            consecutive_failures,
+            run_duration,
+            last_success,
+        }
+    }
+}
+
+impl RegistersMetrics for Scheduler {
+    fn metrics(&self) -> Vec<Box<dyn Collector>> {
+        vec![
+            Box::new(self.metrics.runs.clone()),
+            Box::new(self.metrics.failures.clone()),
+            Box::new(self.metrics.consecutive_failures.clone()),
+            Box::new(self.metrics.run_duration.clone()),
+            Box::new(self.metrics.last_success.clone()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::next_delay;
+
+    #[test]
+    fn polls_at_the_regular_interval_without_failures() {
+        // given
+        let interval = Duration::from_secs(5);
+
+        // when
+        let delay = next_delay(interval, Duration::from_secs(300), 0);
+
+        // then
+        assert_eq!(delay, interval);
+    }
+
+    #[test]
+    fn backoff_grows_with_consecutive_failures() {
+        // given
+        let interval = Duration::from_secs(5);
+        let max = Duration::from_secs(300);
+
+        for failures in 1..=5 {
+            // when
+            let delay = next_delay(interval, max, failures);
+
+            // then
+            let backoff = interval * 2u32.pow(failures);
+            assert!(
+                delay >= backoff / 2 && delay <= backoff,
+                "{delay:?} for {failures}"
+            );
+        }
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        // given
+        let max = Duration::from_secs(300);
+
+        // when
+        let delay = next_delay(Duration::from_secs(5), max, 100);
+
+        // then
+        assert!(delay >= max / 2 && delay <= max);
+    }
+
+    #[test]
+    fn backoff_never_polls_faster_than_the_interval() {
+        // given
+        let interval = Duration::from_secs(60);
+
+        // when
+        let delay = next_delay(interval, Duration::from_secs(10), 3);
+
+        // then
+        assert_eq!(delay, interval);
+    }
+}
@@ -7,31 +7,52 @@ use metrics::{
     HealthChecker, RegistersMetrics,
     prometheus::{IntGauge, Registry},
 };
+use opentelemetry::{KeyValue, trace::TracerProvider as _};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{Resource, trace::TracerProvider};
 use services::{
     BlockBundler, BlockBundlerConfig, Runner,
-    block_committer::{port::l1::Contract, service::BlockCommitter},
+    admin::service::PauseSync,
+    block_committer::{
+        port::l1::Contract,
+        service::{BlockCommitter, ReconciliationConfig},
+    },
+    commit_watcher::service::CommitWatcher,
+    cost_feed::service::CostFeed,
+    critical_section::CriticalSections,
+    events::service::{EventBus, EventRecorder},
     fee_metrics_tracker::service::FeeMetricsTracker,
     fees::cache::CachingApi,
-    state_committer::port::Storage,
+    leader_election::service::{LeaderElection, Leadership},
+    state_committer::{
+        port::Storage,
+        service::{FeeDecisions, ForcedActions},
+    },
     state_listener::service::StateListener,
     state_pruner::service::StatePruner,
-    wallet_balance_tracker::service::WalletBalanceTracker,
+    wallet_balance_tracker::service::{WalletBalanceTracker, WalletBalances},
 };
-use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
-use tracing::{error, info};
-
-use crate::{Database, FuelApi, L1, config, errors::Result};
+use tracing::{error, info, warn};
+use tracing_subscriber::{EnvFilter, Layer, layer::SubscriberExt, util::SubscriberInitExt};
+
+use crate::{
+    Database, FuelApi, L1,
+    config::{self, Role},
+    errors::{Error, Result},
+    scheduler::{RunnerHandle, Scheduler},
+};
 
 pub fn wallet_balance_tracker(
     internal_config: &config::Internal,
     registry: &Registry,
     l1: L1,
-    cancel_token: CancellationToken,
-) -> tokio::task::JoinHandle<()> {
+    scheduler: &Scheduler,
+    wallet_balances: WalletBalances,
+) -> RunnerHandle {
     let contract_caller_address = l1.contract_caller_address();
     let blob_posting_address = l1.blob_poster_address();
-    let mut wallet_balance_tracker = WalletBalanceTracker::new(l1);
+    let mut wallet_balance_tracker = WalletBalanceTracker::new(l1).with_balances(wallet_balances);
 
     wallet_balance_tracker.track_address("contract_caller", contract_caller_address);
     if let Some(address) = blob_posting_address {
@@ -41,22 +62,36 @@ pub fn wallet_balance_tracker(
     // to be called only after all `track_address` calls
     wallet_balance_tracker.register_metrics(registry);
 
-    schedule_polling(
+    scheduler.schedule(
         internal_config.balance_update_interval,
         wallet_balance_tracker,
         "Wallet Balance Tracker",
-        cancel_token,
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn block_committer(
     l1: L1,
     storage: Database,
     fuel: FuelApi,
     config: &config::Config,
-    cancel_token: CancellationToken,
-) -> tokio::task::JoinHandle<()> {
+    scheduler: &Scheduler,
+    registry: &Registry,
+    leadership: Leadership,
+    critical_sections: CriticalSections,
+    events: EventBus,
+) -> RunnerHandle {
     let commit_interval = l1.commit_interval();
+    let num_commit_slots = l1.num_commit_slots();
+    // the block ids are cross-checked with every other endpoint, so the main one must not fail
+    // over to any of them
+    let (fuel, other_fuel_nodes) = if config.fuel.block_id_quorum.get() > 1 {
+        let mut endpoints = fuel.endpoints();
+        let primary = endpoints.remove(0);
+        (primary, endpoints)
+    } else {
+        (fuel, vec![])
+    };
     let block_committer = BlockCommitter::new(
         l1,
         storage,
@@ -64,23 +99,62 @@ pub fn block_committer(
         SystemClock,
         commit_interval,
         config.app.num_blocks_to_finalize_tx,
-    );
+    )
+    .with_block_id_quorum(other_fuel_nodes, config.fuel.block_id_quorum)
+    .with_gas_bump_timeout(config.app.block_commit.gas_bump_timeout)
+    .with_reconciliation(ReconciliationConfig {
+        interval: config.app.block_commit.reconciliation_interval,
+        lookback: config.app.block_commit.reconciliation_lookback,
+        recommit_current_epoch: config.app.block_commit.recommit_on_mismatch,
+        num_commit_slots,
+    })
+    .with_critical_sections(critical_sections)
+    .with_events(events);
+
+    block_committer.register_metrics(registry);
 
-    schedule_polling(
+    scheduler.schedule_for_leader(
         config.app.block_check_interval,
         block_committer,
         "Block Committer",
-        cancel_token,
+        leadership,
     )
 }
 
+/// Returns the watcher's health check alongside its handle, for the health reporter.
+pub fn commit_watcher(
+    l1: L1,
+    storage: Database,
+    fuel: FuelApi,
+    config: &config::Config,
+    scheduler: &Scheduler,
+    registry: &Registry,
+) -> (RunnerHandle, HealthChecker) {
+    let commit_interval = l1.commit_interval();
+    let commit_watcher = CommitWatcher::new(l1, fuel, storage, SystemClock, commit_interval);
+    let health_check = commit_watcher.health_checker();
+
+    commit_watcher.register_metrics(registry);
+
+    // not leader only, a standby instance should notice foreign commits as well
+    let handle = scheduler.schedule(
+        config.app.block_check_interval,
+        commit_watcher,
+        "Commit Watcher",
+    );
+
+    (handle, health_check)
+}
+
 pub fn block_bundler(
     fuel: FuelApi,
     storage: Database,
-    cancel_token: CancellationToken,
+    scheduler: &Scheduler,
     config: &config::Config,
     registry: &Registry,
-) -> tokio::task::JoinHandle<()> {
+    leadership: Leadership,
+    events: EventBus,
+) -> RunnerHandle {
     let bundler_factory = services::BundlerFactory::new(
         BlobEncoder,
         bundle::Encoder::new(config.app.bundle.compression_level),
@@ -104,27 +178,35 @@ pub fn block_bundler(
                 .try_into()
                 .expect("num cpus not zero"),
         },
-    );
+    )
+    .with_events(events)
+    .with_leadership(leadership.clone());
 
     block_bundler.register_metrics(registry);
 
-    schedule_polling(
+    scheduler.schedule_for_leader(
         config.app.bundle.new_bundle_check_interval,
         block_bundler,
         "Block Bundler",
-        cancel_token,
+        leadership,
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn state_committer(
     fuel: FuelApi,
     l1: L1,
     storage: Database,
-    cancel_token: CancellationToken,
+    scheduler: &Scheduler,
     config: &config::Config,
     registry: &Registry,
     fee_api: CachingApi<L1>,
-) -> Result<tokio::task::JoinHandle<()>> {
+    leadership: Leadership,
+    critical_sections: CriticalSections,
+    fee_decisions: FeeDecisions,
+    forced_actions: ForcedActions,
+    events: EventBus,
+) -> Result<RunnerHandle> {
     let state_committer = services::StateCommitter::new(
         l1,
         fuel,
@@ -138,25 +220,29 @@ pub fn state_committer(
         },
         SystemClock,
         fee_api,
-    );
+    )
+    .with_critical_sections(critical_sections)
+    .with_fee_decisions(fee_decisions)
+    .with_forced_actions(forced_actions)
+    .with_events(events);
 
     state_committer.register_metrics(registry);
 
-    Ok(schedule_polling(
+    Ok(scheduler.schedule_for_leader(
         config.app.tx_finalization_check_interval,
         state_committer,
         "State Committer",
-        cancel_token,
+        leadership,
     ))
 }
 
 pub fn block_importer(
     fuel: FuelApi,
     storage: Database,
-    cancel_token: CancellationToken,
+    scheduler: &Scheduler,
     config: &config::Config,
     internal_config: &config::Internal,
-) -> tokio::task::JoinHandle<()> {
+) -> RunnerHandle {
     let block_importer = services::block_importer::service::BlockImporter::new(
         storage,
         fuel,
@@ -165,11 +251,10 @@ pub fn block_importer(
         internal_config.import_batches.max_cumulative_size,
     );
 
-    schedule_polling(
+    scheduler.schedule(
         config.app.block_check_interval,
         block_importer,
         "State Importer",
-        cancel_token,
     )
 }
 
@@ -181,47 +266,119 @@ pub fn last_finalization_metric() -> IntGauge {
     .expect("seconds_since_last_finalized_fragment gauge to be correctly configured")
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn state_listener(
     l1: L1,
     storage: Database,
-    cancel_token: CancellationToken,
+    scheduler: &Scheduler,
     registry: &Registry,
     config: &config::Config,
     last_finalization: IntGauge,
-) -> tokio::task::JoinHandle<()> {
+    leadership: Leadership,
+    events: EventBus,
+) -> RunnerHandle {
     let state_listener = StateListener::new(
         l1,
         storage,
         config.app.num_blocks_to_finalize_tx,
         SystemClock,
         last_finalization,
-    );
+    )
+    .with_events(events);
 
     state_listener.register_metrics(registry);
 
-    schedule_polling(
+    scheduler.schedule_for_leader(
         config.app.block_check_interval,
         state_listener,
         "State Listener",
-        cancel_token,
+        leadership,
+    )
+}
+
+pub fn event_recorder(
+    storage: Database,
+    scheduler: &Scheduler,
+    config: &config::Config,
+    events: EventBus,
+) -> RunnerHandle {
+    // not leader only, a former leader still has to record the events published before it stepped
+    // down
+    let event_recorder = EventRecorder::new(storage, events, SystemClock);
+
+    scheduler.schedule(
+        config.internal.event_recording_interval,
+        event_recorder,
+        "Event Recorder",
     )
 }
 
+/// Applies the stored pauses once upfront, before the runners are scheduled, and then keeps them in
+/// sync with the other instances.
+pub async fn pause_sync(
+    storage: Database,
+    scheduler: &Scheduler,
+    config: &config::Config,
+) -> RunnerHandle {
+    let mut pause_sync = PauseSync::new(storage, scheduler.runner_controls());
+
+    if let Err(e) = pause_sync.run().await {
+        error!("Pause Sync encountered an error: {e}");
+    }
+
+    scheduler.schedule(
+        config.internal.pause_sync_interval,
+        pause_sync,
+        "Pause Sync",
+    )
+}
+
+pub fn cost_feed(
+    url: &str,
+    storage: Database,
+    scheduler: &Scheduler,
+    registry: &Registry,
+    config: &config::Config,
+    leadership: Leadership,
+) -> Result<RunnerHandle> {
+    let cost_feed_config = &config.app.cost_feed;
+    let url = url
+        .parse()
+        .map_err(|e| Error::Other(format!("invalid cost feed url: {e}")))?;
+    let sink = webhook::HttpSink::new(
+        url,
+        cost_feed_config.auth_token.clone(),
+        cost_feed_config.request_timeout,
+    )?;
+
+    let cost_feed = CostFeed::new(sink, storage, SystemClock, cost_feed_config.batch_size);
+
+    cost_feed.register_metrics(registry);
+
+    Ok(scheduler.schedule_for_leader(
+        cost_feed_config.check_interval,
+        cost_feed,
+        "Cost Feed",
+        leadership,
+    ))
+}
+
 pub fn state_pruner(
     storage: Database,
-    cancel_token: CancellationToken,
+    scheduler: &Scheduler,
     registry: &Registry,
     config: &config::Config,
-) -> tokio::task::JoinHandle<()> {
+    leadership: Leadership,
+) -> RunnerHandle {
     let state_pruner = StatePruner::new(storage, SystemClock, config.app.state_pruner_retention);
 
     state_pruner.register_metrics(registry);
 
-    schedule_polling(
+    scheduler.schedule_for_leader(
         config.app.state_pruner_run_interval,
         state_pruner,
-        "State Listener",
-        cancel_token,
+        "State Pruner",
+        leadership,
     )
 }
 
@@ -230,13 +387,23 @@ pub async fn l1_adapter(
     internal_config: &config::Internal,
     registry: &Registry,
 ) -> Result<(L1, HealthChecker)> {
+    let eth = config
+        .eth
+        .as_ref()
+        .ok_or_else(|| crate::errors::Error::Other("missing `eth` configuration".to_string()))?;
+
     let l1 = L1::connect(
-        config.eth.rpc.clone(),
-        config.eth.state_contract_address,
-        Signers::for_keys(config.eth.l1_keys.clone()).await?,
+        eth.rpc.clone(),
+        eth.state_contract_address,
+        Signers::for_keys(eth.l1_keys.clone()).await?,
         internal_config.eth_errors_before_unhealthy,
         eth::TxConfig {
             tx_max_fee: u128::from(config.app.tx_fees.max),
+            commit_max_fee: config
+                .app
+                .block_commit
+                .max_fee
+                .map_or(u128::MAX, u128::from),
             send_tx_request_timeout: config.app.send_tx_request_timeout,
             acceptable_priority_fee_percentage: AcceptablePriorityFeePercentages::new(
                 config.app.tx_fees.min_reward_perc,
@@ -253,27 +420,80 @@ pub async fn l1_adapter(
     Ok((l1, health_check))
 }
 
-fn schedule_polling(
-    polling_interval: Duration,
-    mut runner: impl Runner + 'static,
-    name: &'static str,
-    cancel_token: CancellationToken,
-) -> tokio::task::JoinHandle<()> {
-    tokio::spawn(async move {
-        loop {
-            if let Err(e) = runner.run().await {
-                error!("{name} encountered an error: {e}");
-            }
+pub fn leader_election(
+    storage: Database,
+    config: &config::Config,
+    registry: &Registry,
+    forced_actions: ForcedActions,
+) -> Option<LeaderElection<Database>> {
+    let leader_election_config = &config.app.leader_election;
+    if !leader_election_config.enabled {
+        return None;
+    }
 
-            if cancel_token.is_cancelled() {
-                break;
-            }
+    let leader_election = LeaderElection::new(
+        storage,
+        leader_election_config.candidate_id.clone(),
+        leader_election_config.lease_duration,
+    )
+    .with_forced_actions(Role::StateCommitter.name(), forced_actions);
+
+    leader_election.register_metrics(registry);
+
+    Some(leader_election)
+}
 
-            tokio::time::sleep(polling_interval).await;
+/// The leadership of `lease`, or an unconditional one if leader election is disabled.
+pub async fn leadership(
+    leader_election: &mut Option<LeaderElection<Database>>,
+    lease: &'static str,
+) -> Leadership {
+    let Some(leader_election) = leader_election else {
+        return Leadership::unconditional();
+    };
+
+    let leadership = leader_election.leadership(lease);
+
+    // Campaign right away so that the leader doesn't have to wait a full polling interval before
+    // starting the service.
+    if let Err(e) = leader_election.campaign().await {
+        error!("Leader Election encountered an error: {e}");
+    }
+
+    leadership
+}
+
+/// Keeps renewing the leases handed out by [`leadership`].
+pub fn schedule_leader_election(
+    leader_election: LeaderElection<Database>,
+    scheduler: &Scheduler,
+    config: &config::Config,
+) -> RunnerHandle {
+    scheduler.schedule(
+        config.app.leader_election.renewal_interval,
+        leader_election,
+        "Leader Election",
+    )
+}
+
+/// Resolves once `SIGTERM` or `SIGINT` is received, returning the signal's name.
+pub async fn shutdown_signal() -> Result<&'static str> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut terminate = signal(SignalKind::terminate())?;
+        tokio::select! {
+            _ = terminate.recv() => Ok("SIGTERM"),
+            result = tokio::signal::ctrl_c() => result.map(|_| "SIGINT").map_err(Into::into),
         }
+    }
 
-        info!("{name} stopped");
-    })
+    #[cfg(not(unix))]
+    {
+        tokio::signal::ctrl_c().await?;
+        Ok("SIGINT")
+    }
 }
 
 pub fn fuel_adapter(
@@ -283,8 +503,10 @@ pub fn fuel_adapter(
 ) -> (FuelApi, HealthChecker) {
     let fuel_adapter = FuelApi::new(
         &config.fuel.graphql_endpoint,
+        &config.fuel.additional_graphql_endpoints,
         internal_config.fuel_errors_before_unhealthy,
         config.fuel.num_buffered_requests,
+        config.fuel.request_timeout,
     );
     fuel_adapter.register_metrics(registry);
 
@@ -293,13 +515,73 @@ pub fn fuel_adapter(
     (fuel_adapter, fuel_connection_health)
 }
 
-pub fn logger() {
-    tracing_subscriber::fmt()
+/// Keeps the trace exporter alive. Call [`Tracing::shut_down`] before exiting to flush the spans
+/// not exported yet.
+pub struct Tracing {
+    tracer_provider: Option<TracerProvider>,
+}
+
+impl Tracing {
+    pub fn shut_down(self) {
+        if let Some(tracer_provider) = self.tracer_provider {
+            if let Err(e) = tracer_provider.shutdown() {
+                error!("Failed to flush the exported traces: {e}");
+            }
+        }
+    }
+}
+
+pub fn logger(config: &config::LoggingConfig) -> Result<Tracing> {
+    let filter = match &config.filter {
+        Some(directives) => {
+            EnvFilter::try_new(directives).map_err(|e| Error::Other(e.to_string()))?
+        }
+        None => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+    };
+
+    let fmt = tracing_subscriber::fmt::layer()
         .with_writer(std::io::stderr)
         .with_level(true)
-        .with_line_number(true)
-        .json()
+        .with_line_number(true);
+    let fmt = match config.format {
+        config::LogFormat::Json => fmt.json().boxed(),
+        config::LogFormat::Pretty => fmt.pretty().boxed(),
+        config::LogFormat::Compact => fmt.compact().boxed(),
+    };
+
+    let tracer_provider = config
+        .otlp_endpoint
+        .as_deref()
+        .map(tracer_provider)
+        .transpose()?;
+    let otel = tracer_provider.as_ref().map(|tracer_provider| {
+        tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer(SERVICE_NAME))
+    });
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt)
+        .with(otel)
         .init();
+
+    Ok(Tracing { tracer_provider })
+}
+
+const SERVICE_NAME: &str = "fuel-block-committer";
+
+fn tracer_provider(endpoint: &str) -> Result<TracerProvider> {
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()
+        .map_err(|e| Error::Other(format!("failed to create the OTLP exporter: {e}")))?;
+
+    let tracer_provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, opentelemetry_sdk::runtime::Tokio)
+        .with_resource(Resource::new([KeyValue::new("service.name", SERVICE_NAME)]))
+        .build();
+
+    Ok(tracer_provider)
 }
 
 pub async fn storage(
@@ -307,7 +589,10 @@ pub async fn storage(
     registry: &Registry,
     last_finalization: &IntGauge,
 ) -> Result<Database> {
-    let postgres = Database::connect(&config.app.db).await?;
+    let mut postgres = Database::connect(&config.app.db).await?;
+    if config.app.cost_feed.url.is_some() {
+        postgres = postgres.with_cost_feed_outbox();
+    }
     postgres.migrate().await?;
 
     postgres.register_metrics(registry);
@@ -319,36 +604,86 @@ pub async fn storage(
     Ok(postgres)
 }
 
+/// Lets every runner finish its current run for up to `timeout`, then aborts the remaining ones.
+/// Critical sections that have already started are always waited for. A failed runner doesn't cut
+/// the shutdown short, the first failure is returned once everything is stopped.
 pub async fn shut_down(
     cancel_token: CancellationToken,
-    handles: Vec<JoinHandle<()>>,
+    handles: Vec<RunnerHandle>,
+    critical_sections: CriticalSections,
+    timeout: Duration,
+    leader_election: Option<LeaderElection<Database>>,
     storage: Database,
 ) -> Result<()> {
     cancel_token.cancel();
 
-    for handle in handles {
-        handle.await?;
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut unfinished = vec![];
+    let mut first_failure = None;
+    for RunnerHandle { name, mut handle } in handles {
+        match tokio::time::timeout_at(deadline, &mut handle).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                error!("{name} failed: {e}");
+                first_failure.get_or_insert(e);
+            }
+            Err(_) => unfinished.push(RunnerHandle { name, handle }),
+        }
+    }
+
+    if !unfinished.is_empty() {
+        let names = unfinished
+            .iter()
+            .map(|runner| runner.name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        warn!(
+            "runners did not finish within {}: {names}",
+            humantime::format_duration(timeout)
+        );
+    }
+
+    critical_sections.close().await;
+
+    for RunnerHandle { name, handle } in unfinished {
+        handle.abort();
+        if let Err(e) = handle.await {
+            if e.is_panic() {
+                error!("{name} panicked: {e}");
+            }
+        }
+        info!("{name} aborted");
+    }
+
+    // Only resign once all services stopped so that the next leader doesn't run concurrently.
+    if let Some(leader_election) = leader_election {
+        if let Err(e) = leader_election.resign().await {
+            error!("Failed to release the leader lease: {e}");
+        }
     }
 
     storage.close().await;
-    Ok(())
+
+    match first_failure {
+        Some(e) => Err(e.into()),
+        None => Ok(()),
+    }
 }
 
 pub fn fee_metrics_tracker(
     api: CachingApi<L1>,
-    cancel_token: CancellationToken,
+    scheduler: &Scheduler,
     config: &config::Config,
     registry: &Registry,
-) -> Result<tokio::task::JoinHandle<()>> {
+) -> Result<RunnerHandle> {
     let fee_metrics_tracker = FeeMetricsTracker::new(api);
 
     fee_metrics_tracker.register_metrics(registry);
 
-    let handle = schedule_polling(
+    let handle = scheduler.schedule(
         config.app.l1_fee_check_interval,
         fee_metrics_tracker,
         "Fee Tracker",
-        cancel_token,
     );
 
     Ok(handle)
@@ -43,6 +43,7 @@ impl DeployedContract {
             5,
             eth::TxConfig {
                 tx_max_fee,
+                commit_max_fee: u128::MAX,
                 send_tx_request_timeout,
                 acceptable_priority_fee_percentage: AcceptablePriorityFeePercentages::default(),
             },
@@ -22,11 +22,16 @@ macro_rules! impl_port {
 }
 
 impl_port!(
+    admin,
     state_pruner,
     state_listener,
     state_committer,
     block_bundler,
-    block_committer
+    block_committer,
+    cost_feed,
+    commit_watcher,
+    events,
+    status_reporter
 );
 
 #[cfg(feature = "test-helpers")]
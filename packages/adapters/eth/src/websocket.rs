@@ -1,23 +1,33 @@
-use std::{num::NonZeroU32, ops::RangeInclusive, str::FromStr, time::Duration};
+use std::{
+    num::NonZeroU32,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, PoisonError, RwLock},
+    time::{Duration, Instant, SystemTime},
+};
 
 use ::metrics::{HealthChecker, RegistersMetrics, prometheus::core::Collector};
 use alloy::{
-    consensus::SignableTransaction,
-    network::TxSigner,
+    consensus::{SignableTransaction, TxEnvelope, TypedTransaction},
+    network::{Ethereum, NetworkWallet, TxSigner},
     primitives::{Address, B256, ChainId},
     rpc::types::FeeHistory,
     signers::{Signature, local::PrivateKeySigner},
 };
 use delegate::delegate;
+use futures::{StreamExt, stream::BoxStream};
 use serde::Deserialize;
 use services::{
     Result,
+    commit_watcher::port::ObservedCommit,
     state_committer::port::l1::Priority,
     types::{
         BlockSubmissionTx, Fragment, FragmentsSubmitted, L1Height, L1Tx, NonEmpty,
         TransactionResponse, U256,
     },
 };
+use tracing::{info, warn};
 use url::Url;
 
 use self::{
@@ -32,19 +42,37 @@ mod health_tracking_middleware;
 #[derive(Clone)]
 pub struct WebsocketClient {
     inner: HealthTrackingMiddleware<WsConnection>,
-    blob_poster_address: Option<Address>,
-    contract_caller_address: Address,
+    main_wallet: SignerWallet,
+    blob_wallet: Option<SignerWallet>,
 }
 
 impl services::block_committer::port::l1::Contract for WebsocketClient {
     delegate! {
         to self {
-            async fn submit(&self, hash: [u8; 32], height: u32) -> Result<BlockSubmissionTx>;
+            async fn submit(
+                &self,
+                hash: [u8; 32],
+                height: u32,
+                previous_tx: Option<BlockSubmissionTx>
+            ) -> Result<BlockSubmissionTx>;
+            async fn block_hash_at_commit_height(
+                &self,
+                commit_height: u32
+            ) -> Result<Option<[u8; 32]>>;
             fn commit_interval(&self) -> NonZeroU32;
         }
     }
 }
 
+impl services::commit_watcher::port::l1::Api for WebsocketClient {
+    delegate! {
+        to (*self) {
+            async fn subscribe_commits(&self) -> Result<BoxStream<'static, Result<ObservedCommit>>>;
+            async fn commits_since(&self, l1_block: u64) -> Result<Vec<ObservedCommit>>;
+        }
+    }
+}
+
 impl services::state_listener::port::l1::Api for WebsocketClient {
     delegate! {
         to (*self) {
@@ -121,6 +149,15 @@ impl services::state_committer::port::l1::Api for WebsocketClient {
 pub enum L1Key {
     Kms(String),
     Private(String),
+    /// A file containing the hex encoded private key, e.g. a mounted Kubernetes secret. Read again
+    /// whenever the file changes, so a rotated secret takes effect without a restart.
+    PrivateFile(PathBuf),
+    /// An encrypted JSON keystore along with the file containing its password. Read once when the
+    /// signers are created.
+    Keystore {
+        path: PathBuf,
+        password_file: PathBuf,
+    },
 }
 
 impl<'a> serde::Deserialize<'a> for L1Key {
@@ -136,6 +173,23 @@ impl<'a> serde::Deserialize<'a> for L1Key {
             .and_then(|s| s.strip_suffix(')'))
         {
             Ok(L1Key::Private(k.to_string()))
+        } else if let Some(path) = value
+            .strip_prefix("PrivateFile(")
+            .and_then(|s| s.strip_suffix(')'))
+        {
+            Ok(L1Key::PrivateFile(PathBuf::from(path.trim())))
+        } else if let Some(args) = value
+            .strip_prefix("Keystore(")
+            .and_then(|s| s.strip_suffix(')'))
+        {
+            let (path, password_file) = args.split_once(',').ok_or_else(|| {
+                serde::de::Error::custom("expected `Keystore(<path>, <password file>)`")
+            })?;
+
+            Ok(L1Key::Keystore {
+                path: PathBuf::from(path.trim()),
+                password_file: PathBuf::from(password_file.trim()),
+            })
         } else {
             Err(serde::de::Error::custom("invalid L1Key format"))
         }
@@ -159,6 +213,8 @@ impl L1Keys {
 #[derive(Debug, Clone)]
 pub struct TxConfig {
     pub tx_max_fee: u128,
+    /// Maximum fee, in wei, a block commit tx replacing a pending one may cost.
+    pub commit_max_fee: u128,
     pub send_tx_request_timeout: Duration,
     pub acceptable_priority_fee_percentage: AcceptablePriorityFeePercentages,
 }
@@ -168,6 +224,7 @@ impl Default for TxConfig {
     fn default() -> Self {
         Self {
             tx_max_fee: u128::MAX,
+            commit_max_fee: u128::MAX,
             send_tx_request_timeout: Duration::from_secs(10),
             acceptable_priority_fee_percentage: AcceptablePriorityFeePercentages::default(),
         }
@@ -256,7 +313,103 @@ impl alloy::signers::Signer<Signature> for Signer {
     }
 }
 
+/// A wallet around a single signer. Unlike `EthereumWallet`, which registers signers under the
+/// address they had when added, the address is asked from the signer on every use so that a
+/// re-read key file takes effect.
+#[derive(Clone)]
+pub(crate) struct SignerWallet(Arc<dyn TxSigner<Signature> + Send + Sync>);
+
+impl SignerWallet {
+    pub(crate) fn new(signer: impl TxSigner<Signature> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(signer))
+    }
+
+    pub(crate) fn address(&self) -> Address {
+        self.0.address()
+    }
+
+    async fn sign(
+        &self,
+        sender: Address,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> alloy::signers::Result<Signature> {
+        // the key might have been replaced since the tx was filled in for `sender`
+        if sender != self.address() {
+            return Err(alloy::signers::Error::other(format!(
+                "Missing signing credential for {sender}"
+            )));
+        }
+
+        self.0.sign_transaction(tx).await
+    }
+}
+
+impl std::fmt::Debug for SignerWallet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SignerWallet")
+            .field(&self.address())
+            .finish()
+    }
+}
+
+impl NetworkWallet<Ethereum> for SignerWallet {
+    fn default_signer_address(&self) -> Address {
+        self.address()
+    }
+
+    fn has_signer_for(&self, address: &Address) -> bool {
+        *address == self.address()
+    }
+
+    fn signer_addresses(&self) -> impl Iterator<Item = Address> {
+        std::iter::once(self.address())
+    }
+
+    async fn sign_transaction_from(
+        &self,
+        sender: Address,
+        tx: TypedTransaction,
+    ) -> alloy::signers::Result<TxEnvelope> {
+        match tx {
+            TypedTransaction::Legacy(mut t) => {
+                let sig = self.sign(sender, &mut t).await?;
+                Ok(t.into_signed(sig).into())
+            }
+            TypedTransaction::Eip2930(mut t) => {
+                let sig = self.sign(sender, &mut t).await?;
+                Ok(t.into_signed(sig).into())
+            }
+            TypedTransaction::Eip1559(mut t) => {
+                let sig = self.sign(sender, &mut t).await?;
+                Ok(t.into_signed(sig).into())
+            }
+            TypedTransaction::Eip4844(mut t) => {
+                let sig = self.sign(sender, &mut t).await?;
+                Ok(t.into_signed(sig).into())
+            }
+            TypedTransaction::Eip7702(mut t) => {
+                let sig = self.sign(sender, &mut t).await?;
+                Ok(t.into_signed(sig).into())
+            }
+        }
+    }
+}
+
 impl Signer {
+    async fn for_key(aws_client: Option<&AwsClient>, key: L1Key) -> Result<Self> {
+        match key {
+            L1Key::Kms(key) => {
+                Self::make_aws_signer(aws_client.expect("is set for kms keys"), key).await
+            }
+            L1Key::Private(key) => Self::make_private_key_signer(&key),
+            L1Key::PrivateFile(path) => Self::make_private_key_file_signer(&path),
+            L1Key::Keystore {
+                path,
+                password_file,
+            } => Self::make_keystore_signer(&path, &password_file),
+        }
+    }
+
     pub async fn make_aws_signer(client: &AwsClient, key: String) -> Result<Self> {
         let signer = client.make_signer(key).await?;
 
@@ -273,6 +426,160 @@ impl Signer {
             signer: Box::new(signer),
         })
     }
+
+    pub fn make_private_key_file_signer(path: &Path) -> Result<Self> {
+        Ok(Signer {
+            signer: Box::new(KeyFileSigner::load(path)?),
+        })
+    }
+
+    pub fn make_keystore_signer(path: &Path, password_file: &Path) -> Result<Self> {
+        let password = read_secret(password_file)?;
+
+        let signer = PrivateKeySigner::decrypt_keystore(path, password).map_err(|e| {
+            services::Error::Other(format!(
+                "Could not decrypt keystore `{}`: {e}",
+                path.display()
+            ))
+        })?;
+
+        Ok(Signer {
+            signer: Box::new(signer),
+        })
+    }
+}
+
+/// Reads a secret from a file, ignoring surrounding whitespace such as a trailing newline.
+fn read_secret(path: &Path) -> Result<String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| services::Error::Other(format!("Could not read `{}`: {e}", path.display())))?;
+
+    Ok(contents.trim().to_string())
+}
+
+fn read_private_key(path: &Path) -> Result<PrivateKeySigner> {
+    PrivateKeySigner::from_str(&read_secret(path)?)
+        .map_err(|_| services::Error::Other(format!("Invalid private key in `{}`", path.display())))
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// How long a key file is trusted to be unchanged before its modification time is checked again.
+const KEY_FILE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Signs with the private key in a file, reading it again whenever the file's modification time
+/// changes. The modification time is checked at most once per `check_interval`, so that signing
+/// doesn't hit the filesystem every time. The previous key stays in use while the file can't be
+/// read or holds no valid key.
+struct KeyFileSigner {
+    path: PathBuf,
+    check_interval: Duration,
+    key: RwLock<LoadedKey>,
+}
+
+struct LoadedKey {
+    modified: Option<SystemTime>,
+    checked_at: Instant,
+    key: PrivateKeySigner,
+}
+
+impl KeyFileSigner {
+    fn load(path: &Path) -> Result<Self> {
+        let modified = modified(path);
+        let key = read_private_key(path)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            check_interval: KEY_FILE_CHECK_INTERVAL,
+            key: RwLock::new(LoadedKey {
+                modified,
+                checked_at: Instant::now(),
+                key,
+            }),
+        })
+    }
+
+    fn current(&self) -> PrivateKeySigner {
+        {
+            let loaded = self.key.read().unwrap_or_else(PoisonError::into_inner);
+            if loaded.checked_at.elapsed() < self.check_interval {
+                return loaded.key.clone();
+            }
+        }
+
+        let mut loaded = self.key.write().unwrap_or_else(PoisonError::into_inner);
+        // another caller may have checked while we waited for the lock
+        if loaded.checked_at.elapsed() < self.check_interval {
+            return loaded.key.clone();
+        }
+
+        loaded.checked_at = Instant::now();
+        let modified = modified(&self.path);
+        if modified.is_none() || modified == loaded.modified {
+            return loaded.key.clone();
+        }
+
+        match read_private_key(&self.path) {
+            Ok(mut key) => {
+                alloy::signers::Signer::set_chain_id(&mut key, loaded.key.chain_id());
+                if key.address() != loaded.key.address() {
+                    info!(
+                        "key in `{}` changed from {} to {}",
+                        self.path.display(),
+                        loaded.key.address(),
+                        key.address()
+                    );
+                }
+                loaded.key = key;
+            }
+            Err(e) => warn!("keeping the previous key: {e}"),
+        }
+        // a file that failed to load is retried once it changes again
+        loaded.modified = modified;
+
+        loaded.key.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl TxSigner<Signature> for KeyFileSigner {
+    fn address(&self) -> Address {
+        self.current().address()
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> alloy::signers::Result<Signature> {
+        TxSigner::<Signature>::sign_transaction(&self.current(), tx).await
+    }
+}
+
+#[async_trait::async_trait]
+impl alloy::signers::Signer<Signature> for KeyFileSigner {
+    async fn sign_hash(&self, hash: &B256) -> alloy::signers::Result<Signature> {
+        self.current().sign_hash(hash).await
+    }
+
+    fn address(&self) -> Address {
+        self.current().address()
+    }
+
+    fn chain_id(&self) -> Option<ChainId> {
+        self.current().chain_id()
+    }
+
+    fn set_chain_id(&mut self, chain_id: Option<ChainId>) {
+        self.key
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .key
+            .set_chain_id(chain_id)
+    }
 }
 
 pub struct Signers {
@@ -289,17 +596,21 @@ impl Signers {
         };
 
         let blob_signer = match keys.blob {
-            Some(L1Key::Kms(key)) => {
-                Some(Signer::make_aws_signer(aws_client.as_ref().expect("is set"), key).await?)
-            }
-            Some(L1Key::Private(key)) => Some(Signer::make_private_key_signer(&key)?),
+            Some(key) => Some(Signer::for_key(aws_client.as_ref(), key).await?),
             None => None,
         };
 
-        let main_signer = match keys.main {
-            L1Key::Kms(key) => Signer::make_aws_signer(&aws_client.expect("is set"), key).await?,
-            L1Key::Private(key) => Signer::make_private_key_signer(&key)?,
-        };
+        let main_signer = Signer::for_key(aws_client.as_ref(), keys.main).await?;
+
+        // Keys given in different ways (e.g. inline and from a file) can still be the same key.
+        if blob_signer
+            .as_ref()
+            .is_some_and(|blob| TxSigner::address(blob) == TxSigner::address(&main_signer))
+        {
+            return Err(services::Error::Other(
+                "main and blob keys resolve to the same address".to_string(),
+            ));
+        }
 
         Ok(Self {
             main: main_signer,
@@ -316,18 +627,22 @@ impl WebsocketClient {
         unhealthy_after_n_errors: usize,
         tx_config: TxConfig,
     ) -> services::Result<Self> {
-        let blob_poster_address = signers
-            .blob
-            .as_ref()
-            .map(|signer| TxSigner::address(&signer));
-        let contract_caller_address = TxSigner::address(&signers.main);
-
-        let provider = WsConnection::connect(url, contract_address, signers, tx_config).await?;
+        let main_wallet = SignerWallet::new(signers.main);
+        let blob_wallet = signers.blob.map(SignerWallet::new);
+
+        let provider = WsConnection::connect(
+            url,
+            contract_address,
+            main_wallet.clone(),
+            blob_wallet.clone(),
+            tx_config,
+        )
+        .await?;
 
         Ok(Self {
             inner: HealthTrackingMiddleware::new(provider, unhealthy_after_n_errors),
-            blob_poster_address,
-            contract_caller_address,
+            main_wallet,
+            blob_wallet,
         })
     }
 
@@ -335,14 +650,24 @@ impl WebsocketClient {
         self.inner.connection_health_checker()
     }
 
-    pub(crate) async fn submit(&self, hash: [u8; 32], height: u32) -> Result<BlockSubmissionTx> {
-        Ok(self.inner.submit(hash, height).await?)
+    pub(crate) async fn submit(
+        &self,
+        hash: [u8; 32],
+        height: u32,
+        previous_tx: Option<BlockSubmissionTx>,
+    ) -> Result<BlockSubmissionTx> {
+        Ok(self.inner.submit(hash, height, previous_tx).await?)
     }
 
     pub(crate) fn commit_interval(&self) -> NonZeroU32 {
         self.inner.commit_interval()
     }
 
+    /// Number of commits the state contract keeps before reusing the slot of the oldest one.
+    pub fn num_commit_slots(&self) -> NonZeroU32 {
+        self.inner.num_commit_slots()
+    }
+
     pub(crate) async fn _get_block_number(&self) -> Result<u64> {
         Ok(self.inner.get_block_number().await?)
     }
@@ -387,20 +712,34 @@ impl WebsocketClient {
         Ok(self.inner.finalized(hash, height).await?)
     }
 
-    #[cfg(feature = "test-helpers")]
-    pub async fn block_hash_at_commit_height(&self, commit_height: u32) -> Result<[u8; 32]> {
+    pub async fn block_hash_at_commit_height(
+        &self,
+        commit_height: u32,
+    ) -> Result<Option<[u8; 32]>> {
         Ok(self
             .inner
             .block_hash_at_commit_height(commit_height)
             .await?)
     }
 
+    pub(crate) async fn subscribe_commits(
+        &self,
+    ) -> Result<BoxStream<'static, Result<ObservedCommit>>> {
+        let commits = self.inner.subscribe_commits().await?;
+
+        Ok(commits.map(|commit| Ok(commit?)).boxed())
+    }
+
+    pub(crate) async fn commits_since(&self, l1_block: u64) -> Result<Vec<ObservedCommit>> {
+        Ok(self.inner.commits_since(l1_block).await?)
+    }
+
     pub fn blob_poster_address(&self) -> Option<Address> {
-        self.blob_poster_address
+        self.blob_wallet.as_ref().map(SignerWallet::address)
     }
 
     pub fn contract_caller_address(&self) -> Address {
-        self.contract_caller_address
+        self.main_wallet.address()
     }
 }
 
@@ -413,10 +752,18 @@ impl RegistersMetrics for WebsocketClient {
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        path::Path,
+        time::{Duration, SystemTime},
+    };
+
+    use alloy::{hex, network::TxSigner, signers::local::PrivateKeySigner};
     use pretty_assertions::assert_eq;
     use services::state_committer::port::l1::Priority;
 
-    use super::L1Key;
+    use super::{KeyFileSigner, L1Key, L1Keys, Signer, SignerWallet, Signers};
+
+    const PRIVATE_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
 
     #[test]
     fn can_deserialize_private_key() {
@@ -442,6 +789,164 @@ mod tests {
         assert_eq!(key, L1Key::Kms("0x1234".to_owned()));
     }
 
+    #[test]
+    fn can_deserialize_private_key_file() {
+        // given
+        let val = r#""PrivateFile(/secrets/main)""#;
+
+        // when
+        let key: L1Key = serde_json::from_str(val).unwrap();
+
+        // then
+        assert_eq!(key, L1Key::PrivateFile("/secrets/main".into()));
+    }
+
+    #[test]
+    fn can_deserialize_keystore() {
+        // given
+        let val = r#""Keystore(/secrets/keystore.json, /secrets/password)""#;
+
+        // when
+        let key: L1Key = serde_json::from_str(val).unwrap();
+
+        // then
+        assert_eq!(
+            key,
+            L1Key::Keystore {
+                path: "/secrets/keystore.json".into(),
+                password_file: "/secrets/password".into(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_private_key_from_file() {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main");
+        std::fs::write(&path, format!("{PRIVATE_KEY}\n")).unwrap();
+        let keys = L1Keys {
+            main: L1Key::PrivateFile(path),
+            blob: None,
+        };
+
+        // when
+        let signers = Signers::for_keys(keys).await.unwrap();
+
+        // then
+        let expected = Signer::make_private_key_signer(PRIVATE_KEY).unwrap();
+        assert_eq!(
+            TxSigner::address(&signers.main),
+            TxSigner::address(&expected)
+        );
+    }
+
+    fn key_file_signer(path: &Path, check_interval: Duration) -> Signer {
+        Signer {
+            signer: Box::new(KeyFileSigner {
+                check_interval,
+                ..KeyFileSigner::load(path).unwrap()
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn picks_up_a_rotated_key_file() {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main");
+        std::fs::write(&path, PRIVATE_KEY).unwrap();
+        let wallet = SignerWallet::new(key_file_signer(&path, Duration::ZERO));
+
+        let rotated = PrivateKeySigner::random();
+        std::fs::write(&path, hex::encode_prefixed(rotated.to_bytes())).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(1))
+            .unwrap();
+
+        // when
+        let address = wallet.address();
+
+        // then
+        assert_eq!(address, rotated.address());
+    }
+
+    #[tokio::test]
+    async fn checks_the_key_file_at_most_once_per_interval() {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main");
+        std::fs::write(&path, PRIVATE_KEY).unwrap();
+        let wallet = SignerWallet::new(key_file_signer(&path, Duration::from_secs(60)));
+
+        std::fs::write(
+            &path,
+            hex::encode_prefixed(PrivateKeySigner::random().to_bytes()),
+        )
+        .unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(1))
+            .unwrap();
+
+        // when
+        let address = wallet.address();
+
+        // then
+        let expected = Signer::make_private_key_signer(PRIVATE_KEY).unwrap();
+        assert_eq!(address, TxSigner::address(&expected));
+    }
+
+    #[tokio::test]
+    async fn keeps_the_previous_key_if_the_rotated_file_is_invalid() {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main");
+        std::fs::write(&path, PRIVATE_KEY).unwrap();
+        let wallet = SignerWallet::new(key_file_signer(&path, Duration::ZERO));
+
+        std::fs::write(&path, "not a key").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(1))
+            .unwrap();
+
+        // when
+        let address = wallet.address();
+
+        // then
+        let expected = Signer::make_private_key_signer(PRIVATE_KEY).unwrap();
+        assert_eq!(address, TxSigner::address(&expected));
+    }
+
+    #[tokio::test]
+    async fn rejects_main_and_blob_keys_resolving_to_the_same_address() {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blob");
+        std::fs::write(&path, PRIVATE_KEY).unwrap();
+        let keys = L1Keys {
+            main: L1Key::Private(PRIVATE_KEY.to_owned()),
+            blob: Some(L1Key::PrivateFile(path)),
+        };
+
+        // when
+        let result = Signers::for_keys(keys).await;
+
+        // then
+        let Err(err) = result else {
+            panic!("expected an error");
+        };
+        assert!(err.to_string().contains("same address"));
+    }
+
     #[test]
     fn lowest_priority_gives_min_priority_fee_perc() {
         // given
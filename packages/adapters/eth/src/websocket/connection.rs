@@ -8,30 +8,34 @@ use alloy::{
         BlockNumberOrTag,
         eip4844::{BYTES_PER_BLOB, DATA_GAS_PER_BLOB},
     },
-    network::{Ethereum, EthereumWallet, TransactionBuilder, TransactionBuilder4844, TxSigner},
+    network::{Ethereum, TransactionBuilder, TransactionBuilder4844},
     primitives::{Address, U256},
     providers::{
-        Provider, ProviderBuilder, SendableTx, WsConnect,
+        Provider, ProviderBuilder, SendableTx, WalletProvider, WsConnect,
         utils::{EIP1559_FEE_ESTIMATION_PAST_BLOCKS, Eip1559Estimation},
     },
     pubsub::PubSubFrontend,
     rpc::types::{FeeHistory, TransactionReceipt, TransactionRequest},
     sol,
+    transports::RpcError,
 };
 use estimation::{MaxTxFeesPerGas, TransactionRequestExt};
+use futures::{StreamExt, stream::BoxStream};
 use itertools::Itertools;
 use metrics::{
     RegistersMetrics,
     prometheus::{self, histogram_opts},
 };
 use services::{
+    commit_watcher::port::ObservedCommit,
     state_committer::port::l1::Priority,
     types::{BlockSubmissionTx, Fragment, FragmentsSubmitted, L1Tx, NonEmpty, TransactionResponse},
 };
 use tracing::info;
 use url::Url;
 
-use super::{Signers, TxConfig, health_tracking_middleware::EthApi};
+use self::IFuelStateContract::IFuelStateContractErrors;
+use super::{SignerWallet, TxConfig, health_tracking_middleware::EthApi};
 use crate::{
     blob_encoder::{self},
     error::{Error, Result},
@@ -52,12 +56,15 @@ pub type WsProvider = alloy::providers::fillers::FillProvider<
                 >,
             >,
         >,
-        alloy::providers::fillers::WalletFiller<EthereumWallet>,
+        alloy::providers::fillers::WalletFiller<SignerWallet>,
     >,
     alloy::providers::RootProvider<alloy::pubsub::PubSubFrontend>,
     alloy::pubsub::PubSubFrontend,
     Ethereum,
 >;
+/// Blocks covered by a single `eth_getLogs` request, which providers commonly limit.
+const COMMIT_LOGS_BLOCK_RANGE: u64 = 5_000;
+
 type FuelStateContract = IFuelStateContract::IFuelStateContractInstance<PubSubFrontend, WsProvider>;
 
 sol!(
@@ -68,17 +75,18 @@ sol!(
         function finalized(bytes32 blockHash, uint256 blockHeight) external view whenNotPaused returns (bool);
         function blockHashAtCommit(uint256 commitHeight) external view returns (bytes32);
         function BLOCKS_PER_COMMIT_INTERVAL() external view returns (uint256);
+        function NUM_COMMIT_SLOTS() external view returns (uint256);
+        error UnknownBlock();
     }
 );
 
 #[derive(Clone)]
 pub struct WsConnection {
     provider: WsProvider,
-    main_address: Address,
     blob_provider: Option<WsProvider>,
-    blob_signer_address: Option<Address>,
     contract: FuelStateContract,
     commit_interval: NonZeroU32,
+    num_commit_slots: NonZeroU32,
     metrics: Metrics,
     tx_config: TxConfig,
 }
@@ -148,33 +156,65 @@ impl Default for Metrics {
 
 #[async_trait::async_trait]
 impl EthApi for WsConnection {
-    async fn submit(&self, hash: [u8; 32], height: u32) -> Result<BlockSubmissionTx> {
+    #[tracing::instrument(skip_all)]
+    async fn submit(
+        &self,
+        hash: [u8; 32],
+        height: u32,
+        previous_tx: Option<BlockSubmissionTx>,
+    ) -> Result<BlockSubmissionTx> {
         let commit_height = Self::calculate_commit_height(height, self.commit_interval);
 
         let contract_call = self.contract.commit(hash.into(), commit_height);
         let tx_request = contract_call.into_transaction_request();
 
-        let Eip1559Estimation {
-            max_fee_per_gas,
-            max_priority_fee_per_gas,
-        } = self.provider.estimate_eip1559_fees(None).await?;
+        let is_replacement = previous_tx.is_some();
+        let tx_request = if let Some(previous_tx) = previous_tx {
+            // a stuck commit is replaced with the highest acceptable priority
+            let projected_fees = self.current_fees(Priority::MAX).await?.projected();
+            let minimum_replacement_fees = MaxTxFeesPerGas::from(&previous_tx).double();
+            let fees = projected_fees
+                .retain_max(minimum_replacement_fees)
+                .normalized();
 
-        let nonce = self
-            .provider
-            .get_transaction_count(self.main_address)
-            .await?;
-        let tx_request = tx_request
-            .max_fee_per_gas(max_fee_per_gas)
-            .max_priority_fee_per_gas(max_priority_fee_per_gas)
-            .nonce(nonce);
+            tx_request
+                .max_fee_per_gas(fees.normal)
+                .max_priority_fee_per_gas(fees.priority)
+                .nonce(previous_tx.nonce as u64)
+        } else {
+            let Eip1559Estimation {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } = self.provider.estimate_eip1559_fees(None).await?;
+
+            let nonce = self
+                .provider
+                .get_transaction_count(self.provider.default_signer_address())
+                .await?;
+
+            tx_request
+                .max_fee_per_gas(max_fee_per_gas)
+                .max_priority_fee_per_gas(max_priority_fee_per_gas)
+                .nonce(nonce)
+        };
 
-        let send_fut = self.provider.send_transaction(tx_request);
-        let tx = tokio::time::timeout(self.tx_config.send_tx_request_timeout, send_fut)
-            .await
-            .map_err(|_| Error::Network("timed out trying to submit block".to_string()))??;
-        tracing::info!("tx: {} submitted", tx.tx_hash());
+        let tx = self.provider.fill(tx_request).await?;
+        let SendableTx::Envelope(tx) = tx else {
+            return Err(crate::error::Error::Other(
+                "Expected an envelope because we have a wallet filler as well, but got a builder from alloy. This is a bug.".to_string(),
+            ));
+        };
 
-        let nonce = nonce.try_into().map_err(|_| {
+        // only bumps are capped, a first submission is priced by the node's estimate
+        let max_fee = tx.max_fee_per_gas().saturating_mul(tx.gas_limit());
+        if is_replacement && max_fee > self.tx_config.commit_max_fee {
+            return Err(Error::Other(format!(
+                "max fee exceeded: tried {}, limit {}",
+                max_fee, self.tx_config.commit_max_fee
+            )));
+        }
+
+        let nonce = tx.nonce().try_into().map_err(|_| {
             Error::Other(
                 "could not convert `u64` nonce to `u32` when storing `BlockSubmissionTx`"
                     .to_string(),
@@ -184,14 +224,29 @@ impl EthApi for WsConnection {
         let submission_tx = BlockSubmissionTx {
             hash: tx.tx_hash().0,
             nonce,
-            max_fee: max_fee_per_gas,
-            priority_fee: max_priority_fee_per_gas,
+            max_fee: tx.max_fee_per_gas(),
+            priority_fee: tx
+                .max_priority_fee_per_gas()
+                .expect("eip1559 tx to have priority fee"),
             ..Default::default()
         };
 
+        let send_fut = self.provider.send_tx_envelope(tx);
+        let tx = tokio::time::timeout(self.tx_config.send_tx_request_timeout, send_fut)
+            .await
+            .map_err(|_| Error::Network("timed out trying to submit block".to_string()))??;
+        tracing::info!(
+            "tx: {} submitted with nonce: {}, max_fee_per_gas: {}, tip: {}",
+            tx.tx_hash(),
+            submission_tx.nonce,
+            submission_tx.max_fee,
+            submission_tx.priority_fee
+        );
+
         Ok(submission_tx)
     }
 
+    #[tracing::instrument(skip_all)]
     async fn fees(
         &self,
         height_range: RangeInclusive<u64>,
@@ -205,11 +260,13 @@ impl EthApi for WsConnection {
             .await?)
     }
 
+    #[tracing::instrument(skip_all)]
     async fn get_block_number(&self) -> Result<u64> {
         let response = self.provider.get_block_number().await?;
         Ok(response)
     }
 
+    #[tracing::instrument(skip_all)]
     async fn balance(&self, address: Address) -> Result<U256> {
         Ok(self.provider.get_balance(address).await?)
     }
@@ -218,6 +275,11 @@ impl EthApi for WsConnection {
         self.commit_interval
     }
 
+    fn num_commit_slots(&self) -> NonZeroU32 {
+        self.num_commit_slots
+    }
+
+    #[tracing::instrument(skip_all)]
     async fn get_transaction_response(
         &self,
         tx_hash: [u8; 32],
@@ -230,6 +292,7 @@ impl EthApi for WsConnection {
         Self::convert_to_tx_response(tx_receipt)
     }
 
+    #[tracing::instrument(skip_all)]
     async fn is_squeezed_out(&self, tx_hash: [u8; 32]) -> Result<bool> {
         Ok(self
             .provider
@@ -238,17 +301,17 @@ impl EthApi for WsConnection {
             .is_none())
     }
 
+    #[tracing::instrument(skip_all)]
     async fn submit_state_fragments(
         &self,
         fragments: NonEmpty<Fragment>,
         previous_tx: Option<L1Tx>,
         priority: Priority,
     ) -> Result<(L1Tx, services::types::FragmentsSubmitted)> {
-        let (blob_provider, blob_signer_address) =
-            match (&self.blob_provider, &self.blob_signer_address) {
-                (Some(provider), Some(address)) => (provider, address),
-                _ => return Err(Error::Other("blob pool signer not configured".to_string())),
-            };
+        let Some(blob_provider) = &self.blob_provider else {
+            return Err(Error::Other("blob pool signer not configured".to_string()));
+        };
+        let blob_signer_address = blob_provider.default_signer_address();
 
         // we only want to add it to the metrics if the submission succeeds
         let unused_bytes_per_fragment = fragments.iter().map(|f| f.unused_bytes).collect_vec();
@@ -262,7 +325,7 @@ impl EthApi for WsConnection {
 
         let blob_tx = TransactionRequest::default()
             .with_blob_sidecar(sidecar)
-            .with_to(*blob_signer_address);
+            .with_to(blob_signer_address);
 
         let blob_tx = if let Some(previous_tx) = previous_tx {
             let minimum_replacement_fees = MaxTxFeesPerGas::from(&previous_tx).double();
@@ -339,6 +402,7 @@ impl EthApi for WsConnection {
     }
 
     #[cfg(feature = "test-helpers")]
+    #[tracing::instrument(skip_all)]
     async fn finalized(&self, hash: [u8; 32], height: u32) -> Result<bool> {
         Ok(self
             .contract
@@ -348,67 +412,136 @@ impl EthApi for WsConnection {
             ._0)
     }
 
-    #[cfg(feature = "test-helpers")]
-    async fn block_hash_at_commit_height(&self, commit_height: u32) -> Result<[u8; 32]> {
-        Ok(self
+    #[tracing::instrument(skip_all)]
+    async fn block_hash_at_commit_height(&self, commit_height: u32) -> Result<Option<[u8; 32]>> {
+        match self
             .contract
             .blockHashAtCommit(U256::from(commit_height))
             .call()
-            .await?
-            ._0
-            .into())
+            .await
+        {
+            Ok(response) => Ok(Some(response._0.into())),
+            // the contract reverts instead of returning a zero hash for an empty slot
+            Err(alloy::contract::Error::TransportError(RpcError::ErrorResp(payload)))
+                if matches!(
+                    payload.as_decoded_error::<IFuelStateContractErrors>(false),
+                    Some(IFuelStateContractErrors::UnknownBlock(_))
+                ) =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn subscribe_commits(&self) -> Result<BoxStream<'static, Result<ObservedCommit>>> {
+        let filter = self.contract.CommitSubmitted_filter().filter;
+        let subscription = self.provider.subscribe_logs(&filter).await?;
+
+        let commits = subscription
+            .into_stream()
+            .filter(|log| std::future::ready(!log.removed))
+            .map(observed_commit);
+
+        Ok(commits.boxed())
+    }
+
+    async fn commits_since(&self, l1_block: u64) -> Result<Vec<ObservedCommit>> {
+        let latest = self.provider.get_block_number().await?;
+
+        let mut commits = vec![];
+        let mut from = l1_block;
+        while from <= latest {
+            let to = min(from.saturating_add(COMMIT_LOGS_BLOCK_RANGE - 1), latest);
+            let filter = self
+                .contract
+                .CommitSubmitted_filter()
+                .from_block(from)
+                .to_block(to)
+                .filter;
+
+            for log in self.provider.get_logs(&filter).await? {
+                if !log.removed {
+                    commits.push(observed_commit(log)?);
+                }
+            }
+
+            from = to + 1;
+        }
+
+        Ok(commits)
     }
 }
 
+fn observed_commit(log: alloy::rpc::types::Log) -> Result<ObservedCommit> {
+    let missing = |field| Error::Other(format!("CommitSubmitted log without {field}"));
+    let tx_hash = log.transaction_hash.ok_or_else(|| missing("tx hash"))?;
+    let log_index = log.log_index.ok_or_else(|| missing("log index"))?;
+    let l1_block = log.block_number.ok_or_else(|| missing("block number"))?;
+    let event = log.log_decode::<IFuelStateContract::CommitSubmitted>()?;
+
+    let commit_height = u32::try_from(event.inner.data.commitHeight)
+        .map_err(|e| Error::Other(format!("invalid commit height: {e}")))?;
+
+    Ok(ObservedCommit {
+        commit_height,
+        block_hash: event.inner.data.blockHash.into(),
+        tx_hash: tx_hash.into(),
+        log_index,
+        l1_block,
+    })
+}
+
 impl WsConnection {
     pub async fn connect(
         url: Url,
         contract_address: Address,
-        signers: Signers,
+        main_wallet: SignerWallet,
+        blob_wallet: Option<SignerWallet>,
         tx_config: TxConfig,
     ) -> Result<Self> {
-        let address = TxSigner::address(&signers.main);
         let ws = WsConnect::new(url);
-        let provider = Self::provider_with_signer(ws.clone(), signers.main).await?;
+        let provider = Self::provider_with_wallet(ws.clone(), main_wallet).await?;
 
-        let (blob_provider, blob_signer_address) = if let Some(signer) = signers.blob {
-            let blob_signer_address = TxSigner::address(&signer);
-            let blob_provider = Self::provider_with_signer(ws, signer).await?;
-            (Some(blob_provider), Some(blob_signer_address))
+        let blob_provider = if let Some(wallet) = blob_wallet {
+            Some(Self::provider_with_wallet(ws, wallet).await?)
         } else {
-            (None, None)
+            None
         };
 
         let contract_address = Address::from_slice(contract_address.as_ref());
         let contract = FuelStateContract::new(contract_address, provider.clone());
 
-        let interval_u256 = contract.BLOCKS_PER_COMMIT_INTERVAL().call().await?._0;
-
-        let commit_interval = u32::try_from(interval_u256)
-            .map_err(|e| Error::Other(e.to_string()))
-            .and_then(|value| {
-                NonZeroU32::new(value).ok_or_else(|| {
-                    Error::Other("l1 contract reported a commit interval of 0".to_string())
-                })
-            })?;
+        let commit_interval = Self::non_zero_u32(
+            contract.BLOCKS_PER_COMMIT_INTERVAL().call().await?._0,
+            "commit interval",
+        )?;
+        let num_commit_slots = Self::non_zero_u32(
+            contract.NUM_COMMIT_SLOTS().call().await?._0,
+            "number of commit slots",
+        )?;
 
         Ok(Self {
             provider,
-            main_address: address,
             blob_provider,
-            blob_signer_address,
             contract,
             commit_interval,
+            num_commit_slots,
             tx_config,
             metrics: Default::default(),
         })
     }
 
-    async fn provider_with_signer<S>(ws: WsConnect, signer: S) -> Result<WsProvider>
-    where
-        S: TxSigner<alloy::signers::Signature> + Send + Sync + 'static,
-    {
-        let wallet = EthereumWallet::from(signer);
+    fn non_zero_u32(value: U256, name: &str) -> Result<NonZeroU32> {
+        u32::try_from(value)
+            .map_err(|e| Error::Other(e.to_string()))
+            .and_then(|value| {
+                NonZeroU32::new(value)
+                    .ok_or_else(|| Error::Other(format!("l1 contract reported a {name} of 0")))
+            })
+    }
+
+    async fn provider_with_wallet(ws: WsConnect, wallet: SignerWallet) -> Result<WsProvider> {
         ProviderBuilder::new()
             .with_recommended_fillers()
             .wallet(wallet)
@@ -489,8 +622,8 @@ mod tests {
         let signer: PrivateKeySigner = anvil.keys()[0].clone().into();
         let blob_signer: PrivateKeySigner = anvil.keys()[1].clone().into();
 
-        let wallet = EthereumWallet::from(signer.clone());
-        let blob_wallet = EthereumWallet::from(blob_signer.clone());
+        let wallet = SignerWallet::new(signer.clone());
+        let blob_wallet = SignerWallet::new(blob_signer.clone());
 
         let ws = WsConnect::new(anvil.ws_endpoint());
         let provider = ProviderBuilder::new()
@@ -508,14 +641,13 @@ mod tests {
 
         let connection = WsConnection {
             provider: provider.clone(),
-            main_address: signer.address(),
             blob_provider: Some(blob_provider.clone()),
-            blob_signer_address: Some(blob_signer.address()),
             contract: FuelStateContract::new(
                 Address::from_slice([0u8; 20].as_ref()),
                 provider.clone(),
             ),
             commit_interval: 3.try_into().unwrap(),
+            num_commit_slots: 240.try_into().unwrap(),
             tx_config: TxConfig::default(),
             metrics: Default::default(),
         };
@@ -565,8 +697,8 @@ mod tests {
         let signer: PrivateKeySigner = anvil.keys()[0].clone().into();
         let blob_signer: PrivateKeySigner = anvil.keys()[1].clone().into();
 
-        let wallet = EthereumWallet::from(signer.clone());
-        let blob_wallet = EthereumWallet::from(blob_signer.clone());
+        let wallet = SignerWallet::new(signer.clone());
+        let blob_wallet = SignerWallet::new(blob_signer.clone());
 
         let ws = WsConnect::new(anvil.ws_endpoint());
         let provider = ProviderBuilder::new()
@@ -585,14 +717,13 @@ mod tests {
         let tx_max_fee = 1;
         let connection = WsConnection {
             provider: provider.clone(),
-            main_address: signer.address(),
             blob_provider: Some(blob_provider.clone()),
-            blob_signer_address: Some(blob_signer.address()),
             contract: FuelStateContract::new(
                 Address::from_slice([0u8; 20].as_ref()),
                 provider.clone(),
             ),
             commit_interval: 3.try_into().unwrap(),
+            num_commit_slots: 240.try_into().unwrap(),
             tx_config: TxConfig {
                 tx_max_fee,
                 ..Default::default()
@@ -618,4 +749,43 @@ mod tests {
                 .contains(&format!("limit {}", tx_max_fee))
         );
     }
+
+    #[tokio::test]
+    async fn first_commit_is_sent_regardless_of_fee_limits() {
+        // given
+        let anvil = Anvil::new().try_spawn().unwrap();
+
+        let signer: PrivateKeySigner = anvil.keys()[0].clone().into();
+        let wallet = SignerWallet::new(signer.clone());
+
+        let provider = ProviderBuilder::new()
+            .with_recommended_fillers()
+            .wallet(wallet)
+            .on_ws(WsConnect::new(anvil.ws_endpoint()))
+            .await
+            .unwrap();
+
+        let connection = WsConnection {
+            provider: provider.clone(),
+            blob_provider: None,
+            contract: FuelStateContract::new(
+                Address::from_slice([0u8; 20].as_ref()),
+                provider.clone(),
+            ),
+            commit_interval: 3.try_into().unwrap(),
+            num_commit_slots: 240.try_into().unwrap(),
+            tx_config: TxConfig {
+                tx_max_fee: 1,
+                commit_max_fee: 1,
+                ..Default::default()
+            },
+            metrics: Default::default(),
+        };
+
+        // when
+        let submitted_tx = connection.submit([1; 32], 3, None).await.unwrap();
+
+        // then
+        assert_eq!(submitted_tx.nonce, 0);
+    }
 }
@@ -6,7 +6,7 @@ use alloy::{
     rpc::types::{FeeHistory, TransactionRequest},
 };
 use itertools::Itertools;
-use services::types::L1Tx;
+use services::types::{BlockSubmissionTx, L1Tx};
 
 use crate::error::{Error, Result};
 
@@ -27,6 +27,16 @@ impl<'a> From<&'a L1Tx> for MaxTxFeesPerGas {
     }
 }
 
+impl<'a> From<&'a BlockSubmissionTx> for MaxTxFeesPerGas {
+    fn from(value: &'a BlockSubmissionTx) -> Self {
+        Self {
+            normal: value.max_fee,
+            priority: value.priority_fee,
+            blob: 0,
+        }
+    }
+}
+
 impl MaxTxFeesPerGas {
     /// Returns a new [`MaxTxFeesPerGas`] instance with its `normal` fee adjusted to ensure
     /// that it is at least as high as the transaction's `priority` fee.
@@ -5,7 +5,9 @@ use ::metrics::{
 };
 use alloy::rpc::types::FeeHistory;
 use delegate::delegate;
+use futures::stream::BoxStream;
 use services::{
+    commit_watcher::port::ObservedCommit,
     state_committer::port::l1::Priority,
     types::{Address, BlockSubmissionTx, Fragment, NonEmpty, TransactionResponse, U256},
 };
@@ -18,7 +20,12 @@ use crate::{
 #[cfg_attr(test, mockall::automock)]
 #[async_trait::async_trait]
 pub trait EthApi {
-    async fn submit(&self, hash: [u8; 32], height: u32) -> Result<BlockSubmissionTx>;
+    async fn submit(
+        &self,
+        hash: [u8; 32],
+        height: u32,
+        previous_tx: Option<BlockSubmissionTx>,
+    ) -> Result<BlockSubmissionTx>;
     async fn fees(
         &self,
         height_range: RangeInclusive<u64>,
@@ -27,6 +34,7 @@ pub trait EthApi {
     async fn get_block_number(&self) -> Result<u64>;
     async fn balance(&self, address: Address) -> Result<U256>;
     fn commit_interval(&self) -> NonZeroU32;
+    fn num_commit_slots(&self) -> NonZeroU32;
     async fn get_transaction_response(
         &self,
         tx_hash: [u8; 32],
@@ -40,8 +48,9 @@ pub trait EthApi {
     ) -> Result<(services::types::L1Tx, services::types::FragmentsSubmitted)>;
     #[cfg(feature = "test-helpers")]
     async fn finalized(&self, hash: [u8; 32], height: u32) -> Result<bool>;
-    #[cfg(feature = "test-helpers")]
-    async fn block_hash_at_commit_height(&self, commit_height: u32) -> Result<[u8; 32]>;
+    async fn block_hash_at_commit_height(&self, commit_height: u32) -> Result<Option<[u8; 32]>>;
+    async fn subscribe_commits(&self) -> Result<BoxStream<'static, Result<ObservedCommit>>>;
+    async fn commits_since(&self, l1_block: u64) -> Result<Vec<ObservedCommit>>;
 }
 
 #[cfg(test)]
@@ -103,11 +112,17 @@ where
     delegate! {
         to self.adapter {
             fn commit_interval(&self) -> NonZeroU32;
+            fn num_commit_slots(&self) -> NonZeroU32;
         }
     }
 
-    async fn submit(&self, hash: [u8; 32], height: u32) -> Result<BlockSubmissionTx> {
-        let response = self.adapter.submit(hash, height).await;
+    async fn submit(
+        &self,
+        hash: [u8; 32],
+        height: u32,
+        previous_tx: Option<BlockSubmissionTx>,
+    ) -> Result<BlockSubmissionTx> {
+        let response = self.adapter.submit(hash, height, previous_tx).await;
         self.note_network_status(&response);
         response
     }
@@ -170,8 +185,7 @@ where
         response
     }
 
-    #[cfg(feature = "test-helpers")]
-    async fn block_hash_at_commit_height(&self, commit_height: u32) -> Result<[u8; 32]> {
+    async fn block_hash_at_commit_height(&self, commit_height: u32) -> Result<Option<[u8; 32]>> {
         let response = self
             .adapter
             .block_hash_at_commit_height(commit_height)
@@ -179,6 +193,18 @@ where
         self.note_network_status(&response);
         response
     }
+
+    async fn subscribe_commits(&self) -> Result<BoxStream<'static, Result<ObservedCommit>>> {
+        let response = self.adapter.subscribe_commits().await;
+        self.note_network_status(&response);
+        response
+    }
+
+    async fn commits_since(&self, l1_block: u64) -> Result<Vec<ObservedCommit>> {
+        let response = self.adapter.commits_since(l1_block).await;
+        self.note_network_status(&response);
+        response
+    }
 }
 
 #[cfg(test)]
@@ -193,7 +219,7 @@ mod tests {
         let mut eth_adapter = MockEthApi::new();
         eth_adapter
             .expect_submit()
-            .returning(|_, _| Err(Error::Network("An error".into())));
+            .returning(|_, _, _| Err(Error::Network("An error".into())));
 
         eth_adapter
             .expect_get_block_number()
@@ -202,7 +228,7 @@ mod tests {
         let adapter = HealthTrackingMiddleware::new(eth_adapter, 1);
         let health_check = adapter.connection_health_checker();
 
-        let _ = adapter.submit([0; 32], 0).await;
+        let _ = adapter.submit([0; 32], 0, None).await;
 
         // when
         let _ = adapter.get_block_number().await;
@@ -217,7 +243,7 @@ mod tests {
         let mut eth_adapter = MockEthApi::new();
         eth_adapter
             .expect_submit()
-            .returning(|_, _| Err(Error::Other("An error".into())));
+            .returning(|_, _, _| Err(Error::Other("An error".into())));
 
         eth_adapter
             .expect_get_block_number()
@@ -226,7 +252,7 @@ mod tests {
         let adapter = HealthTrackingMiddleware::new(eth_adapter, 2);
         let health_check = adapter.connection_health_checker();
 
-        let _ = adapter.submit([0; 32], 0).await;
+        let _ = adapter.submit([0; 32], 0, None).await;
 
         // when
         let _ = adapter.get_block_number().await;
@@ -240,7 +266,7 @@ mod tests {
         let mut eth_adapter = MockEthApi::new();
         eth_adapter
             .expect_submit()
-            .returning(|_, _| Err(Error::Network("An error".into())));
+            .returning(|_, _, _| Err(Error::Network("An error".into())));
 
         eth_adapter
             .expect_get_block_number()
@@ -250,7 +276,7 @@ mod tests {
         let health_check = adapter.connection_health_checker();
         assert!(health_check.healthy());
 
-        let _ = adapter.submit([0; 32], 0).await;
+        let _ = adapter.submit([0; 32], 0, None).await;
         assert!(health_check.healthy());
 
         let _ = adapter.get_block_number().await;
@@ -265,7 +291,7 @@ mod tests {
         let mut eth_adapter = MockEthApi::new();
         eth_adapter
             .expect_submit()
-            .returning(|_, _| Err(Error::Network("An error".into())));
+            .returning(|_, _, _| Err(Error::Network("An error".into())));
 
         eth_adapter
             .expect_get_block_number()
@@ -275,7 +301,7 @@ mod tests {
         let adapter = HealthTrackingMiddleware::new(eth_adapter, 3);
         adapter.register_metrics(&registry);
 
-        let _ = adapter.submit([0; 32], 0).await;
+        let _ = adapter.submit([0; 32], 0, None).await;
         let _ = adapter.get_block_number().await;
 
         let metrics = registry.gather();
@@ -13,7 +13,8 @@ use fuel_core_types::fuel_tx::Bytes32;
 use fuel_core_types::fuel_tx::Transaction;
 use futures::{Stream, StreamExt, stream};
 use metrics::{
-    ConnectionHealthTracker, HealthChecker, RegistersMetrics, prometheus::core::Collector,
+    ConnectionHealthTracker, HealthCheck, HealthChecker, RegistersMetrics,
+    prometheus::core::Collector,
 };
 use services::{
     Error, Result,
@@ -85,11 +86,26 @@ impl HttpClient {
         url: &Url,
         unhealthy_after_n_errors: usize,
         num_buffered_requests: NonZeroU32,
+    ) -> Self {
+        Self::with_metrics(
+            url,
+            unhealthy_after_n_errors,
+            num_buffered_requests,
+            Metrics::default(),
+        )
+    }
+
+    /// Clients sharing `metrics` report their errors and heights together.
+    pub(crate) fn with_metrics(
+        url: &Url,
+        unhealthy_after_n_errors: usize,
+        num_buffered_requests: NonZeroU32,
+        metrics: Metrics,
     ) -> Self {
         let client = GqlClient::new(url).expect("Url to be well formed");
         Self {
             client,
-            metrics: Metrics::default(),
+            metrics,
             health_tracker: ConnectionHealthTracker::new(unhealthy_after_n_errors),
             num_buffered_requests,
         }
@@ -145,6 +161,7 @@ impl HttpClient {
         }
     }
 
+    #[tracing::instrument(skip(self))]
     pub async fn block_at_height(&self, height: u32) -> Result<Option<FuelBlock>> {
         let query = BlockAtHeightQuery::build(BlockAtHeightVariables {
             height: custom_queries::U32(height.to_string()),
@@ -178,6 +195,7 @@ impl HttpClient {
         }
     }
 
+    #[tracing::instrument(skip(self))]
     pub async fn compressed_block_at_height(
         &self,
         height: u32,
@@ -220,6 +238,7 @@ impl HttpClient {
             .filter_map(|result| async move { result.transpose() })
     }
 
+    #[tracing::instrument(skip(self))]
     pub async fn latest_block(&self) -> Result<FuelBlock> {
         let query = custom_queries::latest_block::LatestBlockQuery::build(());
 
@@ -254,6 +273,15 @@ impl HttpClient {
         self.health_tracker.tracker()
     }
 
+    pub(crate) fn healthy(&self) -> bool {
+        self.health_tracker.healthy()
+    }
+
+    /// For requests abandoned by the caller, counted like a network error.
+    pub(crate) fn note_timeout(&self) {
+        self.handle_network_error();
+    }
+
     fn handle_network_error(&self) {
         self.health_tracker.note_failure();
         self.metrics.fuel_network_errors.inc();
@@ -5,9 +5,11 @@ use std::ops::RangeInclusive;
 use futures::{StreamExt, stream::BoxStream};
 mod client;
 mod metrics;
+mod multi_endpoint;
 
 pub use client::*;
 use delegate::delegate;
+pub use multi_endpoint::MultiEndpointClient;
 use services::{Result, block_committer::port::fuel::FuelBlock};
 pub use url::Url;
 
@@ -30,6 +32,18 @@ impl services::block_bundler::port::fuel::Api for client::HttpClient {
     }
 }
 
+impl services::block_reporter::port::fuel::Api for client::HttpClient {
+    async fn latest_height(&self) -> Result<u32> {
+        self.latest_block().await.map(|b| b.height)
+    }
+}
+
+impl services::status_reporter::port::fuel::Api for client::HttpClient {
+    async fn latest_height(&self) -> Result<u32> {
+        self.latest_block().await.map(|b| b.height)
+    }
+}
+
 impl services::block_committer::port::fuel::Api for client::HttpClient {
     delegate! {
         to self {
@@ -39,12 +53,74 @@ impl services::block_committer::port::fuel::Api for client::HttpClient {
     }
 }
 
+impl services::commit_watcher::port::fuel::Api for client::HttpClient {
+    delegate! {
+        to self {
+            async fn block_at_height(&self, height: u32) -> Result<Option<FuelBlock>>;
+        }
+    }
+}
+
 impl services::state_committer::port::fuel::Api for client::HttpClient {
     async fn latest_height(&self) -> Result<u32> {
         self.latest_block().await.map(|b| b.height)
     }
 }
 
+impl services::block_importer::port::fuel::Api for MultiEndpointClient {
+    async fn latest_height(&self) -> Result<u32> {
+        self.latest_block().await.map(|b| b.height)
+    }
+
+    fn compressed_blocks_in_height_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> BoxStream<'_, Result<services::types::CompressedFuelBlock>> {
+        self._compressed_blocks_in_height_range(range).boxed()
+    }
+}
+
+impl services::block_bundler::port::fuel::Api for MultiEndpointClient {
+    async fn latest_height(&self) -> Result<u32> {
+        self.latest_block().await.map(|b| b.height)
+    }
+}
+
+impl services::block_reporter::port::fuel::Api for MultiEndpointClient {
+    async fn latest_height(&self) -> Result<u32> {
+        self.latest_block().await.map(|b| b.height)
+    }
+}
+
+impl services::status_reporter::port::fuel::Api for MultiEndpointClient {
+    async fn latest_height(&self) -> Result<u32> {
+        self.latest_block().await.map(|b| b.height)
+    }
+}
+
+impl services::block_committer::port::fuel::Api for MultiEndpointClient {
+    delegate! {
+        to self {
+            async fn block_at_height(&self, height: u32) -> Result<Option<FuelBlock>>;
+            async fn latest_block(&self) -> Result<FuelBlock>;
+        }
+    }
+}
+
+impl services::commit_watcher::port::fuel::Api for MultiEndpointClient {
+    delegate! {
+        to self {
+            async fn block_at_height(&self, height: u32) -> Result<Option<FuelBlock>>;
+        }
+    }
+}
+
+impl services::state_committer::port::fuel::Api for MultiEndpointClient {
+    async fn latest_height(&self) -> Result<u32> {
+        self.latest_block().await.map(|b| b.height)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ::metrics::{
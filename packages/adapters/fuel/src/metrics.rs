@@ -1,6 +1,6 @@
 use metrics::{
     RegistersMetrics,
-    prometheus::{IntCounter, IntGauge, Opts, core::Collector},
+    prometheus::{GaugeVec, IntCounter, IntGauge, IntGaugeVec, Opts, core::Collector},
 };
 
 #[derive(Clone)]
@@ -35,3 +35,63 @@ impl Default for Metrics {
         }
     }
 }
+
+#[derive(Clone)]
+pub struct EndpointMetrics {
+    pub up: IntGaugeVec,
+    pub latency: GaugeVec,
+    pub height: IntGaugeVec,
+    pub failovers: IntCounter,
+}
+
+impl RegistersMetrics for EndpointMetrics {
+    fn metrics(&self) -> Vec<Box<dyn Collector>> {
+        vec![
+            Box::new(self.up.clone()),
+            Box::new(self.latency.clone()),
+            Box::new(self.height.clone()),
+            Box::new(self.failovers.clone()),
+        ]
+    }
+}
+
+impl Default for EndpointMetrics {
+    fn default() -> Self {
+        let up = IntGaugeVec::new(
+            Opts::new(
+                "fuel_endpoint_up",
+                "Whether a Fuel endpoint is considered healthy (1) or not (0).",
+            ),
+            &["endpoint"],
+        )
+        .expect("fuel_endpoint_up metric to be correctly configured");
+        let latency = GaugeVec::new(
+            Opts::new(
+                "fuel_endpoint_latency_seconds",
+                "Moving average of the latency of successful requests to a Fuel endpoint.",
+            ),
+            &["endpoint"],
+        )
+        .expect("fuel_endpoint_latency_seconds metric to be correctly configured");
+        let height = IntGaugeVec::new(
+            Opts::new(
+                "fuel_endpoint_height",
+                "Latest block height a Fuel endpoint is known to have.",
+            ),
+            &["endpoint"],
+        )
+        .expect("fuel_endpoint_height metric to be correctly configured");
+        let failovers = IntCounter::with_opts(Opts::new(
+            "fuel_endpoint_failovers",
+            "Number of requests retried on another Fuel endpoint after one failed, timed out or lagged behind.",
+        ))
+        .expect("fuel_endpoint_failovers metric to be correctly configured");
+
+        Self {
+            up,
+            latency,
+            height,
+            failovers,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+BEGIN;
+
+-- Single row holding the lease of the committer instance currently allowed to run the mutating
+-- services. Expiry is evaluated using the database clock so that candidates don't need synchronized
+-- clocks.
+CREATE TABLE IF NOT EXISTS leader_lease (
+    id          SMALLINT PRIMARY KEY DEFAULT 1,
+    holder      TEXT NOT NULL,
+    expires_at  TIMESTAMPTZ NOT NULL
+);
+
+ALTER TABLE leader_lease
+  ADD CONSTRAINT leader_lease_single_row_check
+  CHECK (
+    id = 1
+);
+
+COMMIT;
@@ -0,0 +1,12 @@
+BEGIN;
+
+-- Every intervention made through the admin API, recorded before it is applied.
+CREATE TABLE IF NOT EXISTS admin_audit_log (
+    id            SERIAL PRIMARY KEY,
+    action        TEXT NOT NULL,
+    runner        TEXT NOT NULL,
+    requested_by  TEXT,
+    performed_at  TIMESTAMPTZ NOT NULL
+);
+
+COMMIT;
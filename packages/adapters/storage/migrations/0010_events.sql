@@ -0,0 +1,14 @@
+BEGIN;
+
+-- Lifecycle events published by the services, in the order they were published. The id is what
+-- SSE consumers send back as `Last-Event-ID` to resume the stream.
+CREATE TABLE IF NOT EXISTS events (
+    id           BIGSERIAL PRIMARY KEY,
+    kind         TEXT NOT NULL,
+    payload      JSONB NOT NULL,
+    recorded_at  TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_events_recorded_at ON events (recorded_at);
+
+COMMIT;
@@ -0,0 +1,20 @@
+BEGIN;
+
+-- When the last transaction carrying the bundle was finalized, so that costs can be aggregated over
+-- wall-clock windows.
+ALTER TABLE bundle_cost
+  ADD COLUMN IF NOT EXISTS finalized_at TIMESTAMPTZ;
+
+UPDATE bundle_cost bc
+SET finalized_at = (
+    SELECT MAX(t.finalized_at)
+    FROM l1_fragments f
+    JOIN l1_transaction_fragments tf ON tf.fragment_id = f.id
+    JOIN l1_blob_transaction t ON t.id = tf.transaction_id
+    WHERE f.bundle_id = bc.bundle_id AND t.state = 1
+)
+WHERE bc.is_finalized;
+
+CREATE INDEX IF NOT EXISTS idx_bundle_cost_finalized_at ON bundle_cost (finalized_at);
+
+COMMIT;
@@ -0,0 +1,14 @@
+BEGIN;
+
+-- Costs of finalized bundles waiting to be pushed to the cost feed. Rows are added in the same
+-- transaction that finalizes the bundle cost and removed once the cost was delivered.
+CREATE TABLE IF NOT EXISTS cost_feed_outbox (
+    bundle_id     INTEGER PRIMARY KEY REFERENCES bundles(id),
+    finalized_at  TIMESTAMPTZ NOT NULL,
+    attempts      INTEGER NOT NULL DEFAULT 0,
+    last_error    TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_cost_feed_outbox_finalized_at ON cost_feed_outbox (finalized_at);
+
+COMMIT;
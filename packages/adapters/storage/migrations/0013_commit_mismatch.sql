@@ -0,0 +1,14 @@
+BEGIN;
+
+-- Block submissions whose commit on L1 disagrees with what we submitted, as found by the block
+-- committer when reconciling with the state contract. Rows are removed once the two agree again.
+CREATE TABLE IF NOT EXISTS commit_mismatch (
+    submission_id       INTEGER PRIMARY KEY REFERENCES l1_fuel_block_submission(id) ON DELETE CASCADE,
+    kind                SMALLINT NOT NULL,
+    onchain_block_hash  BYTEA,
+    detected_at         TIMESTAMPTZ NOT NULL,
+    CHECK (kind IN (0, 1) AND (kind = 0) = (onchain_block_hash IS NULL)),
+    CHECK (onchain_block_hash IS NULL OR octet_length(onchain_block_hash) = 32)
+);
+
+COMMIT;
@@ -0,0 +1,21 @@
+BEGIN;
+
+-- `CommitSubmitted` events of the state contract as seen by the commit watcher, including commits
+-- made by anyone else. `foreign` marks commits whose hash differs from the Fuel block at that height.
+CREATE TABLE IF NOT EXISTS observed_commits (
+    tx_hash         BYTEA NOT NULL,
+    log_index       BIGINT NOT NULL,
+    commit_height   BIGINT NOT NULL,
+    block_hash      BYTEA NOT NULL,
+    l1_block        BIGINT NOT NULL,
+    foreign_commit  BOOLEAN NOT NULL,
+    observed_at     TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (tx_hash, log_index),
+    CHECK (octet_length(tx_hash) = 32),
+    CHECK (octet_length(block_hash) = 32),
+    CHECK (log_index >= 0 AND commit_height >= 0 AND l1_block >= 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_observed_commits_commit_height ON observed_commits(commit_height);
+
+COMMIT;
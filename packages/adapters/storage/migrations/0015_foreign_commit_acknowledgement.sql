@@ -0,0 +1,10 @@
+BEGIN;
+
+-- Set once an operator acknowledged a foreign commit, after which it no longer makes the
+-- instances unhealthy. Foreign commits are kept regardless of their age.
+ALTER TABLE observed_commits ADD COLUMN IF NOT EXISTS acknowledged_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_observed_commits_unacknowledged_foreign
+    ON observed_commits(l1_block) WHERE foreign_commit AND acknowledged_at IS NULL;
+
+COMMIT;
@@ -0,0 +1,10 @@
+BEGIN;
+
+-- Commits at a height the Fuel node had no block for yet are recorded without `foreign_commit`
+-- and checked again by the commit watcher until the block is known.
+ALTER TABLE observed_commits ALTER COLUMN foreign_commit DROP NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_observed_commits_unverified
+    ON observed_commits(l1_block, log_index) WHERE foreign_commit IS NULL;
+
+COMMIT;
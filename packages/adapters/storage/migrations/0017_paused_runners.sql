@@ -0,0 +1,10 @@
+BEGIN;
+
+-- Runners paused through the admin API, by slug. Applied by every instance so that a pause
+-- outlasts restarts and failovers.
+CREATE TABLE IF NOT EXISTS paused_runners (
+    runner     TEXT PRIMARY KEY,
+    paused_at  TIMESTAMPTZ NOT NULL
+);
+
+COMMIT;
@@ -0,0 +1,17 @@
+BEGIN;
+
+-- One lease per group of services gated by leader election instead of a single one, so that the
+-- roles can be spread over several instances. The single lease is dropped, its holder steps down
+-- once it fails to renew it.
+DROP TABLE IF EXISTS leader_lease;
+
+-- Expiry is evaluated using the database clock so that candidates don't need synchronized clocks.
+CREATE TABLE IF NOT EXISTS leader_leases (
+    name        TEXT PRIMARY KEY,
+    holder      TEXT NOT NULL,
+    expires_at  TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_leader_leases_holder ON leader_leases(holder);
+
+COMMIT;
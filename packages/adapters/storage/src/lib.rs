@@ -2,29 +2,32 @@ mod mappings;
 #[cfg(feature = "test-helpers")]
 mod test_instance;
 
-use std::ops::RangeInclusive;
+use std::{collections::HashMap, ops::RangeInclusive};
 
 #[cfg(feature = "test-helpers")]
 pub use test_instance::*;
 
 pub(crate) mod error;
 mod postgres;
+use postgres::BundleFilter;
 pub use postgres::{DbConfig, Postgres};
 use services::{
     Result,
     block_bundler::port::UnbundledBlocks,
     types::{
-        BlockSubmission, BlockSubmissionTx, BundleCost, CompressedFuelBlock, DateTime, Fragment,
-        L1Tx, NonEmpty, NonNegative, TransactionCostUpdate, TransactionState, Utc,
-        storage::BundleFragment,
+        BlockSubmission, BlockSubmissionTx, BundleCost, CommitMismatch, CompressedFuelBlock,
+        DateTime, Fragment, HeightRange, L1Tx, NonEmpty, NonNegative, TransactionCostUpdate,
+        TransactionState, Utc, storage::BundleFragment,
     },
 };
 
 impl services::state_listener::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
     async fn get_non_finalized_txs(&self) -> Result<Vec<L1Tx>> {
         self._get_non_finalized_txs().await.map_err(Into::into)
     }
 
+    #[tracing::instrument(skip_all)]
     async fn update_tx_states_and_costs(
         &self,
         selective_changes: Vec<([u8; 32], TransactionState)>,
@@ -36,10 +39,12 @@ impl services::state_listener::port::Storage for Postgres {
             .map_err(Into::into)
     }
 
+    #[tracing::instrument(skip_all)]
     async fn has_pending_txs(&self) -> Result<bool> {
         self._has_pending_txs().await.map_err(Into::into)
     }
 
+    #[tracing::instrument(skip_all)]
     async fn earliest_submission_attempt(&self, nonce: u32) -> Result<Option<DateTime<Utc>>> {
         self._earliest_submission_attempt(nonce)
             .await
@@ -48,6 +53,7 @@ impl services::state_listener::port::Storage for Postgres {
 }
 
 impl services::cost_reporter::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
     async fn get_finalized_costs(
         &self,
         from_block_height: u32,
@@ -58,18 +64,104 @@ impl services::cost_reporter::port::Storage for Postgres {
             .map_err(Into::into)
     }
 
+    #[tracing::instrument(skip_all)]
     async fn get_latest_costs(&self, limit: usize) -> Result<Vec<BundleCost>> {
         self._get_latest_costs(limit).await.map_err(Into::into)
     }
+
+    #[tracing::instrument(skip_all)]
+    async fn finalized_bundle_cost(&self, bundle_id: u32) -> Result<Option<BundleCost>> {
+        self._finalized_bundle_cost(bundle_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn cost_aggregates(
+        &self,
+        filter: services::cost_reporter::port::CostFilter,
+        bucket: Option<services::cost_reporter::port::CostBucket>,
+    ) -> Result<Vec<services::cost_reporter::port::CostAggregate>> {
+        self._cost_aggregates(filter, bucket)
+            .await
+            .map_err(Into::into)
+    }
+}
+
+impl services::cost_feed::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
+    async fn undelivered_costs(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<services::cost_feed::port::UndeliveredCost>> {
+        self._undelivered_costs(limit).await.map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn undelivered_cost_backlog(&self) -> Result<services::cost_feed::port::CostBacklog> {
+        self._undelivered_cost_backlog().await.map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn mark_cost_delivered(&self, bundle_id: u64) -> Result<()> {
+        self._mark_cost_delivered(bundle_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn record_cost_delivery_failure(&self, bundle_id: u64, error: String) -> Result<()> {
+        self._record_cost_delivery_failure(bundle_id, error)
+            .await
+            .map_err(Into::into)
+    }
 }
 
 impl services::status_reporter::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
     async fn submission_w_latest_block(&self) -> Result<Option<BlockSubmission>> {
         self._submission_w_latest_block().await.map_err(Into::into)
     }
+
+    #[tracing::instrument(skip_all)]
+    async fn latest_imported_height(&self) -> Result<Option<u32>> {
+        self._latest_imported_height().await.map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn latest_bundled_height(&self) -> Result<Option<u32>> {
+        self._latest_bundled_height().await.map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn latest_finalized_fragment_height(&self) -> Result<Option<u32>> {
+        self._latest_finalized_fragment_height()
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn unbundled_blocks(&self, starting_height: u32) -> Result<u64> {
+        self.total_unbundled_blocks(starting_height)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn unsubmitted_fragments(&self, starting_height: u32) -> Result<u64> {
+        self._unsubmitted_fragments(starting_height)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn latest_pending_blob_tx(&self) -> Result<Option<L1Tx>> {
+        self._get_latest_pending_txs().await.map_err(Into::into)
+    }
 }
 
 impl services::block_importer::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
     async fn missing_blocks(
         &self,
         starting_height: u32,
@@ -80,12 +172,14 @@ impl services::block_importer::port::Storage for Postgres {
             .map_err(Into::into)
     }
 
+    #[tracing::instrument(skip_all)]
     async fn insert_blocks(&self, blocks: NonEmpty<CompressedFuelBlock>) -> Result<()> {
         Ok(self._insert_blocks(blocks).await?)
     }
 }
 
 impl services::block_bundler::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
     async fn lowest_sequence_of_unbundled_blocks(
         &self,
         starting_height: u32,
@@ -95,6 +189,7 @@ impl services::block_bundler::port::Storage for Postgres {
             .await
             .map_err(Into::into)
     }
+    #[tracing::instrument(skip_all)]
     async fn insert_bundle_and_fragments(
         &self,
         bundle_id: NonNegative<i32>,
@@ -105,12 +200,14 @@ impl services::block_bundler::port::Storage for Postgres {
             .await
             .map_err(Into::into)
     }
+    #[tracing::instrument(skip_all)]
     async fn next_bundle_id(&self) -> Result<NonNegative<i32>> {
         self._next_bundle_id().await.map_err(Into::into)
     }
 }
 
 impl services::block_committer::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
     async fn record_block_submission(
         &self,
         submission_tx: BlockSubmissionTx,
@@ -121,6 +218,17 @@ impl services::block_committer::port::Storage for Postgres {
             .await
             .map_err(Into::into)
     }
+    #[tracing::instrument(skip_all)]
+    async fn record_block_submission_tx(
+        &self,
+        submission_tx: BlockSubmissionTx,
+        created_at: DateTime<Utc>,
+    ) -> Result<()> {
+        self._record_block_submission_tx(submission_tx, created_at)
+            .await
+            .map_err(Into::into)
+    }
+    #[tracing::instrument(skip_all)]
     async fn get_pending_block_submission_txs(
         &self,
         submission_id: NonNegative<i32>,
@@ -129,6 +237,7 @@ impl services::block_committer::port::Storage for Postgres {
             .await
             .map_err(Into::into)
     }
+    #[tracing::instrument(skip_all)]
     async fn update_block_submission_tx(
         &self,
         hash: [u8; 32],
@@ -138,20 +247,41 @@ impl services::block_committer::port::Storage for Postgres {
             .await
             .map_err(Into::into)
     }
+    #[tracing::instrument(skip_all)]
     async fn submission_w_latest_block(&self) -> Result<Option<BlockSubmission>> {
         self._submission_w_latest_block().await.map_err(Into::into)
     }
+    #[tracing::instrument(skip_all)]
+    async fn recent_block_submissions(&self, limit: usize) -> Result<Vec<BlockSubmission>> {
+        self._block_submissions(None, HeightRange::default(), limit)
+            .await
+            .map_err(Into::into)
+    }
+    #[tracing::instrument(skip_all)]
+    async fn set_commit_mismatch(
+        &self,
+        submission_id: NonNegative<i32>,
+        mismatch: Option<CommitMismatch>,
+        detected_at: DateTime<Utc>,
+    ) -> Result<()> {
+        self._set_commit_mismatch(submission_id, mismatch, detected_at)
+            .await
+            .map_err(Into::into)
+    }
 }
 
 impl services::state_committer::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
     async fn has_nonfinalized_txs(&self) -> Result<bool> {
         self._has_nonfinalized_txs().await.map_err(Into::into)
     }
+    #[tracing::instrument(skip_all)]
     async fn last_time_a_fragment_was_finalized(&self) -> Result<Option<DateTime<Utc>>> {
         self._last_time_a_fragment_was_finalized()
             .await
             .map_err(Into::into)
     }
+    #[tracing::instrument(skip_all)]
     async fn record_pending_tx(
         &self,
         tx: L1Tx,
@@ -162,6 +292,7 @@ impl services::state_committer::port::Storage for Postgres {
             .await
             .map_err(Into::into)
     }
+    #[tracing::instrument(skip_all)]
     async fn oldest_nonfinalized_fragments(
         &self,
         starting_height: u32,
@@ -171,21 +302,25 @@ impl services::state_committer::port::Storage for Postgres {
             .await
             .map_err(Into::into)
     }
+    #[tracing::instrument(skip_all)]
     async fn fragments_submitted_by_tx(&self, tx_hash: [u8; 32]) -> Result<Vec<BundleFragment>> {
         self._fragments_submitted_by_tx(tx_hash)
             .await
             .map_err(Into::into)
     }
+    #[tracing::instrument(skip_all)]
     async fn get_latest_pending_txs(&self) -> Result<Option<services::types::L1Tx>> {
         self._get_latest_pending_txs().await.map_err(Into::into)
     }
 
+    #[tracing::instrument(skip_all)]
     async fn latest_bundled_height(&self) -> Result<Option<u32>> {
         self._latest_bundled_height().await.map_err(Into::into)
     }
 }
 
 impl services::state_pruner::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
     async fn prune_entries_older_than(
         &self,
         date: DateTime<Utc>,
@@ -195,11 +330,254 @@ impl services::state_pruner::port::Storage for Postgres {
             .map_err(Into::into)
     }
 
+    #[tracing::instrument(skip_all)]
     async fn table_sizes(&self) -> Result<services::state_pruner::port::TableSizes> {
         self._table_sizes().await.map_err(Into::into)
     }
 }
 
+impl services::leader_election::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
+    async fn acquire_or_renew_lease(
+        &self,
+        lease: &str,
+        candidate_id: &str,
+        lease_duration: std::time::Duration,
+    ) -> Result<bool> {
+        self._acquire_or_renew_lease(lease, candidate_id, lease_duration)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn release_leases(&self, candidate_id: &str) -> Result<()> {
+        self._release_leases(candidate_id).await.map_err(Into::into)
+    }
+}
+
+impl services::bundle_reporter::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
+    async fn bundles(
+        &self,
+        before_id: Option<u32>,
+        heights: HeightRange,
+        limit: usize,
+    ) -> Result<Vec<services::types::BundleOverview>> {
+        self._bundles(BundleFilter::Page { before_id, heights }, limit)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn bundles_by_id(&self, ids: Vec<u32>) -> Result<Vec<services::types::BundleOverview>> {
+        let limit = ids.len();
+        self._bundles(BundleFilter::Ids(ids), limit)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn fragments_of_bundles(
+        &self,
+        ids: Vec<u32>,
+    ) -> Result<HashMap<u32, Vec<services::types::FragmentSubmissions>>> {
+        self._bundle_fragments(&ids).await.map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn bundle_ids_carried_by(
+        &self,
+        tx_hashes: Vec<[u8; 32]>,
+    ) -> Result<HashMap<[u8; 32], Vec<u32>>> {
+        self._bundle_ids_carried_by(&tx_hashes)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn blob_transactions(
+        &self,
+        before_id: Option<u64>,
+        heights: HeightRange,
+        limit: usize,
+    ) -> Result<Vec<L1Tx>> {
+        self._blob_transactions(before_id, heights, limit)
+            .await
+            .map_err(Into::into)
+    }
+}
+
+impl services::block_reporter::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
+    async fn is_block_imported(&self, height: u32) -> Result<bool> {
+        self._is_block_imported(height).await.map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn bundle_containing(
+        &self,
+        height: u32,
+    ) -> Result<Option<services::types::BundleOverview>> {
+        let bundles = self._bundles(BundleFilter::Containing(height), 1).await?;
+
+        Ok(bundles.into_iter().next())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn bundle_fragments(
+        &self,
+        bundle_id: u32,
+    ) -> Result<Vec<services::types::FragmentSubmissions>> {
+        let mut fragments = self._bundle_fragments(&[bundle_id]).await?;
+
+        Ok(fragments.remove(&bundle_id).unwrap_or_default())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn finalized_bundle_da_height(&self, bundle_id: u32) -> Result<Option<u64>> {
+        self._finalized_bundle_da_height(bundle_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn block_submission(&self, height: u32) -> Result<Option<BlockSubmission>> {
+        self._block_submission(height).await.map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn block_submissions(
+        &self,
+        before_height: Option<u32>,
+        heights: HeightRange,
+        limit: usize,
+    ) -> Result<Vec<BlockSubmission>> {
+        self._block_submissions(before_height, heights, limit)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn block_submission_txs(
+        &self,
+        submission_id: NonNegative<i32>,
+    ) -> Result<Vec<BlockSubmissionTx>> {
+        self._block_submission_txs(submission_id)
+            .await
+            .map_err(Into::into)
+    }
+}
+
+impl services::admin::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
+    async fn record_admin_action(&self, entry: services::admin::port::AuditEntry) -> Result<()> {
+        self._record_admin_action(entry).await.map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn pause_runner(&self, runner: String, paused_at: DateTime<Utc>) -> Result<()> {
+        self._pause_runner(runner, paused_at)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn resume_runner(&self, runner: String) -> Result<()> {
+        self._resume_runner(runner).await.map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn paused_runners(&self) -> Result<Vec<String>> {
+        self._paused_runners().await.map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn acknowledge_foreign_commits(&self, acknowledged_at: DateTime<Utc>) -> Result<u64> {
+        self._acknowledge_foreign_commits(acknowledged_at)
+            .await
+            .map_err(Into::into)
+    }
+}
+
+impl services::events::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
+    async fn record_events(
+        &self,
+        events: NonEmpty<services::events::service::Event>,
+        recorded_at: DateTime<Utc>,
+    ) -> Result<()> {
+        self._record_events(events, recorded_at)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn events_after(
+        &self,
+        last_id: u64,
+        limit: usize,
+    ) -> Result<Vec<services::events::service::RecordedEvent>> {
+        self._events_after(last_id, limit).await.map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn latest_event_id(&self) -> Result<Option<u64>> {
+        self._latest_event_id().await.map_err(Into::into)
+    }
+}
+
+impl services::commit_watcher::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
+    async fn record_observed_commit(
+        &self,
+        commit: services::commit_watcher::port::ObservedCommit,
+        foreign: bool,
+        observed_at: DateTime<Utc>,
+    ) -> Result<bool> {
+        self._record_observed_commit(commit, foreign, observed_at)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn record_unverified_commit(
+        &self,
+        commit: services::commit_watcher::port::ObservedCommit,
+        observed_at: DateTime<Utc>,
+    ) -> Result<bool> {
+        self._record_unverified_commit(commit, observed_at)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn unverified_commits(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<services::commit_watcher::port::ObservedCommit>> {
+        self._unverified_commits(limit).await.map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn latest_observed_commit_l1_block(&self) -> Result<Option<u64>> {
+        self._latest_observed_commit_l1_block()
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn foreign_commit_observed(&self) -> Result<bool> {
+        self._foreign_commit_observed().await.map_err(Into::into)
+    }
+}
+
+impl services::health_reporter::port::Storage for Postgres {
+    #[tracing::instrument(skip_all)]
+    async fn ping(&self) -> Result<()> {
+        self._ping().await.map_err(Into::into)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
@@ -397,87 +775,442 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn updating_a_missing_submission_tx_causes_an_error() {
+    async fn finalizing_a_tx_squeezes_out_the_pending_ones_of_the_same_nonce() {
         use services::block_committer::port::Storage;
 
         // given
-        let process = PostgresProcess::shared().await.unwrap();
-        let db = process.create_random_db().await.unwrap();
+        let storage = start_db().await;
 
-        let submission_tx = given_pending_tx(0);
+        let submission = given_incomplete_submission(random_non_zero_height());
+        let submission_id = storage
+            .record_block_submission(given_pending_tx(0), submission, TestClock::default().now())
+            .await
+            .unwrap();
+        let replacement = BlockSubmissionTx {
+            hash: [1; 32],
+            submission_id: Some(submission_id),
+            ..given_pending_tx(0)
+        };
+        storage
+            .record_block_submission_tx(replacement, TestClock::default().now())
+            .await
+            .unwrap();
 
         // when
-        let result = db
-            .update_block_submission_tx(submission_tx.hash, TransactionState::Finalized(Utc::now()))
-            .await;
+        storage
+            .update_block_submission_tx([1; 32], TransactionState::Finalized(Utc::now()))
+            .await
+            .unwrap();
 
         // then
-        let Err(services::Error::Storage(msg)) = result else {
-            panic!("should be storage error");
-        };
-
-        let tx_hash = hex::encode(submission_tx.hash);
+        let states = sqlx::query_as::<_, (Vec<u8>, i16)>(
+            "SELECT hash, state FROM l1_transaction ORDER BY hash",
+        )
+        .fetch_all(&storage.db.pool())
+        .await
+        .unwrap();
         assert_eq!(
-            msg,
-            format!("Cannot update tx state! Tx with hash: `{tx_hash}` not found in DB.")
+            states,
+            vec![
+                (
+                    vec![0; 32],
+                    i16::from(mappings::tables::L1TxState::SqueezedOut)
+                ),
+                (
+                    vec![1; 32],
+                    i16::from(mappings::tables::L1TxState::Finalized)
+                ),
+            ]
         );
     }
 
     #[tokio::test]
-    async fn can_insert_bundle_and_fragments() {
-        use services::{
-            block_bundler::port::Storage, state_committer::port::Storage as CommitterStorage,
-        };
+    async fn recording_another_tx_reopens_the_submission() {
+        use services::block_committer::port::Storage;
 
         // given
         let storage = start_db().await;
 
-        let block_range = 1..=5;
-        let fragment_1 = Fragment {
-            data: nonempty![1u8, 2, 3],
-            unused_bytes: 1000,
-            total_bytes: 100.try_into().unwrap(),
-        };
-        let fragment_2 = Fragment {
-            data: nonempty![4u8, 5, 6],
-            unused_bytes: 1000,
-            total_bytes: 100.try_into().unwrap(),
-        };
-        let fragments = nonempty![fragment_1.clone(), fragment_2.clone()];
+        let submission = given_incomplete_submission(random_non_zero_height());
+        let submission_id = storage
+            .record_block_submission(given_pending_tx(0), submission, TestClock::default().now())
+            .await
+            .unwrap();
+        storage
+            .update_block_submission_tx([0; 32], TransactionState::Finalized(Utc::now()))
+            .await
+            .unwrap();
 
-        let next_id = storage.next_bundle_id().await.unwrap();
         // when
         storage
-            .insert_bundle_and_fragments(next_id, block_range.clone(), fragments.clone())
+            .record_block_submission_tx(
+                BlockSubmissionTx {
+                    submission_id: Some(submission_id),
+                    ..given_pending_tx(1)
+                },
+                TestClock::default().now(),
+            )
             .await
             .unwrap();
 
         // then
-        let inserted_fragments = storage
-            .oldest_nonfinalized_fragments(0, 2)
+        let submission = storage
+            .submission_w_latest_block()
+            .await
+            .unwrap()
+            .expect("submission to exist");
+        assert!(!submission.completed);
+
+        let pending_hashes = storage
+            .get_pending_block_submission_txs(submission_id)
             .await
             .unwrap()
             .into_iter()
+            .map(|tx| tx.hash)
             .collect_vec();
-
-        assert_eq!(inserted_fragments.len(), 2);
-        for (inserted_fragment, given_fragment) in inserted_fragments.iter().zip(fragments.iter()) {
-            assert_eq!(inserted_fragment.fragment, *given_fragment);
-        }
-    }
-
-    fn round_to_millis(date: DateTime<Utc>) -> DateTime<Utc> {
-        DateTime::from_timestamp_millis(date.timestamp_millis()).unwrap()
+        assert_eq!(pending_hashes, vec![[1; 32]]);
     }
 
     #[tokio::test]
-    async fn can_get_last_time_a_fragment_was_finalized() {
+    async fn commit_mismatches_are_recorded_and_cleared() {
+        use services::{block_committer::port::Storage, types::CommitMismatch};
+
         // given
         let storage = start_db().await;
 
-        let fragment_ids = ensure_some_fragments_exists_in_the_db(storage.clone(), 0..=0).await;
-        let tx = L1Tx {
-            hash: rand::random::<[u8; 32]>(),
+        let submission = given_incomplete_submission(random_non_zero_height());
+        let submission_id = storage
+            .record_block_submission(given_pending_tx(0), submission, TestClock::default().now())
+            .await
+            .unwrap();
+        let mismatches = || async {
+            sqlx::query_as::<_, (i16, Option<Vec<u8>>)>(
+                "SELECT kind, onchain_block_hash FROM commit_mismatch",
+            )
+            .fetch_all(&storage.db.pool())
+            .await
+            .unwrap()
+        };
+
+        // when
+        storage
+            .set_commit_mismatch(
+                submission_id,
+                Some(CommitMismatch::Missing),
+                TestClock::default().now(),
+            )
+            .await
+            .unwrap();
+        let missing = mismatches().await;
+
+        storage
+            .set_commit_mismatch(
+                submission_id,
+                Some(CommitMismatch::Foreign {
+                    block_hash: [7; 32],
+                }),
+                TestClock::default().now(),
+            )
+            .await
+            .unwrap();
+        let foreign = mismatches().await;
+
+        storage
+            .set_commit_mismatch(submission_id, None, TestClock::default().now())
+            .await
+            .unwrap();
+        let cleared = mismatches().await;
+
+        // then
+        assert_eq!(missing, vec![(0, None)]);
+        assert_eq!(foreign, vec![(1, Some(vec![7; 32]))]);
+        assert!(cleared.is_empty());
+    }
+
+    #[tokio::test]
+    async fn observing_the_same_commit_again_is_ignored() {
+        use services::commit_watcher::port::{ObservedCommit, Storage};
+
+        // given
+        let storage = start_db().await;
+        let commit = ObservedCommit {
+            commit_height: 5,
+            block_hash: [1; 32],
+            tx_hash: [2; 32],
+            log_index: 3,
+            l1_block: 100,
+        };
+        storage
+            .record_observed_commit(commit, true, TestClock::default().now())
+            .await
+            .unwrap();
+
+        // when
+        let result = storage
+            .record_observed_commit(commit, false, TestClock::default().now())
+            .await;
+
+        // then
+        assert!(!result.unwrap());
+        let stored = sqlx::query_as::<_, (i64, Vec<u8>, bool)>(
+            "SELECT commit_height, block_hash, foreign_commit FROM observed_commits",
+        )
+        .fetch_all(&storage.db.pool())
+        .await
+        .unwrap();
+        assert_eq!(stored, vec![(5, vec![1; 32], true)]);
+    }
+
+    #[tokio::test]
+    async fn unverified_commit_is_listed_until_it_is_checked() {
+        use services::commit_watcher::port::{ObservedCommit, Storage};
+
+        // given
+        let storage = start_db().await;
+        let commit = ObservedCommit {
+            commit_height: 5,
+            block_hash: [1; 32],
+            tx_hash: [2; 32],
+            log_index: 3,
+            l1_block: 100,
+        };
+        let newly_unverified = storage
+            .record_unverified_commit(commit, TestClock::default().now())
+            .await
+            .unwrap();
+        let unverified_before = storage.unverified_commits(10).await.unwrap();
+
+        // when
+        let checked = storage
+            .record_observed_commit(commit, true, TestClock::default().now())
+            .await
+            .unwrap();
+
+        // then
+        assert!(newly_unverified);
+        assert_eq!(unverified_before, vec![commit]);
+        assert!(checked);
+        assert!(storage.unverified_commits(10).await.unwrap().is_empty());
+        assert!(storage.foreign_commit_observed().await.unwrap());
+        assert_eq!(
+            storage.latest_observed_commit_l1_block().await.unwrap(),
+            Some(100)
+        );
+    }
+
+    #[tokio::test]
+    async fn observed_commits_tell_latest_l1_block_and_whether_any_was_foreign() {
+        use services::commit_watcher::port::{ObservedCommit, Storage};
+
+        // given
+        let storage = start_db().await;
+        let commit = |l1_block: u64| ObservedCommit {
+            commit_height: 5,
+            block_hash: [1; 32],
+            tx_hash: [l1_block as u8; 32],
+            log_index: 0,
+            l1_block,
+        };
+        let nothing_observed = (
+            storage.latest_observed_commit_l1_block().await.unwrap(),
+            storage.foreign_commit_observed().await.unwrap(),
+        );
+
+        // when
+        for (l1_block, foreign) in [(100, false), (120, true), (110, false)] {
+            let recorded = storage
+                .record_observed_commit(commit(l1_block), foreign, TestClock::default().now())
+                .await
+                .unwrap();
+            assert!(recorded);
+        }
+
+        // then
+        assert_eq!(nothing_observed, (None, false));
+        assert_eq!(
+            storage.latest_observed_commit_l1_block().await.unwrap(),
+            Some(120)
+        );
+        assert!(storage.foreign_commit_observed().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn paused_runners_are_stored_until_resumed() {
+        use services::admin::port::Storage;
+
+        // given
+        let storage = start_db().await;
+        let now = TestClock::default().now();
+        for runner in ["state-pruner", "block-committer", "state-pruner"] {
+            storage.pause_runner(runner.to_string(), now).await.unwrap();
+        }
+
+        // when
+        storage
+            .resume_runner("block-committer".to_string())
+            .await
+            .unwrap();
+
+        // then
+        assert_eq!(
+            storage.paused_runners().await.unwrap(),
+            vec!["state-pruner".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn acknowledged_foreign_commits_are_no_longer_reported() {
+        use services::{
+            admin::port::Storage as AdminStorage,
+            commit_watcher::port::{ObservedCommit, Storage},
+        };
+
+        // given
+        let storage = start_db().await;
+        let now = TestClock::default().now();
+        let commit = ObservedCommit {
+            commit_height: 5,
+            block_hash: [1; 32],
+            tx_hash: [1; 32],
+            log_index: 0,
+            l1_block: 100,
+        };
+        storage
+            .record_observed_commit(commit, true, now)
+            .await
+            .unwrap();
+
+        // when
+        let acknowledged = storage.acknowledge_foreign_commits(now).await.unwrap();
+
+        // then
+        assert_eq!(acknowledged, 1);
+        assert!(!storage.foreign_commit_observed().await.unwrap());
+        assert_eq!(storage.acknowledge_foreign_commits(now).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn pruning_keeps_foreign_and_latest_observed_commits() {
+        use services::{
+            commit_watcher::port::{ObservedCommit, Storage},
+            state_pruner::port::Storage as PrunerStorage,
+        };
+
+        // given
+        let storage = start_db().await;
+        let observed_at = TestClock::default().now();
+        for (l1_block, foreign) in [(100, false), (110, true), (120, false)] {
+            let commit = ObservedCommit {
+                commit_height: 5,
+                block_hash: [1; 32],
+                tx_hash: [l1_block as u8; 32],
+                log_index: 0,
+                l1_block,
+            };
+            storage
+                .record_observed_commit(commit, foreign, observed_at)
+                .await
+                .unwrap();
+        }
+
+        // when
+        storage
+            .prune_entries_older_than(observed_at + Duration::from_secs(1))
+            .await
+            .unwrap();
+
+        // then
+        let remaining =
+            sqlx::query_scalar::<_, i64>("SELECT l1_block FROM observed_commits ORDER BY l1_block")
+                .fetch_all(&storage.db.pool())
+                .await
+                .unwrap();
+        assert_eq!(remaining, vec![110, 120]);
+        assert!(storage.foreign_commit_observed().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn updating_a_missing_submission_tx_causes_an_error() {
+        use services::block_committer::port::Storage;
+
+        // given
+        let process = PostgresProcess::shared().await.unwrap();
+        let db = process.create_random_db().await.unwrap();
+
+        let submission_tx = given_pending_tx(0);
+
+        // when
+        let result = db
+            .update_block_submission_tx(submission_tx.hash, TransactionState::Finalized(Utc::now()))
+            .await;
+
+        // then
+        let Err(services::Error::Storage(msg)) = result else {
+            panic!("should be storage error");
+        };
+
+        let tx_hash = hex::encode(submission_tx.hash);
+        assert_eq!(
+            msg,
+            format!("Cannot update tx state! Tx with hash: `{tx_hash}` not found in DB.")
+        );
+    }
+
+    #[tokio::test]
+    async fn can_insert_bundle_and_fragments() {
+        use services::{
+            block_bundler::port::Storage, state_committer::port::Storage as CommitterStorage,
+        };
+
+        // given
+        let storage = start_db().await;
+
+        let block_range = 1..=5;
+        let fragment_1 = Fragment {
+            data: nonempty![1u8, 2, 3],
+            unused_bytes: 1000,
+            total_bytes: 100.try_into().unwrap(),
+        };
+        let fragment_2 = Fragment {
+            data: nonempty![4u8, 5, 6],
+            unused_bytes: 1000,
+            total_bytes: 100.try_into().unwrap(),
+        };
+        let fragments = nonempty![fragment_1.clone(), fragment_2.clone()];
+
+        let next_id = storage.next_bundle_id().await.unwrap();
+        // when
+        storage
+            .insert_bundle_and_fragments(next_id, block_range.clone(), fragments.clone())
+            .await
+            .unwrap();
+
+        // then
+        let inserted_fragments = storage
+            .oldest_nonfinalized_fragments(0, 2)
+            .await
+            .unwrap()
+            .into_iter()
+            .collect_vec();
+
+        assert_eq!(inserted_fragments.len(), 2);
+        for (inserted_fragment, given_fragment) in inserted_fragments.iter().zip(fragments.iter()) {
+            assert_eq!(inserted_fragment.fragment, *given_fragment);
+        }
+    }
+
+    fn round_to_millis(date: DateTime<Utc>) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(date.timestamp_millis()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn can_get_last_time_a_fragment_was_finalized() {
+        // given
+        let storage = start_db().await;
+
+        let fragment_ids = ensure_some_fragments_exists_in_the_db(storage.clone(), 0..=0).await;
+        let tx = L1Tx {
+            hash: rand::random::<[u8; 32]>(),
             ..Default::default()
         };
         let hash = tx.hash;
@@ -986,6 +1719,138 @@ mod tests {
         assert_eq!(bundle_cost[0].cost, cost_per_tx.total_fee);
         assert_eq!(bundle_cost[0].da_block_height, cost_per_tx.da_block_height);
 
+        let bundle_id = u32::try_from(bundle_cost[0].id).unwrap();
+        let by_bundle = storage.finalized_bundle_cost(bundle_id).await?.unwrap();
+        assert_eq!(by_bundle.cost, cost_per_tx.total_fee);
+        assert!(
+            storage
+                .finalized_bundle_cost(bundle_id + 1)
+                .await?
+                .is_none()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn costs_are_aggregated_per_bucket_and_range() -> Result<()> {
+        use services::cost_reporter::port::{CostAggregate, CostBucket, CostFilter, Storage};
+
+        // given
+        let storage = start_db().await;
+        let at = |timestamp: &str| {
+            DateTime::parse_from_rfc3339(timestamp)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+
+        for (nonce, heights, total_fee, da_block_height, finalized_at) in [
+            (1, 0..=4, 1000, 100, at("2024-01-01T10:30:00Z")),
+            (2, 5..=9, 3000, 200, at("2024-01-02T08:00:00Z")),
+        ] {
+            let fragment_ids =
+                ensure_some_fragments_exists_in_the_db(storage.clone(), heights).await;
+            let tx = L1Tx {
+                hash: rand::random::<[u8; 32]>(),
+                nonce,
+                ..Default::default()
+            };
+            storage
+                .record_pending_tx(tx.clone(), fragment_ids, finalized_at)
+                .await?;
+            storage
+                .update_tx_states_and_costs(
+                    vec![],
+                    vec![(tx.hash, nonce, TransactionState::Finalized(finalized_at))],
+                    vec![TransactionCostUpdate {
+                        tx_hash: tx.hash,
+                        total_fee,
+                        da_block_height,
+                    }],
+                )
+                .await?;
+        }
+
+        let bundle = |bucket_start, total_cost| CostAggregate {
+            bucket_start,
+            bundles: 1,
+            total_cost,
+            bytes_posted: 1800,
+            blocks_covered: 5,
+            blob_bytes: 2000,
+            used_blob_bytes: 1800,
+        };
+
+        // when
+        let totals = storage.cost_aggregates(CostFilter::default(), None).await?;
+        let per_day = storage
+            .cost_aggregates(CostFilter::default(), Some(CostBucket::Day))
+            .await?;
+        let by_time = storage
+            .cost_aggregates(
+                CostFilter {
+                    finalized_before: Some(at("2024-01-02T00:00:00Z")),
+                    ..Default::default()
+                },
+                None,
+            )
+            .await?;
+        let by_da_height = storage
+            .cost_aggregates(
+                CostFilter {
+                    from_da_height: Some(150),
+                    ..Default::default()
+                },
+                None,
+            )
+            .await?;
+        let by_l2_height = storage
+            .cost_aggregates(
+                CostFilter {
+                    l2_heights: HeightRange {
+                        from: None,
+                        to: Some(4),
+                    },
+                    ..Default::default()
+                },
+                None,
+            )
+            .await?;
+        let none = storage
+            .cost_aggregates(
+                CostFilter {
+                    from_da_height: Some(201),
+                    ..Default::default()
+                },
+                None,
+            )
+            .await?;
+
+        // then
+        assert_eq!(
+            totals,
+            vec![CostAggregate {
+                bucket_start: None,
+                bundles: 2,
+                total_cost: 4000,
+                bytes_posted: 3600,
+                blocks_covered: 10,
+                blob_bytes: 4000,
+                used_blob_bytes: 3600,
+            }]
+        );
+        assert_eq!(
+            per_day,
+            vec![
+                bundle(Some(at("2024-01-01T00:00:00Z")), 1000),
+                bundle(Some(at("2024-01-02T00:00:00Z")), 3000),
+            ]
+        );
+        assert_eq!(by_time, vec![bundle(None, 1000)]);
+        assert_eq!(by_da_height, vec![bundle(None, 3000)]);
+        assert_eq!(by_l2_height, vec![bundle(None, 1000)]);
+        assert!(none.is_empty());
+
         Ok(())
     }
 
@@ -1223,21 +2088,102 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_fee_split_across_multiple_bundles() {
-        let storage = start_db().await;
+    async fn finalized_costs_are_queued_until_delivered() -> Result<()> {
+        use services::cost_feed::port::Storage as CostFeedStorage;
 
-        let bundle_a_id = storage.next_bundle_id().await.unwrap();
-        let fragment_a = Fragment {
-            data: nonempty![0xaa],
-            unused_bytes: 0,
-            total_bytes: 10.try_into().unwrap(),
-        };
+        // given
+        let storage = start_db().await.with_cost_feed_outbox();
+        ensure_finalized_fragments_exist_in_the_db(storage.clone(), 1..=10, 1000, 5000).await;
+        ensure_finalized_fragments_exist_in_the_db(storage.clone(), 11..=20, 2000, 5001).await;
+        // not finalized, so not queued
+        ensure_some_fragments_exists_in_the_db(storage.clone(), 21..=30).await;
+
+        let queued = storage.undelivered_costs(10).await?;
+        let first_id = queued[0].cost.id;
         storage
-            .insert_bundle_and_fragments(bundle_a_id, 1..=5, nonempty!(fragment_a.clone()))
-            .await
-            .unwrap();
+            .record_cost_delivery_failure(first_id, "timeout".to_string())
+            .await?;
 
-        let fragment_a_id = storage
+        // when
+        storage.mark_cost_delivered(queued[1].cost.id).await?;
+
+        // then
+        assert_eq!(queued.len(), 2);
+        assert_eq!(queued[0].cost.cost, 1000);
+        assert_eq!(queued[1].cost.start_height, 11);
+
+        let remaining = storage.undelivered_costs(10).await?;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].cost.id, first_id);
+        assert_eq!(remaining[0].attempts, 1);
+
+        let backlog = storage.undelivered_cost_backlog().await?;
+        assert_eq!(backlog.count, 1);
+        assert!(backlog.oldest_finalized_at.is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn finalized_costs_are_not_queued_without_a_cost_feed() -> Result<()> {
+        use services::cost_feed::port::Storage as CostFeedStorage;
+
+        // given
+        let storage = start_db().await;
+
+        // when
+        ensure_finalized_fragments_exist_in_the_db(storage.clone(), 1..=10, 1000, 5000).await;
+
+        // then
+        assert!(storage.undelivered_costs(10).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pruning_keeps_bundles_whose_cost_was_not_delivered() -> Result<()> {
+        use services::{
+            cost_feed::port::Storage as CostFeedStorage,
+            state_pruner::port::Storage as PrunerStorage,
+        };
+
+        // given
+        let storage = start_db().await.with_cost_feed_outbox();
+        ensure_finalized_fragments_exist_in_the_db(storage.clone(), 1..=10, 1000, 5000).await;
+        ensure_finalized_fragments_exist_in_the_db(storage.clone(), 11..=20, 2000, 5001).await;
+        let queued = storage.undelivered_costs(10).await?;
+        storage.mark_cost_delivered(queued[0].cost.id).await?;
+
+        // when
+        storage
+            .prune_entries_older_than(Utc::now() + Duration::from_secs(60))
+            .await?;
+
+        // then
+        let remaining = storage.undelivered_costs(10).await?;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].cost.id, queued[1].cost.id);
+        assert_eq!(storage.table_sizes().await?.bundles, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fee_split_across_multiple_bundles() {
+        let storage = start_db().await;
+
+        let bundle_a_id = storage.next_bundle_id().await.unwrap();
+        let fragment_a = Fragment {
+            data: nonempty![0xaa],
+            unused_bytes: 0,
+            total_bytes: 10.try_into().unwrap(),
+        };
+        storage
+            .insert_bundle_and_fragments(bundle_a_id, 1..=5, nonempty!(fragment_a.clone()))
+            .await
+            .unwrap();
+
+        let fragment_a_id = storage
             .oldest_nonfinalized_fragments(0, 10)
             .await
             .unwrap()
@@ -1424,4 +2370,481 @@ mod tests {
             "Blocks 0..=1 are excluded after bundling"
         );
     }
+
+    #[tokio::test]
+    async fn only_one_candidate_holds_the_lease() {
+        use services::leader_election::port::Storage;
+
+        // given
+        let storage = start_db().await;
+        let lease_duration = Duration::from_secs(30);
+
+        // when
+        let first = storage
+            .acquire_or_renew_lease("bundler", "first", lease_duration)
+            .await
+            .unwrap();
+        let second = storage
+            .acquire_or_renew_lease("bundler", "second", lease_duration)
+            .await
+            .unwrap();
+        let renewed = storage
+            .acquire_or_renew_lease("bundler", "first", lease_duration)
+            .await
+            .unwrap();
+
+        // then
+        assert!(first);
+        assert!(!second);
+        assert!(renewed);
+    }
+
+    #[tokio::test]
+    async fn expired_lease_can_be_taken_over() {
+        use services::leader_election::port::Storage;
+
+        // given
+        let storage = start_db().await;
+        storage
+            .acquire_or_renew_lease("bundler", "first", Duration::from_millis(10))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // when
+        let taken_over = storage
+            .acquire_or_renew_lease("bundler", "second", Duration::from_secs(30))
+            .await
+            .unwrap();
+        let first_renewed = storage
+            .acquire_or_renew_lease("bundler", "first", Duration::from_secs(30))
+            .await
+            .unwrap();
+
+        // then
+        assert!(taken_over);
+        assert!(!first_renewed);
+    }
+
+    #[tokio::test]
+    async fn released_leases_can_be_acquired_by_another_candidate() {
+        use services::leader_election::port::Storage;
+
+        // given
+        let storage = start_db().await;
+        let lease_duration = Duration::from_secs(30);
+        storage
+            .acquire_or_renew_lease("bundler", "first", lease_duration)
+            .await
+            .unwrap();
+
+        // when
+        storage.release_leases("second").await.unwrap();
+        let before_release = storage
+            .acquire_or_renew_lease("bundler", "second", lease_duration)
+            .await
+            .unwrap();
+        storage.release_leases("first").await.unwrap();
+        let after_release = storage
+            .acquire_or_renew_lease("bundler", "second", lease_duration)
+            .await
+            .unwrap();
+
+        // then
+        assert!(!before_release);
+        assert!(after_release);
+    }
+
+    #[tokio::test]
+    async fn leases_are_held_independently() {
+        use services::leader_election::port::Storage;
+
+        // given
+        let storage = start_db().await;
+        let lease_duration = Duration::from_secs(30);
+        storage
+            .acquire_or_renew_lease("bundler", "first", lease_duration)
+            .await
+            .unwrap();
+
+        // when
+        let other_lease = storage
+            .acquire_or_renew_lease("state_committer", "second", lease_duration)
+            .await
+            .unwrap();
+        let same_lease = storage
+            .acquire_or_renew_lease("bundler", "second", lease_duration)
+            .await
+            .unwrap();
+
+        // then
+        assert!(other_lease);
+        assert!(!same_lease);
+    }
+
+    #[tokio::test]
+    async fn bundles_are_listed_newest_first_with_their_stats() {
+        use services::bundle_reporter::port::Storage;
+
+        // given
+        let storage = start_db().await;
+        ensure_finalized_fragments_exist_in_the_db(storage.clone(), 0..=1, 1000, 10).await;
+        insert_sequence_of_unbundled_blocks(storage.clone(), 2..=3).await;
+        ensure_some_fragments_exists_in_the_db(storage.clone(), 2..=3).await;
+
+        // when
+        let bundles = storage
+            .bundles(None, HeightRange::default(), 10)
+            .await
+            .unwrap();
+        let older = storage
+            .bundles(Some(bundles[0].id), HeightRange::default(), 10)
+            .await
+            .unwrap();
+
+        // then
+        assert_eq!(bundles.len(), 2);
+        let (newest, oldest) = (&bundles[0], &bundles[1]);
+
+        assert_eq!((newest.start_height, newest.end_height), (2, 3));
+        assert_eq!(newest.num_fragments, 2);
+        assert_eq!(newest.num_finalized_fragments, 0);
+        assert_eq!(newest.compressed_size, 1800);
+        assert_eq!(newest.uncompressed_size, Some(2));
+
+        assert_eq!((oldest.start_height, oldest.end_height), (0, 1));
+        assert_eq!(oldest.num_finalized_fragments, 2);
+        assert_eq!(oldest.uncompressed_size, None);
+
+        assert_eq!(older.len(), 1);
+        assert_eq!(older[0].id, oldest.id);
+    }
+
+    #[tokio::test]
+    async fn bundle_fragments_list_the_txs_that_carried_them() {
+        use services::bundle_reporter::port::Storage;
+
+        // given
+        let storage = start_db().await;
+        let fragment_ids = ensure_some_fragments_exists_in_the_db(storage.clone(), 0..=1).await;
+        let failed_tx = ensure_fragments_have_transaction(
+            storage.clone(),
+            fragment_ids.clone(),
+            TransactionState::Failed,
+        )
+        .await;
+        let pending_tx = ensure_fragments_have_transaction(
+            storage.clone(),
+            nonempty![*fragment_ids.first()],
+            TransactionState::Pending,
+        )
+        .await;
+        let bundle_id = storage
+            .bundles(None, HeightRange::default(), 1)
+            .await
+            .unwrap()[0]
+            .id;
+
+        // when
+        let mut bundles = storage
+            .fragments_of_bundles(vec![bundle_id, bundle_id + 1])
+            .await
+            .unwrap();
+
+        // then
+        assert_eq!(bundles.len(), 1);
+        let fragments = bundles.remove(&bundle_id).unwrap();
+        assert_eq!(fragments.len(), 2);
+        assert_eq!(fragments[0].idx, 0);
+        assert_eq!(fragments[1].idx, 1);
+
+        let hashes = |idx: usize| {
+            fragments[idx]
+                .transactions
+                .iter()
+                .map(|tx| tx.hash)
+                .collect_vec()
+        };
+        assert_eq!(hashes(0), vec![failed_tx, pending_tx]);
+        assert_eq!(hashes(1), vec![failed_tx]);
+        assert_eq!(fragments[0].transactions[0].state, TransactionState::Failed);
+    }
+
+    #[tokio::test]
+    async fn bundles_and_blob_txs_are_filtered_by_height() {
+        use services::bundle_reporter::port::Storage;
+
+        // given
+        let storage = start_db().await;
+        let old_fragments = ensure_some_fragments_exists_in_the_db(storage.clone(), 0..=1).await;
+        let old_tx = ensure_fragments_have_transaction(
+            storage.clone(),
+            old_fragments,
+            TransactionState::Finalized(TestClock::default().now()),
+        )
+        .await;
+        let new_fragments = ensure_some_fragments_exists_in_the_db(storage.clone(), 2..=3).await;
+        let new_tx = ensure_fragments_have_transaction(
+            storage.clone(),
+            new_fragments,
+            TransactionState::Pending,
+        )
+        .await;
+        let from_two = HeightRange {
+            from: Some(2),
+            to: None,
+        };
+        let up_to_one = HeightRange {
+            from: None,
+            to: Some(1),
+        };
+
+        // when
+        let recent_bundles = storage.bundles(None, from_two, 10).await.unwrap();
+        let old_bundles = storage.bundles(None, up_to_one, 10).await.unwrap();
+        let recent_txs = storage.blob_transactions(None, from_two, 10).await.unwrap();
+        let all_txs = storage
+            .blob_transactions(None, HeightRange::default(), 10)
+            .await
+            .unwrap();
+        let older_txs = storage
+            .blob_transactions(all_txs[0].id, HeightRange::default(), 10)
+            .await
+            .unwrap();
+        let carried = storage
+            .bundle_ids_carried_by(vec![old_tx, new_tx, [0; 32]])
+            .await
+            .unwrap();
+
+        // then
+        assert_eq!(recent_bundles.len(), 1);
+        assert_eq!(recent_bundles[0].start_height, 2);
+        assert_eq!(old_bundles.len(), 1);
+        assert_eq!(old_bundles[0].end_height, 1);
+
+        assert_eq!(
+            recent_txs.iter().map(|tx| tx.hash).collect_vec(),
+            vec![new_tx]
+        );
+        assert_eq!(
+            all_txs.iter().map(|tx| tx.hash).collect_vec(),
+            vec![new_tx, old_tx]
+        );
+        assert_eq!(
+            older_txs.iter().map(|tx| tx.hash).collect_vec(),
+            vec![old_tx]
+        );
+
+        assert_eq!(carried.len(), 2);
+        assert_eq!(carried[&old_tx], vec![old_bundles[0].id]);
+        assert_eq!(carried[&new_tx], vec![recent_bundles[0].id]);
+    }
+
+    #[tokio::test]
+    async fn unknown_bundle_is_not_found() {
+        use services::bundle_reporter::port::Storage;
+
+        // given
+        let storage = start_db().await;
+
+        // when
+        let bundles = storage.bundles_by_id(vec![1]).await.unwrap();
+
+        // then
+        assert!(bundles.is_empty());
+    }
+
+    #[tokio::test]
+    async fn finds_the_bundle_containing_a_block() {
+        use services::block_reporter::port::Storage;
+
+        // given
+        let storage = start_db().await;
+        insert_sequence_of_unbundled_blocks(storage.clone(), 0..=3).await;
+        ensure_some_fragments_exists_in_the_db(storage.clone(), 0..=1).await;
+        ensure_some_fragments_exists_in_the_db(storage.clone(), 2..=3).await;
+
+        // when
+        let bundle = storage.bundle_containing(2).await.unwrap().unwrap();
+        let not_bundled = storage.bundle_containing(4).await.unwrap();
+
+        // then
+        assert_eq!((bundle.start_height, bundle.end_height), (2, 3));
+        assert!(not_bundled.is_none());
+        assert!(storage.is_block_imported(3).await.unwrap());
+        assert!(!storage.is_block_imported(4).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn finds_the_contract_commit_of_a_block() {
+        use services::{block_committer::port::Storage as _, block_reporter::port::Storage};
+
+        // given
+        let storage = start_db().await;
+        let height = random_non_zero_height();
+        let submission_tx = given_pending_tx(0);
+        let submission_id = storage
+            .record_block_submission(
+                submission_tx.clone(),
+                given_incomplete_submission(height),
+                TestClock::default().now(),
+            )
+            .await
+            .unwrap();
+        let finalized_at = TestClock::default().now();
+        storage
+            .update_block_submission_tx(
+                submission_tx.hash,
+                TransactionState::Finalized(finalized_at),
+            )
+            .await
+            .unwrap();
+
+        // when
+        let submission = storage.block_submission(height).await.unwrap().unwrap();
+        let txs = storage.block_submission_txs(submission_id).await.unwrap();
+
+        // then
+        assert_eq!(submission.id, Some(submission_id));
+        assert!(submission.completed);
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].hash, submission_tx.hash);
+        assert_eq!(txs[0].state, TransactionState::Finalized(finalized_at));
+        assert!(
+            storage
+                .block_submission(height - 1)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn contract_commits_are_listed_highest_block_first() {
+        use services::{block_committer::port::Storage as _, block_reporter::port::Storage};
+
+        // given
+        let storage = start_db().await;
+        for (nonce, height) in [10, 20, 30].into_iter().enumerate() {
+            storage
+                .record_block_submission(
+                    given_pending_tx(nonce as u32),
+                    given_incomplete_submission(height),
+                    TestClock::default().now(),
+                )
+                .await
+                .unwrap();
+        }
+        let up_to_twenty = HeightRange {
+            from: None,
+            to: Some(20),
+        };
+
+        // when
+        let all = storage
+            .block_submissions(None, HeightRange::default(), 10)
+            .await
+            .unwrap();
+        let below_thirty = storage
+            .block_submissions(Some(30), HeightRange::default(), 1)
+            .await
+            .unwrap();
+        let in_range = storage
+            .block_submissions(None, up_to_twenty, 10)
+            .await
+            .unwrap();
+
+        // then
+        let heights = |submissions: &[BlockSubmission]| {
+            submissions.iter().map(|s| s.block_height).collect_vec()
+        };
+        assert_eq!(heights(&all), vec![30, 20, 10]);
+        assert_eq!(heights(&below_thirty), vec![20]);
+        assert_eq!(heights(&in_range), vec![20, 10]);
+    }
+
+    #[tokio::test]
+    async fn records_admin_actions() {
+        use services::admin::port::{AuditEntry, Storage};
+
+        // given
+        let storage = start_db().await;
+        let entry = AuditEntry {
+            action: "pause_runner".to_string(),
+            runner: "state-committer".to_string(),
+            requested_by: Some("127.0.0.1".to_string()),
+            performed_at: TestClock::default().now(),
+        };
+
+        // when
+        storage.record_admin_action(entry.clone()).await.unwrap();
+
+        // then
+        let (action, runner, requested_by): (String, String, Option<String>) =
+            sqlx::query_as("SELECT action, runner, requested_by FROM admin_audit_log")
+                .fetch_one(&storage.db.pool())
+                .await
+                .unwrap();
+        assert_eq!(action, entry.action);
+        assert_eq!(runner, entry.runner);
+        assert_eq!(requested_by, entry.requested_by);
+    }
+
+    #[tokio::test]
+    async fn recorded_events_are_read_back_in_order() {
+        use services::events::{
+            port::Storage,
+            service::{Event, RecordedEvent},
+        };
+
+        // given
+        let storage = start_db().await;
+        let recorded_at = TestClock::default().now();
+        let created = Event::BundleCreated {
+            bundle_id: 1,
+            start_height: 0,
+            end_height: 9,
+            num_fragments: 2,
+        };
+        let failed = Event::TxFailed {
+            tx_hash: "0x01".to_string(),
+            nonce: 3,
+        };
+        let committed = Event::BlockCommitted {
+            block_height: 9,
+            tx_hash: "0x02".to_string(),
+        };
+        assert_eq!(storage.latest_event_id().await.unwrap(), None);
+
+        // when
+        storage
+            .record_events(nonempty![created, failed.clone()], recorded_at)
+            .await
+            .unwrap();
+        storage
+            .record_events(nonempty![committed.clone()], recorded_at)
+            .await
+            .unwrap();
+
+        // then
+        let latest = storage.latest_event_id().await.unwrap().unwrap();
+        let first = storage.events_after(0, 1).await.unwrap();
+        let rest = storage.events_after(first[0].id, 10).await.unwrap();
+        assert_eq!(
+            rest,
+            vec![
+                RecordedEvent {
+                    id: first[0].id + 1,
+                    recorded_at,
+                    event: failed,
+                },
+                RecordedEvent {
+                    id: latest,
+                    recorded_at,
+                    event: committed,
+                },
+            ]
+        );
+        assert!(storage.events_after(latest, 10).await.unwrap().is_empty());
+    }
 }
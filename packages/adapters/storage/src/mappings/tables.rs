@@ -500,3 +500,90 @@ impl TryFrom<BundleCost> for services::types::BundleCost {
         })
     }
 }
+
+#[derive(sqlx::FromRow)]
+pub struct BundleOverview {
+    pub id: i32,
+    pub start_height: i64,
+    pub end_height: i64,
+    pub num_fragments: i64,
+    pub num_finalized_fragments: i64,
+    pub compressed_size: i64,
+    pub uncompressed_size: Option<i64>,
+}
+
+impl TryFrom<BundleOverview> for services::types::BundleOverview {
+    type Error = crate::error::Error;
+
+    fn try_from(value: BundleOverview) -> Result<Self, Self::Error> {
+        let convert = |name: &str, value: i64| {
+            value.try_into().map_err(|e| {
+                crate::error::Error::Conversion(format!(
+                    "Invalid db `{name}` ({value}). Reason: {e}"
+                ))
+            })
+        };
+
+        Ok(Self {
+            id: convert("id", value.id.into())?,
+            start_height: convert("start_height", value.start_height)?,
+            end_height: convert("end_height", value.end_height)?,
+            num_fragments: convert("num_fragments", value.num_fragments)?,
+            num_finalized_fragments: convert(
+                "num_finalized_fragments",
+                value.num_finalized_fragments,
+            )?,
+            compressed_size: value.compressed_size.try_into().map_err(|e| {
+                crate::error::Error::Conversion(format!(
+                    "Invalid db `compressed_size` ({}). Reason: {e}",
+                    value.compressed_size
+                ))
+            })?,
+            uncompressed_size: value
+                .uncompressed_size
+                .map(|size| {
+                    size.try_into().map_err(|e| {
+                        crate::error::Error::Conversion(format!(
+                            "Invalid db `uncompressed_size` ({size}). Reason: {e}"
+                        ))
+                    })
+                })
+                .transpose()?,
+        })
+    }
+}
+
+/// A fragment joined with one of the transactions that carried it, if any.
+#[derive(sqlx::FromRow)]
+pub struct FragmentSubmission {
+    pub bundle_id: i32,
+    pub fragment_id: i32,
+    pub idx: i32,
+    pub total_bytes: i64,
+    pub unused_bytes: i64,
+    pub tx_id: Option<i32>,
+    pub hash: Option<Vec<u8>>,
+    pub nonce: Option<i64>,
+    pub max_fee: Option<BigDecimal>,
+    pub priority_fee: Option<BigDecimal>,
+    pub blob_fee: Option<BigDecimal>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub state: Option<i16>,
+    pub finalized_at: Option<DateTime<Utc>>,
+}
+
+impl FragmentSubmission {
+    pub fn transaction(&self) -> Option<L1Tx> {
+        Some(L1Tx {
+            id: self.tx_id?,
+            hash: self.hash.clone()?,
+            nonce: self.nonce?,
+            max_fee: self.max_fee.clone()?,
+            priority_fee: self.priority_fee.clone()?,
+            blob_fee: self.blob_fee.clone()?,
+            created_at: self.created_at,
+            state: self.state?,
+            finalized_at: self.finalized_at,
+        })
+    }
+}
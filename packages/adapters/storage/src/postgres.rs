@@ -1,14 +1,15 @@
-use std::{collections::HashMap, ops::RangeInclusive};
+use std::{collections::HashMap, ops::RangeInclusive, time::Duration};
 
 use futures::{TryStreamExt, stream::BoxStream};
 use itertools::Itertools;
 use metrics::{RegistersMetrics, prometheus::IntGauge};
 use services::{
     block_bundler::port::UnbundledBlocks,
+    commit_watcher::port::ObservedCommit,
     types::{
-        BlockSubmission, BlockSubmissionTx, BundleCost, CompressedFuelBlock, DateTime, Fragment,
-        NonEmpty, NonNegative, TransactionCostUpdate, TransactionState, Utc,
-        storage::SequentialFuelBlocks,
+        BlockSubmission, BlockSubmissionTx, BundleCost, CommitMismatch, CompressedFuelBlock,
+        DateTime, Fragment, HeightRange, NonEmpty, NonNegative, TransactionCostUpdate,
+        TransactionState, Utc, storage::SequentialFuelBlocks,
     },
 };
 use sqlx::{
@@ -53,6 +54,7 @@ impl Default for Metrics {
 pub struct Postgres {
     connection_pool: sqlx::Pool<sqlx::Postgres>,
     metrics: Metrics,
+    cost_feed_outbox: bool,
 }
 
 impl RegistersMetrics for Postgres {
@@ -115,9 +117,17 @@ impl Postgres {
         Ok(Self {
             connection_pool,
             metrics: Metrics::default(),
+            cost_feed_outbox: false,
         })
     }
 
+    /// Queues the costs of finalized bundles for the cost feed. Only enable it if the cost feed
+    /// runs, queued costs keep their bundles from being pruned until delivered.
+    pub fn with_cost_feed_outbox(mut self) -> Self {
+        self.cost_feed_outbox = true;
+        self
+    }
+
     #[cfg(feature = "test-helpers")]
     pub fn db_name(&self) -> String {
         self.connection_pool
@@ -192,6 +202,225 @@ impl Postgres {
         Ok(id)
     }
 
+    pub(crate) async fn _record_block_submission_tx(
+        &self,
+        submission_tx: BlockSubmissionTx,
+        created_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let Some(submission_id) = submission_tx.submission_id else {
+            return Err(Error::Database(format!(
+                "Cannot record tx `{}` without a submission id",
+                hex::encode(submission_tx.hash)
+            )));
+        };
+
+        let mut transaction = self.connection_pool.begin().await?;
+
+        let row = tables::L1FuelBlockSubmissionTx::from(submission_tx);
+        sqlx::query!(
+            "INSERT INTO l1_transaction (hash, nonce, max_fee, priority_fee, submission_id, state, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            row.hash,
+            row.nonce,
+            row.max_fee,
+            row.priority_fee,
+            submission_id.as_i32(),
+            i16::from(L1TxState::Pending),
+            created_at,
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        // a new tx for a completed submission re-commits it
+        sqlx::query!(
+            "UPDATE l1_fuel_block_submission SET completed = false WHERE id = $1",
+            submission_id.as_i32()
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn _set_commit_mismatch(
+        &self,
+        submission_id: NonNegative<i32>,
+        mismatch: Option<CommitMismatch>,
+        detected_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let (kind, onchain_block_hash) = match mismatch {
+            None => {
+                sqlx::query!(
+                    "DELETE FROM commit_mismatch WHERE submission_id = $1",
+                    submission_id.as_i32()
+                )
+                .execute(&self.connection_pool)
+                .await?;
+
+                return Ok(());
+            }
+            Some(CommitMismatch::Missing) => (0i16, None),
+            Some(CommitMismatch::Foreign { block_hash }) => (1i16, Some(block_hash.to_vec())),
+        };
+
+        // `detected_at` is kept from the first detection
+        sqlx::query!(
+            r#"
+            INSERT INTO commit_mismatch (submission_id, kind, onchain_block_hash, detected_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (submission_id) DO UPDATE
+            SET kind = EXCLUDED.kind, onchain_block_hash = EXCLUDED.onchain_block_hash
+            "#,
+            submission_id.as_i32(),
+            kind,
+            onchain_block_hash,
+            detected_at,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn _record_observed_commit(
+        &self,
+        commit: ObservedCommit,
+        foreign: bool,
+        observed_at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let log_index = i64::try_from(commit.log_index).map_err(|e| {
+            Error::Conversion(format!("log index {} too large: {e}", commit.log_index))
+        })?;
+        let l1_block = i64::try_from(commit.l1_block).map_err(|e| {
+            Error::Conversion(format!("l1 block {} too large: {e}", commit.l1_block))
+        })?;
+
+        // commits backfilled after (re)subscribing can also arrive over the new subscription, a
+        // commit recorded as unverified is completed
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO observed_commits
+                (tx_hash, log_index, commit_height, block_hash, l1_block, foreign_commit, observed_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (tx_hash, log_index) DO UPDATE
+                SET foreign_commit = EXCLUDED.foreign_commit
+                WHERE observed_commits.foreign_commit IS NULL
+            "#,
+            commit.tx_hash.as_slice(),
+            log_index,
+            i64::from(commit.commit_height),
+            commit.block_hash.as_slice(),
+            l1_block,
+            foreign,
+            observed_at,
+        )
+        .execute(&self.connection_pool)
+        .await?
+        .rows_affected()
+            > 0;
+
+        Ok(inserted)
+    }
+
+    pub(crate) async fn _record_unverified_commit(
+        &self,
+        commit: ObservedCommit,
+        observed_at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let log_index = i64::try_from(commit.log_index).map_err(|e| {
+            Error::Conversion(format!("log index {} too large: {e}", commit.log_index))
+        })?;
+        let l1_block = i64::try_from(commit.l1_block).map_err(|e| {
+            Error::Conversion(format!("l1 block {} too large: {e}", commit.l1_block))
+        })?;
+
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO observed_commits
+                (tx_hash, log_index, commit_height, block_hash, l1_block, foreign_commit, observed_at)
+            VALUES ($1, $2, $3, $4, $5, NULL, $6)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+            commit.tx_hash.as_slice(),
+            log_index,
+            i64::from(commit.commit_height),
+            commit.block_hash.as_slice(),
+            l1_block,
+            observed_at,
+        )
+        .execute(&self.connection_pool)
+        .await?
+        .rows_affected()
+            > 0;
+
+        Ok(inserted)
+    }
+
+    pub(crate) async fn _unverified_commits(&self, limit: usize) -> Result<Vec<ObservedCommit>> {
+        let limit = i64::try_from(limit)
+            .map_err(|e| Error::Conversion(format!("limit {limit} too large: {e}")))?;
+
+        sqlx::query!(
+            r#"
+            SELECT tx_hash, log_index, commit_height, block_hash, l1_block
+            FROM observed_commits
+            WHERE foreign_commit IS NULL
+            ORDER BY l1_block, log_index
+            LIMIT $1
+            "#,
+            limit,
+        )
+        .fetch_all(&self.connection_pool)
+        .await?
+        .into_iter()
+        .map(|row| {
+            let hash = |name: &str, bytes: Vec<u8>| {
+                <[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| {
+                    Error::Conversion(format!("Expected 32 bytes for `{name}`, got: {bytes:?}"))
+                })
+            };
+
+            Ok(ObservedCommit {
+                commit_height: conversion("commit height", row.commit_height)?,
+                block_hash: hash("block hash", row.block_hash)?,
+                tx_hash: hash("tx hash", row.tx_hash)?,
+                log_index: conversion("log index", row.log_index)?,
+                l1_block: conversion("l1 block", row.l1_block)?,
+            })
+        })
+        .collect()
+    }
+
+    pub(crate) async fn _latest_observed_commit_l1_block(&self) -> Result<Option<u64>> {
+        sqlx::query_scalar!("SELECT MAX(l1_block) FROM observed_commits")
+            .fetch_one(&self.connection_pool)
+            .await?
+            .map(|l1_block| conversion("l1 block", l1_block))
+            .transpose()
+    }
+
+    pub(crate) async fn _foreign_commit_observed(&self) -> Result<bool> {
+        Ok(sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM observed_commits WHERE foreign_commit AND acknowledged_at IS NULL) AS "observed!""#
+        )
+        .fetch_one(&self.connection_pool)
+        .await?)
+    }
+
+    pub(crate) async fn _acknowledge_foreign_commits(
+        &self,
+        acknowledged_at: DateTime<Utc>,
+    ) -> Result<u64> {
+        Ok(sqlx::query!(
+            "UPDATE observed_commits SET acknowledged_at = $1 WHERE foreign_commit AND acknowledged_at IS NULL",
+            acknowledged_at,
+        )
+        .execute(&self.connection_pool)
+        .await?
+        .rows_affected())
+    }
+
     pub(crate) async fn _get_pending_block_submission_txs(
         &self,
         submission_id: NonNegative<i32>,
@@ -223,6 +452,10 @@ impl Postgres {
             TransactionState::Finalized(date_time) => Some(*date_time),
             _ => None,
         };
+        let included = matches!(
+            state,
+            TransactionState::Finalized(_) | TransactionState::Failed
+        );
         let state = i16::from(L1TxState::from(&state));
         // update the transaction state
         let tx_row = sqlx::query_as!(
@@ -235,14 +468,27 @@ impl Postgres {
         .fetch_optional(&mut *transaction)
         .await?;
 
-        let submission_id = if let Some(row) = tx_row {
-            row.submission_id
-        } else {
+        let Some(tx_row) = tx_row else {
             let hash = hex::encode(tx_hash);
             return Err(Error::Database(format!(
                 "Cannot update tx state! Tx with hash: `{hash}` not found in DB."
             )));
         };
+        let submission_id = tx_row.submission_id;
+
+        // the txs this one replaced, or was replaced by, can no longer be included
+        if included {
+            sqlx::query!(
+                "UPDATE l1_transaction SET state = $1 WHERE submission_id = $2 AND nonce = $3 AND state = $4 AND hash <> $5",
+                i16::from(L1TxState::SqueezedOut),
+                submission_id,
+                tx_row.nonce,
+                i16::from(L1TxState::Pending),
+                tx_hash.as_slice(),
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
 
         // set submission to completed
         let submission_row = sqlx::query_as!(
@@ -679,6 +925,54 @@ impl Postgres {
             .transpose()
     }
 
+    pub(crate) async fn _latest_imported_height(&self) -> Result<Option<u32>> {
+        sqlx::query_scalar!("SELECT MAX(height) FROM fuel_blocks")
+            .fetch_one(&self.connection_pool)
+            .await?
+            .map(|height| conversion("block height", height))
+            .transpose()
+    }
+
+    pub(crate) async fn _latest_finalized_fragment_height(&self) -> Result<Option<u32>> {
+        sqlx::query_scalar!(
+            r#"SELECT MAX(b.end_height)
+            FROM bundles b
+            JOIN l1_fragments f ON f.bundle_id = b.id
+            JOIN l1_transaction_fragments tf ON tf.fragment_id = f.id
+            JOIN l1_blob_transaction t ON t.id = tf.transaction_id
+            WHERE t.state = $1"#,
+            i16::from(L1TxState::Finalized)
+        )
+        .fetch_one(&self.connection_pool)
+        .await?
+        .map(|height| conversion("block height", height))
+        .transpose()
+    }
+
+    /// Counts the fragments [`Postgres::_oldest_nonfinalized_fragments`] would still return.
+    pub(crate) async fn _unsubmitted_fragments(&self, starting_height: u32) -> Result<u64> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!"
+            FROM l1_fragments f
+            JOIN bundles b ON b.id = f.bundle_id
+            WHERE
+                b.end_height >= $2
+                AND NOT EXISTS (
+                    SELECT 1
+                    FROM l1_transaction_fragments tf
+                    JOIN l1_blob_transaction t ON t.id = tf.transaction_id
+                    WHERE tf.fragment_id = f.id
+                      AND t.state <> $1
+                )"#,
+            i16::from(L1TxState::Failed),
+            i64::from(starting_height),
+        )
+        .fetch_one(&self.connection_pool)
+        .await?;
+
+        conversion("fragment count", count)
+    }
+
     pub(crate) async fn _update_tx_state(
         &self,
         hash: [u8; 32],
@@ -908,28 +1202,63 @@ impl Postgres {
         .fetch_one(&mut *tx)
         .await?;
 
+        // The latest finalization among the transactions carrying the bundle
+        let finalized_at = if is_finalized {
+            sqlx::query_scalar!(
+                r#"
+                SELECT MAX(t.finalized_at)
+                FROM l1_fragments f
+                JOIN l1_transaction_fragments tf ON tf.fragment_id = f.id
+                JOIN l1_blob_transaction t ON t.id = tf.transaction_id
+                WHERE f.bundle_id = $1 AND t.state = $2
+                "#,
+                bundle_id,
+                i16::from(L1TxState::Finalized),
+            )
+            .fetch_one(&mut *tx)
+            .await?
+        } else {
+            None
+        };
+
         sqlx::query!(
             r#"
             INSERT INTO bundle_cost (
-                bundle_id, cost, size, da_block_height, is_finalized
+                bundle_id, cost, size, da_block_height, is_finalized, finalized_at
             ) VALUES (
-                $1, $2, $3, $4, $5
+                $1, $2, $3, $4, $5, $6
             )
             ON CONFLICT (bundle_id) DO UPDATE SET
                 cost = bundle_cost.cost + EXCLUDED.cost,
                 size = bundle_cost.size + EXCLUDED.size,
                 da_block_height = EXCLUDED.da_block_height,
-                is_finalized = EXCLUDED.is_finalized
+                is_finalized = EXCLUDED.is_finalized,
+                finalized_at = EXCLUDED.finalized_at
             "#,
             bundle_id,
             u128_to_bigdecimal(update.cost_contribution),
             i64::try_from(update.size_contribution).unwrap(),
             i64::try_from(update.latest_da_block_height).unwrap(),
             is_finalized,
+            finalized_at,
         )
         .execute(&mut *tx)
         .await?;
 
+        // Queued within the same transaction so that no finalized cost is missed by the cost feed
+        if let Some(finalized_at) = finalized_at.filter(|_| self.cost_feed_outbox) {
+            sqlx::query!(
+                r#"
+                INSERT INTO cost_feed_outbox (bundle_id, finalized_at) VALUES ($1, $2)
+                ON CONFLICT (bundle_id) DO NOTHING
+                "#,
+                bundle_id,
+                finalized_at,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
         Ok(())
     }
 
@@ -1152,15 +1481,20 @@ impl Postgres {
                 WHERE id NOT IN (SELECT id FROM deleted_fragments)
             ),
 
-            -- Delete unreferenced bundles and collect start and end heights
+            -- Delete unreferenced bundles and collect start and end heights. Bundles whose cost
+            -- wasn't delivered to the cost feed yet are kept until a later run after the delivery.
             deleted_bundles AS (
                 DELETE FROM bundles b
-                WHERE id IN (SELECT bundle_id FROM deleted_fragments)
-                  AND NOT EXISTS (
+                WHERE NOT EXISTS (
                       SELECT 1
                       FROM updated_fragments f
                       WHERE f.bundle_id = b.id
                   )
+                  AND NOT EXISTS (
+                      SELECT 1
+                      FROM cost_feed_outbox o
+                      WHERE o.bundle_id = b.id
+                  )
                 RETURNING start_height, end_height, id
             ),
 
@@ -1202,6 +1536,21 @@ impl Postgres {
                       FROM updated_transactions t
                       WHERE t.submission_id = bs.id
                   )
+            ),
+
+            -- Delete old lifecycle events
+            deleted_events AS (
+                DELETE FROM events
+                WHERE recorded_at < $1
+            ),
+
+            -- Delete old observed commits, keeping foreign and unverified ones and the latest one
+            -- the commit watcher resumes from
+            deleted_observed_commits AS (
+                DELETE FROM observed_commits
+                WHERE observed_at < $1
+                  AND NOT foreign_commit
+                  AND l1_block < (SELECT MAX(l1_block) FROM observed_commits)
             )
 
             SELECT
@@ -1250,6 +1599,723 @@ impl Postgres {
             contract_submissions: response.size_contract_submissions.unwrap_or_default() as u32,
         })
     }
+
+    pub(crate) async fn _acquire_or_renew_lease(
+        &self,
+        lease: &str,
+        candidate_id: &str,
+        lease_duration: Duration,
+    ) -> Result<bool> {
+        let holder = sqlx::query_scalar!(
+            r#"
+            INSERT INTO leader_leases (name, holder, expires_at)
+            VALUES ($1, $2, NOW() + make_interval(secs => $3))
+            ON CONFLICT (name) DO UPDATE
+            SET holder = EXCLUDED.holder, expires_at = EXCLUDED.expires_at
+            WHERE leader_leases.holder = EXCLUDED.holder OR leader_leases.expires_at < NOW()
+            RETURNING holder
+            "#,
+            lease,
+            candidate_id,
+            lease_duration.as_secs_f64(),
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?;
+
+        Ok(holder.is_some())
+    }
+
+    pub(crate) async fn _release_leases(&self, candidate_id: &str) -> Result<()> {
+        sqlx::query!("DELETE FROM leader_leases WHERE holder = $1", candidate_id)
+            .execute(&self.connection_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn _bundles(
+        &self,
+        filter: BundleFilter,
+        limit: usize,
+    ) -> Result<Vec<services::types::BundleOverview>> {
+        let (mut before_id, mut ids, mut containing_height) = (None, None, None);
+        let mut heights = HeightRange::default();
+        match filter {
+            BundleFilter::Page {
+                before_id: before,
+                heights: range,
+            } => {
+                before_id = before.map(i64::from);
+                heights = range;
+            }
+            BundleFilter::Ids(bundle_ids) => {
+                ids = Some(bundle_ids.into_iter().map(i64::from).collect::<Vec<_>>());
+            }
+            BundleFilter::Containing(height) => containing_height = Some(i64::from(height)),
+        }
+
+        sqlx::query_as!(
+            tables::BundleOverview,
+            r#"
+            SELECT
+                b.id,
+                b.start_height,
+                b.end_height,
+                COUNT(f.id) AS "num_fragments!",
+                COUNT(f.id) FILTER (
+                    WHERE EXISTS (
+                        SELECT 1
+                        FROM l1_transaction_fragments tf
+                        JOIN l1_blob_transaction t ON t.id = tf.transaction_id
+                        WHERE tf.fragment_id = f.id AND t.state = $4
+                    )
+                ) AS "num_finalized_fragments!",
+                COALESCE(SUM(f.total_bytes - f.unused_bytes), 0)::BIGINT AS "compressed_size!",
+                (
+                    SELECT
+                        CASE
+                            WHEN COUNT(*) = b.end_height - b.start_height + 1
+                            THEN SUM(octet_length(fb.data))
+                        END
+                    FROM fuel_blocks fb
+                    WHERE fb.height BETWEEN b.start_height AND b.end_height
+                )::BIGINT AS uncompressed_size
+            FROM
+                bundles b
+                LEFT JOIN l1_fragments f ON f.bundle_id = b.id
+            WHERE
+                ($1::BIGINT IS NULL OR b.id < $1)
+                AND ($2::BIGINT[] IS NULL OR b.id = ANY($2))
+                AND ($5::BIGINT IS NULL OR $5 BETWEEN b.start_height AND b.end_height)
+                AND ($6::BIGINT IS NULL OR b.end_height >= $6)
+                AND ($7::BIGINT IS NULL OR b.start_height <= $7)
+            GROUP BY
+                b.id
+            ORDER BY
+                b.id DESC
+            LIMIT $3
+            "#,
+            before_id,
+            ids.as_deref(),
+            limit as i64,
+            i16::from(L1TxState::Finalized),
+            containing_height,
+            heights.from.map(i64::from),
+            heights.to.map(i64::from),
+        )
+        .fetch_all(&self.connection_pool)
+        .await?
+        .into_iter()
+        .map(services::types::BundleOverview::try_from)
+        .collect()
+    }
+
+    pub(crate) async fn _bundle_fragments(
+        &self,
+        bundle_ids: &[u32],
+    ) -> Result<HashMap<u32, Vec<services::types::FragmentSubmissions>>> {
+        let bundle_ids = bundle_ids
+            .iter()
+            .map(|id| conversion::<i32, _>("bundle id", *id))
+            .collect::<Result<Vec<_>>>()?;
+
+        let rows = sqlx::query_as!(
+            tables::FragmentSubmission,
+            r#"
+            SELECT
+                f.bundle_id,
+                f.id AS fragment_id,
+                f.idx,
+                f.total_bytes,
+                f.unused_bytes,
+                t.id AS "tx_id?",
+                t.hash AS "hash?",
+                t.nonce AS "nonce?",
+                t.max_fee AS "max_fee?",
+                t.priority_fee AS "priority_fee?",
+                t.blob_fee AS "blob_fee?",
+                t.created_at AS "created_at?",
+                t.state AS "state?",
+                t.finalized_at
+            FROM
+                l1_fragments f
+                LEFT JOIN l1_transaction_fragments tf ON tf.fragment_id = f.id
+                LEFT JOIN l1_blob_transaction t ON t.id = tf.transaction_id
+            WHERE
+                f.bundle_id = ANY($1)
+            ORDER BY
+                f.bundle_id ASC,
+                f.idx ASC,
+                t.created_at ASC,
+                t.id ASC
+            "#,
+            &bundle_ids,
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+
+        let mut bundles: HashMap<u32, Vec<services::types::FragmentSubmissions>> = HashMap::new();
+        for row in rows {
+            let fragments = bundles
+                .entry(conversion("bundle id", row.bundle_id)?)
+                .or_default();
+            let fragment_id = conversion("fragment id", row.fragment_id)?;
+            let transaction = row
+                .transaction()
+                .map(services::types::L1Tx::try_from)
+                .transpose()?;
+
+            // rows are ordered by fragment, one per transaction that carried it
+            match fragments.last_mut() {
+                Some(fragment) if fragment.id == fragment_id => {
+                    fragment.transactions.extend(transaction);
+                }
+                _ => fragments.push(services::types::FragmentSubmissions {
+                    id: fragment_id,
+                    idx: conversion("fragment idx", row.idx)?,
+                    total_bytes: conversion("total_bytes", row.total_bytes)?,
+                    unused_bytes: conversion("unused_bytes", row.unused_bytes)?,
+                    transactions: transaction.into_iter().collect(),
+                }),
+            }
+        }
+
+        Ok(bundles)
+    }
+
+    pub(crate) async fn _bundle_ids_carried_by(
+        &self,
+        tx_hashes: &[[u8; 32]],
+    ) -> Result<HashMap<[u8; 32], Vec<u32>>> {
+        let tx_hashes = tx_hashes.iter().map(|hash| hash.to_vec()).collect_vec();
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT t.hash, f.bundle_id
+            FROM
+                l1_blob_transaction t
+                JOIN l1_transaction_fragments tf ON tf.transaction_id = t.id
+                JOIN l1_fragments f ON f.id = tf.fragment_id
+            WHERE
+                t.hash = ANY($1)
+            ORDER BY
+                f.bundle_id DESC
+            "#,
+            &tx_hashes,
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+
+        let mut bundle_ids: HashMap<[u8; 32], Vec<u32>> = HashMap::new();
+        for row in rows {
+            let hash = <[u8; 32]>::try_from(row.hash.as_slice()).map_err(|_| {
+                Error::Conversion(format!(
+                    "Expected 32 bytes for tx hash, got: {:?}",
+                    row.hash
+                ))
+            })?;
+            bundle_ids
+                .entry(hash)
+                .or_default()
+                .push(conversion("bundle id", row.bundle_id)?);
+        }
+
+        Ok(bundle_ids)
+    }
+
+    pub(crate) async fn _is_block_imported(&self, height: u32) -> Result<bool> {
+        let imported = sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM fuel_blocks WHERE height = $1) AS "imported!""#,
+            i64::from(height)
+        )
+        .fetch_one(&self.connection_pool)
+        .await?;
+
+        Ok(imported)
+    }
+
+    pub(crate) async fn _finalized_bundle_da_height(&self, bundle_id: u32) -> Result<Option<u64>> {
+        sqlx::query_scalar!(
+            "SELECT da_block_height FROM bundle_cost WHERE bundle_id = $1 AND is_finalized = TRUE",
+            i32::try_from(bundle_id).map_err(|_| {
+                Error::Conversion(format!("bundle id {bundle_id} exceeds the db range"))
+            })?
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?
+        .map(|height| conversion("da_block_height", height))
+        .transpose()
+    }
+
+    pub(crate) async fn _block_submission(&self, height: u32) -> Result<Option<BlockSubmission>> {
+        sqlx::query_as!(
+            tables::L1FuelBlockSubmission,
+            "SELECT * FROM l1_fuel_block_submission WHERE fuel_block_height = $1",
+            i64::from(height)
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?
+        .map(BlockSubmission::try_from)
+        .transpose()
+    }
+
+    pub(crate) async fn _block_submissions(
+        &self,
+        before_height: Option<u32>,
+        heights: HeightRange,
+        limit: usize,
+    ) -> Result<Vec<BlockSubmission>> {
+        sqlx::query_as!(
+            tables::L1FuelBlockSubmission,
+            r#"
+            SELECT *
+            FROM l1_fuel_block_submission
+            WHERE
+                ($1::BIGINT IS NULL OR fuel_block_height < $1)
+                AND ($2::BIGINT IS NULL OR fuel_block_height >= $2)
+                AND ($3::BIGINT IS NULL OR fuel_block_height <= $3)
+            ORDER BY fuel_block_height DESC
+            LIMIT $4
+            "#,
+            before_height.map(i64::from),
+            heights.from.map(i64::from),
+            heights.to.map(i64::from),
+            limit as i64
+        )
+        .fetch_all(&self.connection_pool)
+        .await?
+        .into_iter()
+        .map(BlockSubmission::try_from)
+        .collect()
+    }
+
+    pub(crate) async fn _blob_transactions(
+        &self,
+        before_id: Option<u64>,
+        heights: HeightRange,
+        limit: usize,
+    ) -> Result<Vec<services::types::L1Tx>> {
+        let before_id = before_id
+            .map(|id| conversion::<i64, _>("blob transaction id", id))
+            .transpose()?;
+
+        sqlx::query_as!(
+            tables::L1Tx,
+            r#"
+            SELECT t.*
+            FROM l1_blob_transaction t
+            WHERE
+                ($1::BIGINT IS NULL OR t.id < $1)
+                AND (
+                    ($2::BIGINT IS NULL AND $3::BIGINT IS NULL)
+                    OR EXISTS (
+                        SELECT 1
+                        FROM l1_transaction_fragments tf
+                        JOIN l1_fragments f ON f.id = tf.fragment_id
+                        JOIN bundles b ON b.id = f.bundle_id
+                        WHERE
+                            tf.transaction_id = t.id
+                            AND ($2::BIGINT IS NULL OR b.end_height >= $2)
+                            AND ($3::BIGINT IS NULL OR b.start_height <= $3)
+                    )
+                )
+            ORDER BY t.id DESC
+            LIMIT $4
+            "#,
+            before_id,
+            heights.from.map(i64::from),
+            heights.to.map(i64::from),
+            limit as i64
+        )
+        .fetch_all(&self.connection_pool)
+        .await?
+        .into_iter()
+        .map(TryFrom::try_from)
+        .collect()
+    }
+
+    pub(crate) async fn _finalized_bundle_cost(
+        &self,
+        bundle_id: u32,
+    ) -> Result<Option<BundleCost>> {
+        sqlx::query_as!(
+            tables::BundleCost,
+            r#"
+            SELECT
+                bc.bundle_id,
+                bc.cost,
+                bc.size,
+                bc.da_block_height,
+                bc.is_finalized,
+                b.start_height,
+                b.end_height
+            FROM
+                bundle_cost bc
+                JOIN bundles b ON bc.bundle_id = b.id
+            WHERE
+                bc.bundle_id = $1 AND bc.is_finalized = TRUE
+            "#,
+            conversion::<i32, _>("bundle id", bundle_id)?
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?
+        .map(BundleCost::try_from)
+        .transpose()
+    }
+
+    pub(crate) async fn _cost_aggregates(
+        &self,
+        filter: services::cost_reporter::port::CostFilter,
+        bucket: Option<services::cost_reporter::port::CostBucket>,
+    ) -> Result<Vec<services::cost_reporter::port::CostAggregate>> {
+        let from_da_height = filter
+            .from_da_height
+            .map(|height| conversion::<i64, _>("DA height", height))
+            .transpose()?;
+        let to_da_height = filter
+            .to_da_height
+            .map(|height| conversion::<i64, _>("DA height", height))
+            .transpose()?;
+
+        let rows = sqlx::query!(
+            r#"
+            WITH selected AS (
+                SELECT bc.bundle_id, bc.cost, bc.size, bc.finalized_at, b.start_height, b.end_height
+                FROM bundle_cost bc
+                JOIN bundles b ON b.id = bc.bundle_id
+                WHERE bc.is_finalized = TRUE
+                  AND ($1::TIMESTAMPTZ IS NULL OR bc.finalized_at >= $1)
+                  AND ($2::TIMESTAMPTZ IS NULL OR bc.finalized_at < $2)
+                  AND ($3::BIGINT IS NULL OR bc.da_block_height >= $3)
+                  AND ($4::BIGINT IS NULL OR bc.da_block_height <= $4)
+                  AND ($5::BIGINT IS NULL OR b.end_height >= $5)
+                  AND ($6::BIGINT IS NULL OR b.start_height <= $6)
+            ),
+            blob_space AS (
+                SELECT
+                    f.bundle_id,
+                    SUM(f.total_bytes) AS total_bytes,
+                    SUM(f.total_bytes - f.unused_bytes) AS used_bytes
+                FROM l1_fragments f
+                WHERE f.bundle_id IN (SELECT bundle_id FROM selected)
+                GROUP BY f.bundle_id
+            )
+            SELECT
+                date_trunc($7::TEXT, s.finalized_at, 'UTC') AS bucket_start,
+                COUNT(*) AS "bundles!",
+                SUM(s.cost) AS "total_cost!",
+                SUM(s.size)::BIGINT AS "bytes_posted!",
+                SUM(s.end_height - s.start_height + 1)::BIGINT AS "blocks_covered!",
+                COALESCE(SUM(bs.total_bytes), 0)::BIGINT AS "blob_bytes!",
+                COALESCE(SUM(bs.used_bytes), 0)::BIGINT AS "used_blob_bytes!"
+            FROM selected s
+            LEFT JOIN blob_space bs ON bs.bundle_id = s.bundle_id
+            GROUP BY 1
+            ORDER BY 1 NULLS FIRST
+            "#,
+            filter.finalized_from,
+            filter.finalized_before,
+            from_da_height,
+            to_da_height,
+            filter.l2_heights.from.map(i64::from),
+            filter.l2_heights.to.map(i64::from),
+            bucket.map(|bucket| bucket.unit()),
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(services::cost_reporter::port::CostAggregate {
+                    bucket_start: row.bucket_start,
+                    bundles: conversion("bundle count", row.bundles)?,
+                    total_cost: tables::bigdecimal_to_u128(row.total_cost)?,
+                    bytes_posted: conversion("bytes posted", row.bytes_posted)?,
+                    blocks_covered: conversion("blocks covered", row.blocks_covered)?,
+                    blob_bytes: conversion("blob bytes", row.blob_bytes)?,
+                    used_blob_bytes: conversion("used blob bytes", row.used_blob_bytes)?,
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) async fn _undelivered_costs(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<services::cost_feed::port::UndeliveredCost>> {
+        let limit = conversion::<i64, _>("limit", limit)?;
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                o.bundle_id,
+                o.finalized_at,
+                o.attempts,
+                bc.cost,
+                bc.size,
+                bc.da_block_height,
+                bc.is_finalized,
+                b.start_height,
+                b.end_height
+            FROM
+                cost_feed_outbox o
+                JOIN bundle_cost bc ON bc.bundle_id = o.bundle_id
+                JOIN bundles b ON b.id = o.bundle_id
+            ORDER BY o.bundle_id
+            LIMIT $1
+            "#,
+            limit
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let cost = BundleCost::try_from(tables::BundleCost {
+                    bundle_id: row.bundle_id,
+                    cost: row.cost,
+                    size: row.size,
+                    da_block_height: row.da_block_height,
+                    start_height: row.start_height,
+                    end_height: row.end_height,
+                    is_finalized: row.is_finalized,
+                })?;
+
+                Ok(services::cost_feed::port::UndeliveredCost {
+                    cost,
+                    finalized_at: row.finalized_at,
+                    attempts: conversion("delivery attempts", row.attempts)?,
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) async fn _undelivered_cost_backlog(
+        &self,
+    ) -> Result<services::cost_feed::port::CostBacklog> {
+        let row = sqlx::query!(
+            r#"SELECT COUNT(*) AS "count!", MIN(finalized_at) AS oldest_finalized_at FROM cost_feed_outbox"#
+        )
+        .fetch_one(&self.connection_pool)
+        .await?;
+
+        Ok(services::cost_feed::port::CostBacklog {
+            count: conversion("undelivered costs", row.count)?,
+            oldest_finalized_at: row.oldest_finalized_at,
+        })
+    }
+
+    pub(crate) async fn _mark_cost_delivered(&self, bundle_id: u64) -> Result<()> {
+        sqlx::query!(
+            "DELETE FROM cost_feed_outbox WHERE bundle_id = $1",
+            conversion::<i32, _>("bundle id", bundle_id)?
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn _record_cost_delivery_failure(
+        &self,
+        bundle_id: u64,
+        error: String,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE cost_feed_outbox SET attempts = attempts + 1, last_error = $2 WHERE bundle_id = $1",
+            conversion::<i32, _>("bundle id", bundle_id)?,
+            error
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn _block_submission_txs(
+        &self,
+        submission_id: NonNegative<i32>,
+    ) -> Result<Vec<BlockSubmissionTx>> {
+        sqlx::query_as!(
+            tables::L1FuelBlockSubmissionTx,
+            "SELECT * FROM l1_transaction WHERE submission_id = $1 ORDER BY created_at, id",
+            submission_id.as_i32()
+        )
+        .fetch_all(&self.connection_pool)
+        .await?
+        .into_iter()
+        .map(BlockSubmissionTx::try_from)
+        .collect()
+    }
+
+    pub(crate) async fn _record_admin_action(
+        &self,
+        entry: services::admin::port::AuditEntry,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO admin_audit_log (action, runner, requested_by, performed_at) VALUES ($1, $2, $3, $4)",
+            entry.action,
+            entry.runner,
+            entry.requested_by,
+            entry.performed_at,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn _pause_runner(
+        &self,
+        runner: String,
+        paused_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO paused_runners (runner, paused_at) VALUES ($1, $2) ON CONFLICT (runner) DO NOTHING",
+            runner,
+            paused_at,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn _resume_runner(&self, runner: String) -> Result<()> {
+        sqlx::query!("DELETE FROM paused_runners WHERE runner = $1", runner)
+            .execute(&self.connection_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn _paused_runners(&self) -> Result<Vec<String>> {
+        Ok(
+            sqlx::query_scalar!("SELECT runner FROM paused_runners ORDER BY runner")
+                .fetch_all(&self.connection_pool)
+                .await?,
+        )
+    }
+
+    pub(crate) async fn _record_events(
+        &self,
+        events: NonEmpty<services::events::service::Event>,
+        recorded_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let (kinds, payloads): (Vec<_>, Vec<_>) = events
+            .into_iter()
+            .map(|event| {
+                let payload = serde_json::to_string(&event).map_err(|e| {
+                    Error::Conversion(format!("could not serialize event {event:?}: {e}"))
+                })?;
+                Ok((event.name().to_string(), payload))
+            })
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+
+        let mut tx = self.connection_pool.begin().await?;
+
+        // Serializes the writers so that ids become visible in increasing order, otherwise a
+        // reader could see an id before a lower one committed concurrently and skip the latter.
+        // Readers aren't blocked by this mode.
+        sqlx::query("LOCK TABLE events IN SHARE ROW EXCLUSIVE MODE")
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO events (kind, payload, recorded_at)
+            SELECT kind, payload::JSONB, $3
+            FROM UNNEST($1::TEXT[], $2::TEXT[]) WITH ORDINALITY AS e(kind, payload, position)
+            ORDER BY position
+            "#,
+            &kinds,
+            &payloads,
+            recorded_at,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn _events_after(
+        &self,
+        last_id: u64,
+        limit: usize,
+    ) -> Result<Vec<services::events::service::RecordedEvent>> {
+        let last_id = conversion::<i64, _>("event id", last_id)?;
+        let limit = conversion::<i64, _>("limit", limit)?;
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, payload::TEXT AS "payload!", recorded_at
+            FROM events
+            WHERE id > $1
+            ORDER BY id
+            LIMIT $2
+            "#,
+            last_id,
+            limit,
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let event = serde_json::from_str(&row.payload).map_err(|e| {
+                    Error::Conversion(format!("invalid payload of event {}: {e}", row.id))
+                })?;
+
+                Ok(services::events::service::RecordedEvent {
+                    id: conversion("event id", row.id)?,
+                    recorded_at: row.recorded_at,
+                    event,
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) async fn _latest_event_id(&self) -> Result<Option<u64>> {
+        sqlx::query_scalar!("SELECT MAX(id) FROM events")
+            .fetch_one(&self.connection_pool)
+            .await?
+            .map(|id| conversion("event id", id))
+            .transpose()
+    }
+
+    pub(crate) async fn _ping(&self) -> Result<()> {
+        sqlx::query("SELECT 1")
+            .execute(&self.connection_pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Selects the bundles returned by [`Postgres::_bundles`].
+pub(crate) enum BundleFilter {
+    /// Bundles with an id lower than `before_id`, if any, whose block range overlaps `heights`.
+    Page {
+        before_id: Option<u32>,
+        heights: HeightRange,
+    },
+    Ids(Vec<u32>),
+    /// Bundles whose block range includes the height.
+    Containing(u32),
+}
+
+fn conversion<T, V>(name: &str, value: V) -> Result<T>
+where
+    V: TryInto<T> + std::fmt::Display + Copy,
+    V::Error: std::fmt::Display,
+{
+    value
+        .try_into()
+        .map_err(|e| Error::Conversion(format!("Invalid db `{name}` ({value}). Reason: {e}")))
 }
 
 async fn take_blocks_until_limit(
@@ -1,5 +1,6 @@
 use std::{
     borrow::Cow,
+    collections::HashMap,
     ops::RangeInclusive,
     sync::{Arc, Weak},
 };
@@ -9,9 +10,9 @@ use services::{
     block_bundler::{self, port::UnbundledBlocks},
     block_committer, block_importer,
     types::{
-        BlockSubmission, BlockSubmissionTx, BundleCost, CompressedFuelBlock, DateTime, Fragment,
-        L1Tx, NonEmpty, NonNegative, TransactionCostUpdate, TransactionState, Utc,
-        storage::BundleFragment,
+        BlockSubmission, BlockSubmissionTx, BundleCost, CommitMismatch, CompressedFuelBlock,
+        DateTime, Fragment, HeightRange, L1Tx, NonEmpty, NonNegative, TransactionCostUpdate,
+        TransactionState, Utc, storage::BundleFragment,
     },
 };
 use sqlx::Executor;
@@ -166,6 +167,11 @@ impl DbWithProcess {
             pub fn port(&self) -> u16;
         }
     }
+
+    pub fn with_cost_feed_outbox(mut self) -> Self {
+        self.db = self.db.with_cost_feed_outbox();
+        self
+    }
 }
 
 impl services::state_pruner::port::Storage for DbWithProcess {
@@ -182,6 +188,196 @@ impl services::state_pruner::port::Storage for DbWithProcess {
     }
 }
 
+impl services::leader_election::port::Storage for DbWithProcess {
+    delegate! {
+        to self.db {
+            async fn acquire_or_renew_lease(
+                &self,
+                lease: &str,
+                candidate_id: &str,
+                lease_duration: std::time::Duration,
+            ) -> services::Result<bool>;
+            async fn release_leases(&self, candidate_id: &str) -> services::Result<()>;
+        }
+    }
+}
+
+impl services::bundle_reporter::port::Storage for DbWithProcess {
+    delegate! {
+        to self.db {
+            async fn bundles(
+                &self,
+                before_id: Option<u32>,
+                heights: HeightRange,
+                limit: usize,
+            ) -> services::Result<Vec<services::types::BundleOverview>>;
+            async fn bundles_by_id(
+                &self,
+                ids: Vec<u32>,
+            ) -> services::Result<Vec<services::types::BundleOverview>>;
+            async fn fragments_of_bundles(
+                &self,
+                ids: Vec<u32>,
+            ) -> services::Result<HashMap<u32, Vec<services::types::FragmentSubmissions>>>;
+            async fn bundle_ids_carried_by(
+                &self,
+                tx_hashes: Vec<[u8; 32]>,
+            ) -> services::Result<HashMap<[u8; 32], Vec<u32>>>;
+            async fn blob_transactions(
+                &self,
+                before_id: Option<u64>,
+                heights: HeightRange,
+                limit: usize,
+            ) -> services::Result<Vec<L1Tx>>;
+        }
+    }
+}
+
+impl services::block_reporter::port::Storage for DbWithProcess {
+    delegate! {
+        to self.db {
+            async fn is_block_imported(&self, height: u32) -> services::Result<bool>;
+            async fn bundle_containing(
+                &self,
+                height: u32,
+            ) -> services::Result<Option<services::types::BundleOverview>>;
+            async fn bundle_fragments(
+                &self,
+                bundle_id: u32,
+            ) -> services::Result<Vec<services::types::FragmentSubmissions>>;
+            async fn finalized_bundle_da_height(
+                &self,
+                bundle_id: u32,
+            ) -> services::Result<Option<u64>>;
+            async fn block_submission(
+                &self,
+                height: u32,
+            ) -> services::Result<Option<BlockSubmission>>;
+            async fn block_submissions(
+                &self,
+                before_height: Option<u32>,
+                heights: HeightRange,
+                limit: usize,
+            ) -> services::Result<Vec<BlockSubmission>>;
+            async fn block_submission_txs(
+                &self,
+                submission_id: NonNegative<i32>,
+            ) -> services::Result<Vec<BlockSubmissionTx>>;
+        }
+    }
+}
+
+impl services::admin::port::Storage for DbWithProcess {
+    delegate! {
+        to self.db {
+            async fn record_admin_action(
+                &self,
+                entry: services::admin::port::AuditEntry,
+            ) -> services::Result<()>;
+            async fn acknowledge_foreign_commits(
+                &self,
+                acknowledged_at: DateTime<Utc>,
+            ) -> services::Result<u64>;
+            async fn pause_runner(
+                &self,
+                runner: String,
+                paused_at: DateTime<Utc>,
+            ) -> services::Result<()>;
+            async fn resume_runner(&self, runner: String) -> services::Result<()>;
+            async fn paused_runners(&self) -> services::Result<Vec<String>>;
+        }
+    }
+}
+
+impl services::events::port::Storage for DbWithProcess {
+    delegate! {
+        to self.db {
+            async fn record_events(
+                &self,
+                events: NonEmpty<services::events::service::Event>,
+                recorded_at: DateTime<Utc>,
+            ) -> services::Result<()>;
+            async fn events_after(
+                &self,
+                last_id: u64,
+                limit: usize,
+            ) -> services::Result<Vec<services::events::service::RecordedEvent>>;
+            async fn latest_event_id(&self) -> services::Result<Option<u64>>;
+        }
+    }
+}
+
+impl services::cost_feed::port::Storage for DbWithProcess {
+    delegate! {
+        to self.db {
+            async fn undelivered_costs(
+                &self,
+                limit: usize,
+            ) -> services::Result<Vec<services::cost_feed::port::UndeliveredCost>>;
+            async fn undelivered_cost_backlog(
+                &self,
+            ) -> services::Result<services::cost_feed::port::CostBacklog>;
+            async fn mark_cost_delivered(&self, bundle_id: u64) -> services::Result<()>;
+            async fn record_cost_delivery_failure(
+                &self,
+                bundle_id: u64,
+                error: String,
+            ) -> services::Result<()>;
+        }
+    }
+}
+
+impl services::commit_watcher::port::Storage for DbWithProcess {
+    async fn record_observed_commit(
+        &self,
+        commit: services::commit_watcher::port::ObservedCommit,
+        foreign: bool,
+        observed_at: DateTime<Utc>,
+    ) -> services::Result<bool> {
+        self.db
+            ._record_observed_commit(commit, foreign, observed_at)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn record_unverified_commit(
+        &self,
+        commit: services::commit_watcher::port::ObservedCommit,
+        observed_at: DateTime<Utc>,
+    ) -> services::Result<bool> {
+        self.db
+            ._record_unverified_commit(commit, observed_at)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn unverified_commits(
+        &self,
+        limit: usize,
+    ) -> services::Result<Vec<services::commit_watcher::port::ObservedCommit>> {
+        self.db._unverified_commits(limit).await.map_err(Into::into)
+    }
+
+    async fn latest_observed_commit_l1_block(&self) -> services::Result<Option<u64>> {
+        self.db
+            ._latest_observed_commit_l1_block()
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn foreign_commit_observed(&self) -> services::Result<bool> {
+        self.db._foreign_commit_observed().await.map_err(Into::into)
+    }
+}
+
+impl services::health_reporter::port::Storage for DbWithProcess {
+    delegate! {
+        to self.db {
+            async fn ping(&self) -> services::Result<()>;
+        }
+    }
+}
+
 impl services::state_listener::port::Storage for DbWithProcess {
     async fn get_non_finalized_txs(&self) -> services::Result<Vec<L1Tx>> {
         self.db._get_non_finalized_txs().await.map_err(Into::into)
@@ -270,6 +466,16 @@ impl block_committer::port::Storage for DbWithProcess {
             .await
             .map_err(Into::into)
     }
+    async fn record_block_submission_tx(
+        &self,
+        submission_tx: BlockSubmissionTx,
+        created_at: DateTime<Utc>,
+    ) -> services::Result<()> {
+        self.db
+            ._record_block_submission_tx(submission_tx, created_at)
+            .await
+            .map_err(Into::into)
+    }
     async fn get_pending_block_submission_txs(
         &self,
         submission_id: NonNegative<i32>,
@@ -295,6 +501,26 @@ impl block_committer::port::Storage for DbWithProcess {
             .await
             .map_err(Into::into)
     }
+    async fn recent_block_submissions(
+        &self,
+        limit: usize,
+    ) -> services::Result<Vec<BlockSubmission>> {
+        self.db
+            ._block_submissions(None, HeightRange::default(), limit)
+            .await
+            .map_err(Into::into)
+    }
+    async fn set_commit_mismatch(
+        &self,
+        submission_id: NonNegative<i32>,
+        mismatch: Option<CommitMismatch>,
+        detected_at: DateTime<Utc>,
+    ) -> services::Result<()> {
+        self.db
+            ._set_commit_mismatch(submission_id, mismatch, detected_at)
+            .await
+            .map_err(Into::into)
+    }
 }
 
 impl services::state_committer::port::Storage for DbWithProcess {
@@ -353,6 +579,17 @@ impl services::status_reporter::port::Storage for DbWithProcess {
             .await
             .map_err(Into::into)
     }
+
+    delegate! {
+        to self.db {
+            async fn latest_imported_height(&self) -> services::Result<Option<u32>>;
+            async fn latest_bundled_height(&self) -> services::Result<Option<u32>>;
+            async fn latest_finalized_fragment_height(&self) -> services::Result<Option<u32>>;
+            async fn unbundled_blocks(&self, starting_height: u32) -> services::Result<u64>;
+            async fn unsubmitted_fragments(&self, starting_height: u32) -> services::Result<u64>;
+            async fn latest_pending_blob_tx(&self) -> services::Result<Option<L1Tx>>;
+        }
+    }
 }
 
 impl services::cost_reporter::port::Storage for DbWithProcess {
@@ -370,4 +607,22 @@ impl services::cost_reporter::port::Storage for DbWithProcess {
     async fn get_latest_costs(&self, limit: usize) -> services::Result<Vec<BundleCost>> {
         self.db._get_latest_costs(limit).await.map_err(Into::into)
     }
+
+    async fn finalized_bundle_cost(&self, bundle_id: u32) -> services::Result<Option<BundleCost>> {
+        self.db
+            ._finalized_bundle_cost(bundle_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn cost_aggregates(
+        &self,
+        filter: services::cost_reporter::port::CostFilter,
+        bucket: Option<services::cost_reporter::port::CostBucket>,
+    ) -> services::Result<Vec<services::cost_reporter::port::CostAggregate>> {
+        self.db
+            ._cost_aggregates(filter, bucket)
+            .await
+            .map_err(Into::into)
+    }
 }
@@ -0,0 +1,134 @@
+#![deny(unused_crate_dependencies)]
+
+use std::time::Duration;
+
+use services::{Error, Result, types::BundleCost};
+pub use url::Url;
+
+/// Delivers bundle costs by POSTing them as JSON to a webhook.
+#[derive(Clone)]
+pub struct HttpSink {
+    client: reqwest::Client,
+    url: Url,
+    auth_token: Option<String>,
+}
+
+impl HttpSink {
+    /// `auth_token`, if given, is sent as a bearer token.
+    pub fn new(url: Url, auth_token: Option<String>, request_timeout: Duration) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(request_timeout)
+            .build()
+            .map_err(|e| Error::Other(format!("could not create the webhook client: {e}")))?;
+
+        Ok(Self {
+            client,
+            url,
+            auth_token,
+        })
+    }
+}
+
+impl services::cost_feed::port::Sink for HttpSink {
+    #[tracing::instrument(skip_all, fields(bundle_id = cost.id))]
+    async fn deliver(&self, cost: &BundleCost) -> Result<()> {
+        let mut request = self.client.post(self.url.clone()).json(cost);
+        if let Some(token) = &self.auth_token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Network(format!("could not reach the webhook: {e}")))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::Network(format!("webhook responded with {status}")));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use services::cost_feed::port::Sink;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    fn cost() -> BundleCost {
+        BundleCost {
+            id: 7,
+            cost: 1000,
+            size: 100,
+            da_block_height: 42,
+            start_height: 10,
+            end_height: 19,
+        }
+    }
+
+    /// Answers a single request with `status` and returns the raw request.
+    async fn webhook(status: &'static str) -> (Url, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}/costs", listener.local_addr().unwrap())).unwrap();
+
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = vec![];
+            let mut buf = [0; 1024];
+            // the body is the last thing sent
+            while !request.ends_with(b"}") {
+                let read = stream.read(&mut buf).await.unwrap();
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..read]);
+            }
+            let response = format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\n\r\n");
+            stream.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request).to_string()
+        });
+
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn posts_the_cost_as_json_with_the_token() {
+        // given
+        let (url, request) = webhook("200 OK").await;
+        let sink = HttpSink::new(url, Some("secret".to_string()), Duration::from_secs(5)).unwrap();
+
+        // when
+        sink.deliver(&cost()).await.unwrap();
+
+        // then
+        let request = request.await.unwrap().to_lowercase();
+        assert!(request.starts_with("post /costs"), "{request}");
+        assert!(
+            request.contains("authorization: bearer secret"),
+            "{request}"
+        );
+        assert!(request.contains(r#""id":7"#), "{request}");
+    }
+
+    #[tokio::test]
+    async fn unsuccessful_status_fails_the_delivery() {
+        // given
+        let (url, _request) = webhook("503 Service Unavailable").await;
+        let sink = HttpSink::new(url, None, Duration::from_secs(5)).unwrap();
+
+        // when
+        let result = sink.deliver(&cost()).await;
+
+        // then
+        let Err(Error::Network(msg)) = result else {
+            panic!("expected a network error, got {result:?}");
+        };
+        assert!(msg.contains("503"), "{msg}");
+    }
+}
@@ -0,0 +1,423 @@
+pub mod service {
+    use tracing::info;
+
+    use crate::{
+        Error, Result, Runner,
+        admin::port::AuditEntry,
+        runner_control::{self, RunnerControl, RunnerControls},
+        state_committer::service::ForcedActions,
+    };
+
+    const STATE_COMMITTER: &str = "State Committer";
+    const STATE_PRUNER: &str = "State Pruner";
+    const COMMIT_WATCHER: &str = "Commit Watcher";
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum AdminAction {
+        PauseRunner(String),
+        ResumeRunner(String),
+        /// Makes the state committer submit without waiting for fragments to accumulate or for
+        /// acceptable fees.
+        ForceSubmission,
+        /// Makes the state committer replace its pending tx right away.
+        ForceGasBump,
+        TriggerPruning,
+        /// Makes the foreign commits observed so far stop failing the health checks.
+        AcknowledgeForeignCommits,
+    }
+
+    impl AdminAction {
+        /// Name under which the action is recorded in the audit log.
+        pub fn name(&self) -> &'static str {
+            match self {
+                Self::PauseRunner(_) => "pause_runner",
+                Self::ResumeRunner(_) => "resume_runner",
+                Self::ForceSubmission => "force_submission",
+                Self::ForceGasBump => "force_gas_bump",
+                Self::TriggerPruning => "trigger_pruning",
+                Self::AcknowledgeForeignCommits => "acknowledge_foreign_commits",
+            }
+        }
+
+        fn runner(&self) -> &str {
+            match self {
+                Self::PauseRunner(runner) | Self::ResumeRunner(runner) => runner,
+                Self::ForceSubmission | Self::ForceGasBump => STATE_COMMITTER,
+                Self::TriggerPruning => STATE_PRUNER,
+                Self::AcknowledgeForeignCommits => COMMIT_WATCHER,
+            }
+        }
+    }
+
+    /// Lets operators intervene without restarting the committer. Every action is recorded in
+    /// the audit log before it is applied, and refused if it cannot be recorded. Pauses are stored
+    /// and applied by every instance through [`PauseSync`]. The other actions only affect the
+    /// instance they are sent to, and are refused if the runner is leader-gated and this instance
+    /// is on standby.
+    pub struct Admin<Db, Clock> {
+        storage: Db,
+        clock: Clock,
+        runners: RunnerControls,
+        forced_actions: ForcedActions,
+    }
+
+    impl<Db, Clock> Admin<Db, Clock> {
+        pub fn new(
+            storage: Db,
+            clock: Clock,
+            runners: RunnerControls,
+            forced_actions: ForcedActions,
+        ) -> Self {
+            Self {
+                storage,
+                clock,
+                runners,
+                forced_actions,
+            }
+        }
+    }
+
+    impl<Db, Clock> Admin<Db, Clock>
+    where
+        Db: crate::admin::port::Storage,
+        Clock: crate::admin::port::Clock,
+    {
+        /// `requested_by` identifies the origin of the request in the audit log, e.g. its IP.
+        pub async fn perform(
+            &self,
+            action: AdminAction,
+            requested_by: Option<String>,
+        ) -> Result<()> {
+            let runner = self.runner(action.runner())?;
+
+            let applies_to_all_instances = matches!(
+                action,
+                AdminAction::PauseRunner(_) | AdminAction::ResumeRunner(_)
+            );
+            if !applies_to_all_instances && !runner.is_active() {
+                return Err(Error::NotLeader);
+            }
+
+            self.storage
+                .record_admin_action(AuditEntry {
+                    action: action.name().to_string(),
+                    runner: action.runner().to_string(),
+                    requested_by,
+                    performed_at: self.clock.now(),
+                })
+                .await?;
+
+            info!("admin action {} on {}", action.name(), action.runner());
+
+            match action {
+                AdminAction::PauseRunner(name) => {
+                    self.storage
+                        .pause_runner(runner_control::slug(&name), self.clock.now())
+                        .await?;
+                    runner.pause();
+                }
+                AdminAction::ResumeRunner(name) => {
+                    self.storage
+                        .resume_runner(runner_control::slug(&name))
+                        .await?;
+                    runner.resume();
+                }
+                AdminAction::ForceSubmission => {
+                    self.forced_actions.force_submission();
+                    runner.wake();
+                }
+                AdminAction::ForceGasBump => {
+                    self.forced_actions.force_gas_bump();
+                    runner.wake();
+                }
+                AdminAction::TriggerPruning => runner.wake(),
+                AdminAction::AcknowledgeForeignCommits => {
+                    let acknowledged = self
+                        .storage
+                        .acknowledge_foreign_commits(self.clock.now())
+                        .await?;
+                    info!("acknowledged {acknowledged} foreign commit(s)");
+                    runner.wake();
+                }
+            }
+
+            Ok(())
+        }
+
+        fn runner(&self, name: &str) -> Result<RunnerControl> {
+            self.runners.get(name).ok_or_else(|| {
+                Error::Other(format!(
+                    "runner `{name}` is not running in this instance, available: {}",
+                    self.runners.names().join(", ")
+                ))
+            })
+        }
+    }
+
+    /// Applies the pauses stored by any instance to the runners of this one, so that they outlast
+    /// restarts and failovers.
+    pub struct PauseSync<Db> {
+        storage: Db,
+        runners: RunnerControls,
+    }
+
+    impl<Db> PauseSync<Db> {
+        pub fn new(storage: Db, runners: RunnerControls) -> Self {
+            Self { storage, runners }
+        }
+    }
+
+    impl<Db> Runner for PauseSync<Db>
+    where
+        Db: crate::admin::port::Storage,
+    {
+        async fn run(&mut self) -> Result<()> {
+            let paused = self.storage.paused_runners().await?;
+            self.runners.set_paused(paused);
+
+            Ok(())
+        }
+    }
+}
+
+pub mod port {
+    use crate::{
+        Result,
+        types::{DateTime, Utc},
+    };
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct AuditEntry {
+        pub action: String,
+        pub runner: String,
+        pub requested_by: Option<String>,
+        pub performed_at: DateTime<Utc>,
+    }
+
+    #[allow(async_fn_in_trait)]
+    #[trait_variant::make(Send)]
+    #[cfg_attr(feature = "test-helpers", mockall::automock)]
+    pub trait Storage: Send + Sync {
+        async fn record_admin_action(&self, entry: AuditEntry) -> Result<()>;
+        /// Pausing a paused runner again must not fail.
+        async fn pause_runner(&self, runner: String, paused_at: DateTime<Utc>) -> Result<()>;
+        async fn resume_runner(&self, runner: String) -> Result<()>;
+        /// Slugs of the paused runners.
+        async fn paused_runners(&self) -> Result<Vec<String>>;
+        /// Clears the foreign flag of the observed commits, returning how many were acknowledged.
+        async fn acknowledge_foreign_commits(&self, acknowledged_at: DateTime<Utc>) -> Result<u64>;
+    }
+
+    pub trait Clock {
+        fn now(&self) -> DateTime<Utc>;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        port::{Clock, MockStorage},
+        service::{Admin, AdminAction, PauseSync},
+    };
+    use crate::{
+        Error, Runner,
+        leader_election::{
+            self,
+            service::{LeaderElection, Leadership},
+        },
+        runner_control::RunnerControls,
+        state_committer::service::ForcedActions,
+        types::{DateTime, Utc},
+    };
+
+    struct TestClock;
+
+    impl Clock for TestClock {
+        fn now(&self) -> DateTime<Utc> {
+            DateTime::UNIX_EPOCH
+        }
+    }
+
+    fn recording_storage(times: usize) -> MockStorage {
+        let mut storage = MockStorage::new();
+        storage
+            .expect_record_admin_action()
+            .times(times)
+            .returning(|_| Box::pin(async { Ok(()) }));
+        storage
+    }
+
+    fn standby() -> Leadership {
+        LeaderElection::new(
+            leader_election::port::MockStorage::new(),
+            "b".into(),
+            Duration::from_secs(30),
+        )
+        .leadership("state_committer")
+    }
+
+    #[tokio::test]
+    async fn pauses_and_resumes_runners() {
+        // given
+        let runners = RunnerControls::new();
+        let committer = runners.register("Block Committer");
+        let mut storage = recording_storage(2);
+        storage
+            .expect_pause_runner()
+            .withf(|runner, _| runner == "block-committer")
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(()) }));
+        storage
+            .expect_resume_runner()
+            .withf(|runner| runner == "block-committer")
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+        let admin = Admin::new(storage, TestClock, runners, ForcedActions::new());
+
+        // when
+        admin
+            .perform(AdminAction::PauseRunner("block-committer".into()), None)
+            .await
+            .unwrap();
+        let paused = committer.is_paused();
+        admin
+            .perform(AdminAction::ResumeRunner("block-committer".into()), None)
+            .await
+            .unwrap();
+
+        // then
+        assert!(paused);
+        assert!(!committer.is_paused());
+    }
+
+    #[tokio::test]
+    async fn refuses_unknown_runners() {
+        // given
+        let admin = Admin::new(
+            recording_storage(0),
+            TestClock,
+            RunnerControls::new(),
+            ForcedActions::new(),
+        );
+
+        // when
+        let result = admin.perform(AdminAction::TriggerPruning, None).await;
+
+        // then
+        assert!(matches!(result, Err(Error::Other(_))));
+    }
+
+    #[tokio::test]
+    async fn refuses_actions_that_cannot_be_audited() {
+        // given
+        let runners = RunnerControls::new();
+        let committer = runners.register("Block Committer");
+        let mut storage = MockStorage::new();
+        storage
+            .expect_record_admin_action()
+            .returning(|_| Box::pin(async { Err(Error::Storage("db down".into())) }));
+        let admin = Admin::new(storage, TestClock, runners, ForcedActions::new());
+
+        // when
+        let result = admin
+            .perform(AdminAction::PauseRunner("block-committer".into()), None)
+            .await;
+
+        // then
+        assert!(result.is_err());
+        assert!(!committer.is_paused());
+    }
+
+    #[tokio::test]
+    async fn standbys_refuse_actions_on_leader_gated_runners() {
+        // given
+        let runners = RunnerControls::new();
+        let committer = runners.register_for_leader("State Committer", standby());
+        let forced_actions = ForcedActions::new();
+        let admin = Admin::new(
+            recording_storage(0),
+            TestClock,
+            runners,
+            forced_actions.clone(),
+        );
+
+        // when
+        let result = admin.perform(AdminAction::ForceSubmission, None).await;
+
+        // then
+        assert!(matches!(result, Err(Error::NotLeader)));
+        assert!(!forced_actions.take_submission());
+        assert!(!committer.is_paused());
+    }
+
+    #[tokio::test]
+    async fn standbys_pause_runners() {
+        // given
+        let runners = RunnerControls::new();
+        let listener = runners.register("State Listener");
+        let committer = runners.register_for_leader("State Committer", standby());
+        let mut storage = recording_storage(2);
+        storage
+            .expect_pause_runner()
+            .times(2)
+            .returning(|_, _| Box::pin(async { Ok(()) }));
+        let admin = Admin::new(storage, TestClock, runners, ForcedActions::new());
+
+        // when
+        for runner in ["state-listener", "state-committer"] {
+            admin
+                .perform(AdminAction::PauseRunner(runner.into()), None)
+                .await
+                .unwrap();
+        }
+
+        // then
+        assert!(listener.is_paused());
+        assert!(committer.is_paused());
+    }
+
+    #[tokio::test]
+    async fn stored_pauses_are_applied() {
+        // given
+        let runners = RunnerControls::new();
+        let pruner = runners.register("State Pruner");
+        let mut storage = MockStorage::new();
+        storage
+            .expect_paused_runners()
+            .returning(|| Box::pin(async { Ok(vec!["state-pruner".to_string()]) }));
+        let mut pause_sync = PauseSync::new(storage, runners);
+
+        // when
+        pause_sync.run().await.unwrap();
+
+        // then
+        assert!(pruner.is_paused());
+    }
+
+    #[tokio::test]
+    async fn acknowledges_foreign_commits() {
+        // given
+        let runners = RunnerControls::new();
+        let watcher = runners.register("Commit Watcher");
+        let mut storage = recording_storage(1);
+        storage
+            .expect_acknowledge_foreign_commits()
+            .withf(|acknowledged_at| *acknowledged_at == DateTime::UNIX_EPOCH)
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(2) }));
+        let admin = Admin::new(storage, TestClock, runners, ForcedActions::new());
+
+        // when
+        admin
+            .perform(AdminAction::AcknowledgeForeignCommits, None)
+            .await
+            .unwrap();
+
+        // then
+        tokio::time::timeout(Duration::from_millis(50), watcher.woken())
+            .await
+            .expect("to be woken");
+    }
+}
@@ -8,7 +8,7 @@ pub mod service {
         RegistersMetrics, custom_exponential_buckets,
         prometheus::{Histogram, IntGauge, histogram_opts, linear_buckets},
     };
-    use tracing::info;
+    use tracing::{info, warn};
 
     use super::{
         bundler::{Bundle, BundleProposal, BundlerFactory, Metadata},
@@ -16,6 +16,8 @@ pub mod service {
     };
     use crate::{
         Error, Result, Runner,
+        events::service::{Event, EventBus},
+        leader_election::service::Leadership,
         types::{DateTime, Utc, storage::SequentialFuelBlocks},
     };
 
@@ -55,6 +57,8 @@ pub mod service {
         config: Config,
         last_time_bundled: DateTime<Utc>,
         metrics: Metrics,
+        events: EventBus,
+        leadership: Leadership,
     }
 
     impl<F, S, C, B> RegistersMetrics for BlockBundler<F, S, C, B> {
@@ -145,8 +149,23 @@ pub mod service {
                 bundler_factory,
                 config,
                 metrics: Metrics::default(),
+                events: EventBus::default(),
+                leadership: Leadership::unconditional(),
             }
         }
+
+        /// Every stored bundle is announced on `events`.
+        pub fn with_events(mut self, events: EventBus) -> Self {
+            self.events = events;
+            self
+        }
+
+        /// Bundles are only stored while `leadership` is held. The optimization can take longer
+        /// than the lease, after which another instance may already be bundling the same blocks.
+        pub fn with_leadership(mut self, leadership: Leadership) -> Self {
+            self.leadership = leadership;
+            self
+        }
     }
 
     impl<FuelApi, Db, Clock, BF> BlockBundler<FuelApi, Db, Clock, BF>
@@ -188,10 +207,22 @@ pub mod service {
 
                 tracing::info!("Bundler proposed: {metadata}");
 
+                if !self.leadership.is_leader() {
+                    warn!("dropping the proposed bundle, this instance is no longer the leader");
+                    return Ok(());
+                }
+
                 self.storage
                     .insert_bundle_and_fragments(next_id, metadata.block_heights.clone(), fragments)
                     .await?;
 
+                self.events.publish(Event::BundleCreated {
+                    bundle_id: next_id.as_u32(),
+                    start_height: *metadata.block_heights.start(),
+                    end_height: *metadata.block_heights.end(),
+                    num_fragments: metadata.num_fragments.get(),
+                });
+
                 self.metrics.observe_metadata(&metadata);
                 self.metrics
                     .optimization_duration
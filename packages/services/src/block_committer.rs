@@ -1,27 +1,115 @@
 pub mod service {
-    use std::num::NonZeroU32;
+    use std::{
+        num::{NonZeroU32, NonZeroUsize},
+        time::Duration,
+    };
 
-    use tracing::info;
+    use metrics::{
+        RegistersMetrics,
+        prometheus::{IntCounter, IntGauge, core::Collector},
+    };
+    use tracing::{info, warn};
 
     use super::port::fuel::FuelBlock;
     use crate::{
         Error, Result, Runner,
-        types::{BlockSubmission, NonNegative, TransactionState},
+        critical_section::CriticalSections,
+        events::service::{Event, EventBus, hex_hash},
+        types::{
+            BlockSubmission, BlockSubmissionTx, CommitMismatch, DateTime, NonNegative,
+            TransactionState, Utc,
+        },
     };
 
+    /// Settings for checking recent submissions against what the state contract has committed.
+    #[derive(Debug, Clone, Copy)]
+    pub struct ReconciliationConfig {
+        /// How often to reconcile. The first run of the committer always reconciles.
+        pub interval: Duration,
+        /// Number of most recent submissions to check.
+        pub lookback: usize,
+        /// Whether to commit the current epoch again if its commit is missing or foreign.
+        pub recommit_current_epoch: bool,
+        /// Number of commits the state contract keeps. Older commits have their slot reused, so
+        /// their hash can no longer be read.
+        pub num_commit_slots: NonZeroU32,
+    }
+
+    struct Metrics {
+        gas_bumps: IntCounter,
+        commit_mismatches: IntGauge,
+        reconciliation_failures: IntCounter,
+        quorum_failures: IntCounter,
+    }
+
+    impl Default for Metrics {
+        fn default() -> Self {
+            let gas_bumps = IntCounter::new(
+                "block_commit_gas_bumps",
+                "Number of times a pending block commit tx was replaced with higher fees",
+            )
+            .expect("metric config to be correct");
+
+            let commit_mismatches = IntGauge::new(
+                "block_commit_mismatches",
+                "Number of recent block submissions that are missing or differ on L1",
+            )
+            .expect("metric config to be correct");
+
+            let reconciliation_failures = IntCounter::new(
+                "block_commit_reconciliation_failures",
+                "Number of times recent block submissions could not be compared to L1",
+            )
+            .expect("metric config to be correct");
+
+            let quorum_failures = IntCounter::new(
+                "block_commit_quorum_failures",
+                "Number of times a block was not committed because too few Fuel nodes agreed on its id",
+            )
+            .expect("metric config to be correct");
+
+            Self {
+                gas_bumps,
+                commit_mismatches,
+                reconciliation_failures,
+                quorum_failures,
+            }
+        }
+    }
+
     pub struct BlockCommitter<L1, Db, Fuel, Clock> {
         l1_adapter: L1,
         fuel_adapter: Fuel,
+        other_fuel_nodes: Vec<Fuel>,
+        block_id_quorum: NonZeroUsize,
         storage: Db,
         clock: Clock,
         commit_interval: NonZeroU32,
         num_blocks_to_finalize_tx: u64,
+        gas_bump_timeout: Duration,
+        reconciliation: Option<ReconciliationConfig>,
+        last_reconciliation: Option<DateTime<Utc>>,
+        critical_sections: CriticalSections,
+        events: EventBus,
+        metrics: Metrics,
+    }
+
+    impl<L1, Db, Fuel, Clock> RegistersMetrics for BlockCommitter<L1, Db, Fuel, Clock> {
+        fn metrics(&self) -> Vec<Box<dyn Collector>> {
+            vec![
+                Box::new(self.metrics.gas_bumps.clone()),
+                Box::new(self.metrics.commit_mismatches.clone()),
+                Box::new(self.metrics.reconciliation_failures.clone()),
+                Box::new(self.metrics.quorum_failures.clone()),
+            ]
+        }
     }
 
     #[derive(Debug)]
     enum Action {
         UpdateTx {
             submission_id: NonNegative<i32>,
+            block_hash: [u8; 32],
             block_height: u32,
         },
         Post,
@@ -41,11 +129,58 @@ pub mod service {
                 l1_adapter: l1,
                 storage,
                 fuel_adapter,
+                other_fuel_nodes: vec![],
+                block_id_quorum: NonZeroUsize::MIN,
                 clock,
                 commit_interval,
                 num_blocks_to_finalize_tx,
+                gas_bump_timeout: Duration::from_secs(300),
+                reconciliation: None,
+                last_reconciliation: None,
+                critical_sections: CriticalSections::default(),
+                events: EventBus::default(),
+                metrics: Metrics::default(),
             }
         }
+
+        /// A commit tx that stays pending for longer than `gas_bump_timeout` is replaced by one
+        /// with the same nonce and higher fees.
+        pub fn with_gas_bump_timeout(mut self, gas_bump_timeout: Duration) -> Self {
+            self.gas_bump_timeout = gas_bump_timeout;
+            self
+        }
+
+        /// A block is only committed if at least `quorum` Fuel nodes, counting the main one, report
+        /// the same id for it. `other_fuel_nodes` are asked only for that check.
+        pub fn with_block_id_quorum(
+            mut self,
+            other_fuel_nodes: Vec<Fuel>,
+            quorum: NonZeroUsize,
+        ) -> Self {
+            self.other_fuel_nodes = other_fuel_nodes;
+            self.block_id_quorum = quorum;
+            self
+        }
+
+        /// Recent submissions are compared to the block hashes committed on L1, see
+        /// [`ReconciliationConfig`]. Disabled unless set.
+        pub fn with_reconciliation(mut self, reconciliation: ReconciliationConfig) -> Self {
+            self.reconciliation = Some(reconciliation);
+            self
+        }
+
+        /// Submitting a block and recording the transaction happen within a critical section so
+        /// that a shutdown cannot separate the two.
+        pub fn with_critical_sections(mut self, critical_sections: CriticalSections) -> Self {
+            self.critical_sections = critical_sections;
+            self
+        }
+
+        /// Every finalized block commitment is announced on `events`.
+        pub fn with_events(mut self, events: EventBus) -> Self {
+            self.events = events;
+            self
+        }
     }
 
     impl<L1, Db, Fuel, Clock> BlockCommitter<L1, Db, Fuel, Clock>
@@ -56,11 +191,18 @@ pub mod service {
         Clock: crate::block_committer::port::Clock,
     {
         async fn submit_block(&self, fuel_block: FuelBlock) -> Result<()> {
+            self.ensure_block_id_quorum(&fuel_block).await?;
+
             let submission = BlockSubmission::new(fuel_block.id, fuel_block.height);
 
+            let Some(_section) = self.critical_sections.enter().await else {
+                info!("shutting down, not submitting {fuel_block:?}");
+                return Ok(());
+            };
+
             let mut tx = self
                 .l1_adapter
-                .submit(fuel_block.id, fuel_block.height)
+                .submit(fuel_block.id, fuel_block.height, None)
                 .await?;
             tx.submission_id = submission.id;
             self.storage
@@ -72,6 +214,45 @@ pub mod service {
             Ok(())
         }
 
+        async fn ensure_block_id_quorum(&self, fuel_block: &FuelBlock) -> Result<()> {
+            let quorum = self.block_id_quorum.get();
+            if quorum == 1 {
+                return Ok(());
+            }
+
+            let responses = futures::future::join_all(
+                self.other_fuel_nodes
+                    .iter()
+                    .map(|node| node.block_at_height(fuel_block.height)),
+            )
+            .await;
+
+            // the main node provided the block
+            let mut agreeing = 1;
+            let mut dissent = vec![];
+            for response in responses {
+                match response {
+                    Ok(Some(block)) if block.id == fuel_block.id => agreeing += 1,
+                    Ok(Some(block)) => dissent.push(format!("id {}", hex::encode(block.id))),
+                    Ok(None) => dissent.push("no block".to_string()),
+                    Err(e) => dissent.push(e.to_string()),
+                }
+            }
+
+            if agreeing >= quorum {
+                return Ok(());
+            }
+
+            self.metrics.quorum_failures.inc();
+            Err(Error::NoQuorum(format!(
+                "{agreeing} of {} Fuel nodes agree on id {} of block: {}, {quorum} required. Others reported: {}",
+                self.other_fuel_nodes.len() + 1,
+                hex::encode(fuel_block.id),
+                fuel_block.height,
+                dissent.join(", ")
+            )))
+        }
+
         fn current_epoch_block_height(&self, current_block_height: u32) -> u32 {
             current_block_height - (current_block_height % self.commit_interval)
         }
@@ -107,6 +288,7 @@ pub mod service {
             match latest_submission {
                 Some(submission) if !submission.completed => Action::UpdateTx {
                     submission_id: submission.id.expect("submission to have id"),
+                    block_hash: submission.block_hash,
                     block_height: submission.block_height,
                 },
                 _ => Action::DoNothing,
@@ -116,6 +298,7 @@ pub mod service {
         async fn update_transactions(
             &self,
             submission_id: NonNegative<i32>,
+            block_hash: [u8; 32],
             block_height: u32,
         ) -> Result<()> {
             let transactions = self
@@ -124,12 +307,16 @@ pub mod service {
                 .await?;
             let current_block_number: u64 = self.l1_adapter.get_block_number().await?.into();
 
+            let latest_tx = transactions.iter().max_by_key(|tx| tx.created_at).cloned();
+            let mut any_included = false;
+
             for tx in transactions {
                 let tx_hash = tx.hash;
                 let Some(tx_response) = self.l1_adapter.get_transaction_response(tx_hash).await?
                 else {
                     continue; // not included
                 };
+                any_included = true;
 
                 if !tx_response.succeeded() {
                     self.storage
@@ -155,12 +342,189 @@ pub mod service {
                     )
                     .await?;
 
+                self.events.publish(Event::BlockCommitted {
+                    block_height,
+                    tx_hash: hex_hash(tx_hash),
+                });
+
                 info!(
                     "finalized submission for block: {block_height} with tx: {}",
                     hex::encode(tx_hash)
                 );
             }
 
+            if let Some(latest_tx) = latest_tx.filter(|_| !any_included) {
+                self.bump_gas_if_stalled(submission_id, block_hash, block_height, latest_tx)
+                    .await?;
+            }
+
+            Ok(())
+        }
+
+        fn reconciliation_due(&self) -> Option<ReconciliationConfig> {
+            let reconciliation = self.reconciliation?;
+
+            let due = self.last_reconciliation.is_none_or(|last| {
+                self.clock
+                    .now()
+                    .signed_duration_since(last)
+                    .to_std()
+                    .unwrap_or_default()
+                    >= reconciliation.interval
+            });
+
+            due.then_some(reconciliation)
+        }
+
+        async fn onchain_mismatch(
+            &self,
+            submission: &BlockSubmission,
+        ) -> Result<Option<CommitMismatch>> {
+            let commit_height = submission.block_height / self.commit_interval;
+            let block_hash = self
+                .l1_adapter
+                .block_hash_at_commit_height(commit_height)
+                .await?;
+
+            let mismatch = match block_hash {
+                None => Some(CommitMismatch::Missing),
+                Some(block_hash) if block_hash == submission.block_hash => None,
+                Some(block_hash) => Some(CommitMismatch::Foreign { block_hash }),
+            };
+
+            Ok(mismatch)
+        }
+
+        async fn reconcile(
+            &self,
+            reconciliation: ReconciliationConfig,
+            current_epoch_block_height: u32,
+        ) -> Result<()> {
+            let submissions = self
+                .storage
+                .recent_block_submissions(reconciliation.lookback)
+                .await?;
+
+            // the slot of a commit is reused `num_commit_slots` commits later, after which
+            // reading it returns the hash of that later commit
+            let current_commit_height = current_epoch_block_height / self.commit_interval;
+            let slot_not_reused = |submission: &&BlockSubmission| {
+                let commit_height = submission.block_height / self.commit_interval;
+                commit_height + reconciliation.num_commit_slots.get() > current_commit_height
+            };
+
+            let mut mismatches = 0;
+            let mut current_epoch_mismatched = None;
+            // only completed submissions are expected to be on L1
+            for submission in submissions
+                .iter()
+                .filter(|s| s.completed)
+                .filter(slot_not_reused)
+            {
+                let submission_id = submission.id.expect("submission to have id");
+                let mismatch = self.onchain_mismatch(submission).await?;
+
+                self.storage
+                    .set_commit_mismatch(submission_id, mismatch, self.clock.now())
+                    .await?;
+
+                let Some(mismatch) = mismatch else {
+                    continue;
+                };
+
+                mismatches += 1;
+                warn!(
+                    "commit of block: {} does not match L1: {mismatch:?}",
+                    submission.block_height
+                );
+
+                if submission.block_height == current_epoch_block_height {
+                    current_epoch_mismatched = Some(submission);
+                }
+            }
+
+            self.metrics.commit_mismatches.set(mismatches);
+
+            if let Some(submission) =
+                current_epoch_mismatched.filter(|_| reconciliation.recommit_current_epoch)
+            {
+                self.recommit(submission).await?;
+            }
+
+            Ok(())
+        }
+
+        async fn recommit(&self, submission: &BlockSubmission) -> Result<()> {
+            let Some(_section) = self.critical_sections.enter().await else {
+                info!(
+                    "shutting down, not committing block: {} again",
+                    submission.block_height
+                );
+                return Ok(());
+            };
+
+            let mut tx = self
+                .l1_adapter
+                .submit(submission.block_hash, submission.block_height, None)
+                .await?;
+            tx.submission_id = submission.id;
+            self.storage
+                .record_block_submission_tx(tx, self.clock.now())
+                .await?;
+
+            info!("committed block: {} again", submission.block_height);
+
+            Ok(())
+        }
+
+        async fn bump_gas_if_stalled(
+            &self,
+            submission_id: NonNegative<i32>,
+            block_hash: [u8; 32],
+            block_height: u32,
+            previous_tx: BlockSubmissionTx,
+        ) -> Result<()> {
+            let created_at = previous_tx.created_at.expect("tx to have timestamp");
+            let elapsed = self
+                .clock
+                .now()
+                .signed_duration_since(created_at)
+                .to_std()
+                .unwrap_or_default();
+
+            if elapsed < self.gas_bump_timeout {
+                return Ok(());
+            }
+
+            let Some(_section) = self.critical_sections.enter().await else {
+                info!("shutting down, not replacing the commit of block: {block_height}");
+                return Ok(());
+            };
+
+            info!(
+                "replacing tx {} for block: {block_height} because it was pending for {}s",
+                hex::encode(previous_tx.hash),
+                elapsed.as_secs()
+            );
+
+            let replaced_tx_hash = previous_tx.hash;
+            let mut tx = self
+                .l1_adapter
+                .submit(block_hash, block_height, Some(previous_tx))
+                .await?;
+            tx.submission_id = Some(submission_id);
+            let (tx_hash, nonce) = (tx.hash, tx.nonce);
+            self.storage
+                .record_block_submission_tx(tx, self.clock.now())
+                .await?;
+
+            self.metrics.gas_bumps.inc();
+            self.events.publish(Event::TxReplaced {
+                replaced_tx_hash: hex_hash(replaced_tx_hash),
+                tx_hash: hex_hash(tx_hash),
+                nonce,
+            });
+
             Ok(())
         }
     }
@@ -173,11 +537,24 @@ pub mod service {
         Clock: crate::block_committer::port::Clock + Send + Sync,
     {
         async fn run(&mut self) -> Result<()> {
-            let latest_submission = self.storage.submission_w_latest_block().await?;
-
             let current_block = self.fuel_adapter.latest_block().await?;
             let current_epoch_block_height = self.current_epoch_block_height(current_block.height);
 
+            if let Some(reconciliation) = self.reconciliation_due() {
+                // a failed reconciliation must not keep blocks from being committed, it is retried
+                // once the next one is due
+                if let Err(e) = self
+                    .reconcile(reconciliation, current_epoch_block_height)
+                    .await
+                {
+                    warn!("could not reconcile block submissions with L1: {e}");
+                    self.metrics.reconciliation_failures.inc();
+                }
+                self.last_reconciliation = Some(self.clock.now());
+            }
+
+            let latest_submission = self.storage.submission_w_latest_block().await?;
+
             let action = self.decide_action(latest_submission.as_ref(), current_epoch_block_height);
             match action {
                 Action::DoNothing => {}
@@ -192,9 +569,10 @@ pub mod service {
                 }
                 Action::UpdateTx {
                     submission_id,
+                    block_hash,
                     block_height,
                 } => {
-                    self.update_transactions(submission_id, block_height)
+                    self.update_transactions(submission_id, block_hash, block_height)
                         .await?
                 }
             }
@@ -207,7 +585,10 @@ pub mod service {
 pub mod port {
     use crate::{
         Result,
-        types::{BlockSubmission, BlockSubmissionTx, DateTime, NonNegative, TransactionState, Utc},
+        types::{
+            BlockSubmission, BlockSubmissionTx, CommitMismatch, DateTime, NonNegative,
+            TransactionState, Utc,
+        },
     };
 
     pub mod l1 {
@@ -222,7 +603,21 @@ pub mod port {
         #[trait_variant::make(Send)]
         #[cfg_attr(feature = "test-helpers", mockall::automock)]
         pub trait Contract: Sync {
-            async fn submit(&self, hash: [u8; 32], height: u32) -> Result<BlockSubmissionTx>;
+            /// When `previous_tx` is given it is replaced: its nonce is reused and the fees are
+            /// raised enough for the node to accept the replacement.
+            async fn submit(
+                &self,
+                hash: [u8; 32],
+                height: u32,
+                previous_tx: Option<BlockSubmissionTx>,
+            ) -> Result<BlockSubmissionTx>;
+            /// The block hash committed at `commit_height`, `None` if there is none. The contract
+            /// keeps a limited number of commits, so a later commit reusing the slot is returned
+            /// in place of an old one.
+            async fn block_hash_at_commit_height(
+                &self,
+                commit_height: u32,
+            ) -> Result<Option<[u8; 32]>>;
             fn commit_interval(&self) -> NonZeroU32;
         }
 
@@ -265,6 +660,12 @@ pub mod port {
             submission: BlockSubmission,
             created_at: DateTime<Utc>,
         ) -> Result<NonNegative<i32>>;
+        /// Records a tx replacing a pending one of an already recorded submission.
+        async fn record_block_submission_tx(
+            &self,
+            submission_tx: BlockSubmissionTx,
+            created_at: DateTime<Utc>,
+        ) -> Result<()>;
         async fn get_pending_block_submission_txs(
             &self,
             submission_id: NonNegative<i32>,
@@ -275,6 +676,15 @@ pub mod port {
             state: TransactionState,
         ) -> Result<BlockSubmission>;
         async fn submission_w_latest_block(&self) -> Result<Option<BlockSubmission>>;
+        /// The `limit` submissions with the highest block heights, highest first.
+        async fn recent_block_submissions(&self, limit: usize) -> Result<Vec<BlockSubmission>>;
+        /// Records how the commit of a submission differs from L1, `None` clearing it.
+        async fn set_commit_mismatch(
+            &self,
+            submission_id: NonNegative<i32>,
+            mismatch: Option<CommitMismatch>,
+            detected_at: DateTime<Utc>,
+        ) -> Result<()>;
     }
 
     pub trait Clock {
@@ -0,0 +1,393 @@
+pub mod service {
+    use serde::Serialize;
+    use utoipa::ToSchema;
+
+    use crate::{
+        Result,
+        bundle_reporter::service::{FragmentReport, TransactionStatus},
+        types::{
+            BlockSubmission, BlockSubmissionTx, DateTime, FragmentSubmissions, HeightRange,
+            TransactionState, Utc,
+        },
+    };
+
+    /// Where a fuel block currently is on its way to L1.
+    #[derive(Debug, Serialize, ToSchema)]
+    pub struct BlockReport {
+        pub height: u32,
+        /// Whether the block was imported into the database.
+        pub imported: bool,
+        /// Whether the block is recent enough to still be bundled.
+        pub within_lookback_window: bool,
+        /// The block fell out of the lookback window without being bundled, it won't be posted.
+        pub given_up: bool,
+        pub bundle: Option<BundleInclusion>,
+        pub contract_commit: Option<ContractCommitReport>,
+    }
+
+    #[derive(Debug, Serialize, ToSchema)]
+    pub struct BundleInclusion {
+        pub id: u32,
+        pub start_height: u32,
+        pub end_height: u32,
+        pub fragments: Vec<FragmentReport>,
+        /// When the last fragment of the bundle was finalized, `None` until all of them are.
+        pub finalized_at: Option<String>,
+        /// DA height of the last transaction carrying the bundle, once finalized.
+        pub da_block_height: Option<u64>,
+    }
+
+    #[derive(Debug, Clone, Serialize, ToSchema)]
+    pub struct ContractCommitReport {
+        pub block_height: u32,
+        pub block_hash: String,
+        pub completed: bool,
+        pub transactions: Vec<CommitTransactionReport>,
+    }
+
+    #[derive(Debug, Clone, Serialize, ToSchema)]
+    pub struct CommitTransactionReport {
+        pub hash: String,
+        pub nonce: u32,
+        pub state: TransactionStatus,
+        pub max_fee: u128,
+        pub priority_fee: u128,
+        pub created_at: Option<String>,
+        pub finalized_at: Option<String>,
+    }
+
+    impl From<BlockSubmissionTx> for CommitTransactionReport {
+        fn from(tx: BlockSubmissionTx) -> Self {
+            let (state, finalized_at) = TransactionStatus::of(&tx.state);
+
+            Self {
+                hash: format!("0x{}", hex::encode(tx.hash)),
+                nonce: tx.nonce,
+                state,
+                max_fee: tx.max_fee,
+                priority_fee: tx.priority_fee,
+                created_at: tx.created_at.map(|at| at.to_rfc3339()),
+                finalized_at: finalized_at.map(|at| at.to_rfc3339()),
+            }
+        }
+    }
+
+    /// Looks up how far a single fuel block got in the pipeline, both for the state submission and
+    /// for the contract commit.
+    pub struct BlockReporter<Db, FuelApi> {
+        storage: Db,
+        fuel_api: FuelApi,
+        lookback_window: u32,
+    }
+
+    impl<Db, FuelApi> BlockReporter<Db, FuelApi> {
+        pub fn new(storage: Db, fuel_api: FuelApi, lookback_window: u32) -> Self {
+            Self {
+                storage,
+                fuel_api,
+                lookback_window,
+            }
+        }
+    }
+
+    impl<Db, FuelApi> BlockReporter<Db, FuelApi>
+    where
+        Db: crate::block_reporter::port::Storage,
+        FuelApi: crate::block_reporter::port::fuel::Api,
+    {
+        pub async fn block(&self, height: u32) -> Result<BlockReport> {
+            let latest_height = self.fuel_api.latest_height().await?;
+            let within_lookback_window =
+                height >= latest_height.saturating_sub(self.lookback_window);
+
+            let imported = self.storage.is_block_imported(height).await?;
+            let bundle = self.bundle_inclusion(height).await?;
+            let contract_commit = self.contract_commit(height).await?;
+
+            Ok(BlockReport {
+                height,
+                imported,
+                within_lookback_window,
+                given_up: bundle.is_none() && !within_lookback_window,
+                bundle,
+                contract_commit,
+            })
+        }
+
+        async fn bundle_inclusion(&self, height: u32) -> Result<Option<BundleInclusion>> {
+            let Some(bundle) = self.storage.bundle_containing(height).await? else {
+                return Ok(None);
+            };
+
+            let fragments = self.storage.bundle_fragments(bundle.id).await?;
+            let finalized_at = finalization_time(&fragments);
+            let da_block_height = self.storage.finalized_bundle_da_height(bundle.id).await?;
+
+            Ok(Some(BundleInclusion {
+                id: bundle.id,
+                start_height: bundle.start_height,
+                end_height: bundle.end_height,
+                fragments: fragments.into_iter().map(FragmentReport::from).collect(),
+                finalized_at: finalized_at.map(|at| at.to_rfc3339()),
+                da_block_height,
+            }))
+        }
+
+        /// Lists contract commits from the highest block to the lowest, starting below
+        /// `before_height` if given. Only commits of blocks in `heights` are listed.
+        pub async fn contract_commits(
+            &self,
+            before_height: Option<u32>,
+            heights: HeightRange,
+            limit: usize,
+        ) -> Result<Vec<ContractCommitReport>> {
+            let submissions = self
+                .storage
+                .block_submissions(before_height, heights, limit)
+                .await?;
+
+            let mut reports = Vec::with_capacity(submissions.len());
+            for submission in submissions {
+                reports.push(self.contract_commit_report(submission).await?);
+            }
+
+            Ok(reports)
+        }
+
+        async fn contract_commit(&self, height: u32) -> Result<Option<ContractCommitReport>> {
+            match self.storage.block_submission(height).await? {
+                Some(submission) => Ok(Some(self.contract_commit_report(submission).await?)),
+                None => Ok(None),
+            }
+        }
+
+        async fn contract_commit_report(
+            &self,
+            submission: BlockSubmission,
+        ) -> Result<ContractCommitReport> {
+            let transactions = match submission.id {
+                Some(id) => self.storage.block_submission_txs(id).await?,
+                None => vec![],
+            };
+
+            Ok(ContractCommitReport {
+                block_height: submission.block_height,
+                block_hash: format!("0x{}", hex::encode(submission.block_hash)),
+                completed: submission.completed,
+                transactions: transactions
+                    .into_iter()
+                    .map(CommitTransactionReport::from)
+                    .collect(),
+            })
+        }
+    }
+
+    /// A bundle is finalized once every one of its fragments is carried by a finalized
+    /// transaction.
+    fn finalization_time(fragments: &[FragmentSubmissions]) -> Option<DateTime<Utc>> {
+        if fragments.is_empty() {
+            return None;
+        }
+
+        fragments
+            .iter()
+            .map(|fragment| {
+                fragment.transactions.iter().find_map(|tx| match tx.state {
+                    TransactionState::Finalized(at) => Some(at),
+                    _ => None,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .max()
+    }
+}
+
+pub mod port {
+    use crate::{
+        Result,
+        types::{
+            BlockSubmission, BlockSubmissionTx, BundleOverview, FragmentSubmissions, HeightRange,
+            NonNegative,
+        },
+    };
+
+    #[allow(async_fn_in_trait)]
+    #[trait_variant::make(Send)]
+    #[cfg_attr(feature = "test-helpers", mockall::automock)]
+    pub trait Storage: Send + Sync {
+        async fn is_block_imported(&self, height: u32) -> Result<bool>;
+        /// The most recent bundle whose range includes `height`.
+        async fn bundle_containing(&self, height: u32) -> Result<Option<BundleOverview>>;
+        /// Fragments of the bundle ordered by their index.
+        async fn bundle_fragments(&self, bundle_id: u32) -> Result<Vec<FragmentSubmissions>>;
+        async fn finalized_bundle_da_height(&self, bundle_id: u32) -> Result<Option<u64>>;
+        async fn block_submission(&self, height: u32) -> Result<Option<BlockSubmission>>;
+        /// Submissions of blocks lower than `before_height` and within `heights`, highest first.
+        async fn block_submissions(
+            &self,
+            before_height: Option<u32>,
+            heights: HeightRange,
+            limit: usize,
+        ) -> Result<Vec<BlockSubmission>>;
+        async fn block_submission_txs(
+            &self,
+            submission_id: NonNegative<i32>,
+        ) -> Result<Vec<BlockSubmissionTx>>;
+    }
+
+    pub mod fuel {
+        #[allow(async_fn_in_trait)]
+        #[trait_variant::make(Send)]
+        #[cfg_attr(feature = "test-helpers", mockall::automock)]
+        pub trait Api: Send + Sync {
+            async fn latest_height(&self) -> crate::Result<u32>;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        port::{MockStorage, fuel::MockApi},
+        service::BlockReporter,
+    };
+    use crate::types::{
+        BundleOverview, DateTime, FragmentSubmissions, L1Tx, TransactionState, Utc,
+    };
+
+    fn fuel_at_height(height: u32) -> MockApi {
+        let mut fuel_api = MockApi::new();
+        fuel_api
+            .expect_latest_height()
+            .returning(move || Box::pin(async move { Ok(height) }));
+        fuel_api
+    }
+
+    fn storage_without_submissions() -> MockStorage {
+        let mut storage = MockStorage::new();
+        storage
+            .expect_is_block_imported()
+            .returning(|_| Box::pin(async { Ok(true) }));
+        storage
+            .expect_block_submission()
+            .returning(|_| Box::pin(async { Ok(None) }));
+        storage
+    }
+
+    fn fragment(idx: u32, state: TransactionState) -> FragmentSubmissions {
+        FragmentSubmissions {
+            id: idx + 1,
+            idx,
+            total_bytes: 100,
+            unused_bytes: 0,
+            transactions: vec![L1Tx {
+                state,
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn unbundled_block_outside_the_lookback_window_is_given_up() {
+        // given
+        let mut storage = storage_without_submissions();
+        storage
+            .expect_bundle_containing()
+            .returning(|_| Box::pin(async { Ok(None) }));
+        let reporter = BlockReporter::new(storage, fuel_at_height(1000), 100);
+
+        // when
+        let old = reporter.block(899).await.unwrap();
+        let recent = reporter.block(900).await.unwrap();
+
+        // then
+        assert!(old.given_up);
+        assert!(!old.within_lookback_window);
+        assert!(!recent.given_up);
+        assert!(recent.within_lookback_window);
+    }
+
+    #[tokio::test]
+    async fn bundle_is_finalized_once_all_fragments_are() {
+        // given
+        let first = DateTime::<Utc>::from_timestamp(100, 0).unwrap();
+        let last = DateTime::<Utc>::from_timestamp(200, 0).unwrap();
+
+        let mut storage = storage_without_submissions();
+        storage.expect_bundle_containing().returning(|_| {
+            Box::pin(async {
+                Ok(Some(BundleOverview {
+                    id: 1,
+                    start_height: 0,
+                    end_height: 10,
+                    num_fragments: 2,
+                    num_finalized_fragments: 2,
+                    compressed_size: 200,
+                    uncompressed_size: None,
+                }))
+            })
+        });
+        storage.expect_bundle_fragments().returning(move |_| {
+            Box::pin(async move {
+                Ok(vec![
+                    fragment(0, TransactionState::Finalized(last)),
+                    fragment(1, TransactionState::Finalized(first)),
+                ])
+            })
+        });
+        storage
+            .expect_finalized_bundle_da_height()
+            .returning(|_| Box::pin(async { Ok(Some(42)) }));
+        let reporter = BlockReporter::new(storage, fuel_at_height(1000), 100);
+
+        // when
+        let report = reporter.block(5).await.unwrap();
+
+        // then
+        assert!(!report.given_up);
+        let bundle = report.bundle.unwrap();
+        assert_eq!(bundle.finalized_at, Some(last.to_rfc3339()));
+        assert_eq!(bundle.da_block_height, Some(42));
+        assert_eq!(bundle.fragments.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn bundle_is_not_finalized_while_a_fragment_is_pending() {
+        // given
+        let finalized_at = DateTime::<Utc>::from_timestamp(100, 0).unwrap();
+
+        let mut storage = storage_without_submissions();
+        storage.expect_bundle_containing().returning(|_| {
+            Box::pin(async {
+                Ok(Some(BundleOverview {
+                    id: 1,
+                    start_height: 0,
+                    end_height: 10,
+                    num_fragments: 2,
+                    num_finalized_fragments: 1,
+                    compressed_size: 200,
+                    uncompressed_size: None,
+                }))
+            })
+        });
+        storage.expect_bundle_fragments().returning(move |_| {
+            Box::pin(async move {
+                Ok(vec![
+                    fragment(0, TransactionState::Finalized(finalized_at)),
+                    fragment(1, TransactionState::Pending),
+                ])
+            })
+        });
+        storage
+            .expect_finalized_bundle_da_height()
+            .returning(|_| Box::pin(async { Ok(None) }));
+        let reporter = BlockReporter::new(storage, fuel_at_height(1000), 100);
+
+        // when
+        let report = reporter.block(5).await.unwrap();
+
+        // then
+        assert_eq!(report.bundle.unwrap().finalized_at, None);
+    }
+}
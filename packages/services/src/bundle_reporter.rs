@@ -0,0 +1,380 @@
+pub mod service {
+    use std::collections::HashMap;
+
+    use serde::Serialize;
+    use utoipa::ToSchema;
+
+    use crate::{
+        Error, Result,
+        types::{
+            BundleOverview, DateTime, FragmentSubmissions, HeightRange, L1Tx, TransactionState, Utc,
+        },
+    };
+
+    #[derive(Debug, Serialize, ToSchema)]
+    pub struct BundlesPage {
+        pub bundles: Vec<BundleSummary>,
+        /// Pass as `before` to get the next page, `None` on the last page.
+        pub next_before: Option<u32>,
+    }
+
+    #[derive(Debug, Clone, Serialize, ToSchema)]
+    pub struct BundleSummary {
+        #[serde(flatten)]
+        pub overview: BundleOverview,
+        pub compression_ratio: Option<f64>,
+    }
+
+    impl From<BundleOverview> for BundleSummary {
+        fn from(overview: BundleOverview) -> Self {
+            Self {
+                compression_ratio: overview.compression_ratio(),
+                overview,
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, ToSchema)]
+    pub struct BundleDetails {
+        #[serde(flatten)]
+        pub summary: BundleSummary,
+        pub fragments: Vec<FragmentReport>,
+    }
+
+    #[derive(Debug, Clone, Serialize, ToSchema)]
+    pub struct FragmentReport {
+        pub id: u32,
+        pub idx: u32,
+        pub total_bytes: u32,
+        pub unused_bytes: u32,
+        pub transactions: Vec<TransactionReport>,
+    }
+
+    impl From<FragmentSubmissions> for FragmentReport {
+        fn from(fragment: FragmentSubmissions) -> Self {
+            Self {
+                id: fragment.id,
+                idx: fragment.idx,
+                total_bytes: fragment.total_bytes,
+                unused_bytes: fragment.unused_bytes,
+                transactions: fragment
+                    .transactions
+                    .into_iter()
+                    .map(TransactionReport::from)
+                    .collect(),
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, ToSchema)]
+    pub struct BlobTransactionsPage {
+        pub transactions: Vec<TransactionReport>,
+        /// Pass as `before` to get the next page, `None` on the last page.
+        pub next_before: Option<u64>,
+    }
+
+    #[derive(Debug, Clone, Serialize, ToSchema)]
+    pub struct TransactionReport {
+        pub id: Option<u64>,
+        pub hash: String,
+        pub nonce: u32,
+        pub state: TransactionStatus,
+        pub max_fee: u128,
+        pub priority_fee: u128,
+        pub blob_fee: u128,
+        pub created_at: Option<String>,
+        pub finalized_at: Option<String>,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+    #[serde(rename_all = "snake_case")]
+    pub enum TransactionStatus {
+        Pending,
+        IncludedInBlock,
+        Finalized,
+        SqueezedOut,
+        Failed,
+    }
+
+    impl TransactionStatus {
+        /// Also returns when the transaction was finalized, if it was.
+        pub fn of(state: &TransactionState) -> (Self, Option<DateTime<Utc>>) {
+            match state {
+                TransactionState::Pending => (Self::Pending, None),
+                TransactionState::IncludedInBlock => (Self::IncludedInBlock, None),
+                TransactionState::Finalized(at) => (Self::Finalized, Some(*at)),
+                TransactionState::SqueezedOut => (Self::SqueezedOut, None),
+                TransactionState::Failed => (Self::Failed, None),
+            }
+        }
+    }
+
+    impl From<L1Tx> for TransactionReport {
+        fn from(tx: L1Tx) -> Self {
+            let (state, finalized_at) = TransactionStatus::of(&tx.state);
+
+            Self {
+                id: tx.id,
+                hash: format!("0x{}", hex::encode(tx.hash)),
+                nonce: tx.nonce,
+                state,
+                max_fee: tx.max_fee,
+                priority_fee: tx.priority_fee,
+                blob_fee: tx.blob_fee,
+                created_at: tx.created_at.map(|at| at.to_rfc3339()),
+                finalized_at: finalized_at.map(|at| at.to_rfc3339()),
+            }
+        }
+    }
+
+    /// Exposes the lifecycle of bundles: the fragments they were split into and the blob
+    /// transactions that carried them.
+    pub struct BundleReporter<Db> {
+        storage: Db,
+        request_limit: usize,
+    }
+
+    impl<Db> BundleReporter<Db> {
+        pub fn new(storage: Db, request_limit: usize) -> Self {
+            Self {
+                storage,
+                request_limit,
+            }
+        }
+    }
+
+    impl<Db> BundleReporter<Db>
+    where
+        Db: crate::bundle_reporter::port::Storage,
+    {
+        /// Lists bundles from the newest to the oldest, starting below `before` if given. Only
+        /// bundles with blocks in `heights` are listed.
+        pub async fn bundles(
+            &self,
+            before: Option<u32>,
+            heights: HeightRange,
+            limit: usize,
+        ) -> Result<BundlesPage> {
+            self.check_limit(limit)?;
+
+            let bundles = self.storage.bundles(before, heights, limit).await?;
+
+            let next_before = if bundles.len() == limit {
+                bundles.last().map(|bundle| bundle.id)
+            } else {
+                None
+            };
+
+            Ok(BundlesPage {
+                bundles: bundles.into_iter().map(BundleSummary::from).collect(),
+                next_before,
+            })
+        }
+
+        /// Lists blob transactions from the newest to the oldest, starting below `before` if
+        /// given. Only transactions carrying blocks in `heights` are listed.
+        pub async fn blob_transactions(
+            &self,
+            before: Option<u64>,
+            heights: HeightRange,
+            limit: usize,
+        ) -> Result<BlobTransactionsPage> {
+            self.check_limit(limit)?;
+
+            let transactions = self
+                .storage
+                .blob_transactions(before, heights, limit)
+                .await?;
+
+            let next_before = if transactions.len() == limit {
+                transactions.last().and_then(|tx| tx.id)
+            } else {
+                None
+            };
+
+            Ok(BlobTransactionsPage {
+                transactions: transactions
+                    .into_iter()
+                    .map(TransactionReport::from)
+                    .collect(),
+                next_before,
+            })
+        }
+
+        /// Ids of the bundles with a fragment carried by each of the blob transactions, newest
+        /// first. Transactions carrying no bundle are left out.
+        pub async fn bundle_ids_carried_by(
+            &self,
+            tx_hashes: Vec<[u8; 32]>,
+        ) -> Result<HashMap<[u8; 32], Vec<u32>>> {
+            self.storage.bundle_ids_carried_by(tx_hashes).await
+        }
+
+        pub async fn bundle(&self, id: u32) -> Result<Option<BundleDetails>> {
+            let Some(overview) = self.storage.bundles_by_id(vec![id]).await?.pop() else {
+                return Ok(None);
+            };
+
+            let fragments = self
+                .fragments_of_bundles(vec![id])
+                .await?
+                .remove(&id)
+                .unwrap_or_default();
+
+            Ok(Some(BundleDetails {
+                summary: overview.into(),
+                fragments,
+            }))
+        }
+
+        /// Bundles of the given ids, unknown ones are left out.
+        pub async fn bundles_by_id(&self, ids: Vec<u32>) -> Result<Vec<BundleSummary>> {
+            let bundles = self.storage.bundles_by_id(ids).await?;
+
+            Ok(bundles.into_iter().map(BundleSummary::from).collect())
+        }
+
+        /// Fragments of each of the bundles ordered by their index. Bundles without fragments
+        /// are left out.
+        pub async fn fragments_of_bundles(
+            &self,
+            ids: Vec<u32>,
+        ) -> Result<HashMap<u32, Vec<FragmentReport>>> {
+            let fragments = self.storage.fragments_of_bundles(ids).await?;
+
+            Ok(fragments
+                .into_iter()
+                .map(|(id, fragments)| {
+                    let fragments = fragments.into_iter().map(FragmentReport::from).collect();
+                    (id, fragments)
+                })
+                .collect())
+        }
+
+        fn check_limit(&self, limit: usize) -> Result<()> {
+            if limit > self.request_limit {
+                return Err(Error::Other(format!(
+                    "requested: {} items, but limit is: {}",
+                    limit, self.request_limit
+                )));
+            }
+
+            Ok(())
+        }
+    }
+}
+
+pub mod port {
+    use std::collections::HashMap;
+
+    use crate::{
+        Result,
+        types::{BundleOverview, FragmentSubmissions, HeightRange, L1Tx},
+    };
+
+    #[allow(async_fn_in_trait)]
+    #[trait_variant::make(Send)]
+    #[cfg_attr(feature = "test-helpers", mockall::automock)]
+    pub trait Storage: Send + Sync {
+        /// Bundles with an id lower than `before_id` whose block range overlaps `heights`, newest
+        /// first.
+        async fn bundles(
+            &self,
+            before_id: Option<u32>,
+            heights: HeightRange,
+            limit: usize,
+        ) -> Result<Vec<BundleOverview>>;
+        /// Bundles of the given ids, unknown ones are left out.
+        async fn bundles_by_id(&self, ids: Vec<u32>) -> Result<Vec<BundleOverview>>;
+        /// Fragments of each of the bundles ordered by their index, bundles without fragments
+        /// are left out.
+        async fn fragments_of_bundles(
+            &self,
+            ids: Vec<u32>,
+        ) -> Result<HashMap<u32, Vec<FragmentSubmissions>>>;
+        /// Ids of the bundles with a fragment carried by each of the blob transactions, newest
+        /// first. Transactions carrying no bundle are left out.
+        async fn bundle_ids_carried_by(
+            &self,
+            tx_hashes: Vec<[u8; 32]>,
+        ) -> Result<HashMap<[u8; 32], Vec<u32>>>;
+        /// Blob transactions with an id lower than `before_id` carrying fragments of bundles
+        /// overlapping `heights`, newest first.
+        async fn blob_transactions(
+            &self,
+            before_id: Option<u64>,
+            heights: HeightRange,
+            limit: usize,
+        ) -> Result<Vec<L1Tx>>;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{port::MockStorage, service::BundleReporter};
+    use crate::types::{BundleOverview, HeightRange};
+
+    fn overview(id: u32) -> BundleOverview {
+        BundleOverview {
+            id,
+            start_height: id * 10,
+            end_height: id * 10 + 9,
+            num_fragments: 1,
+            num_finalized_fragments: 0,
+            compressed_size: 100,
+            uncompressed_size: Some(400),
+        }
+    }
+
+    #[tokio::test]
+    async fn full_page_points_to_the_next_one() {
+        // given
+        let mut storage = MockStorage::new();
+        storage
+            .expect_bundles()
+            .withf(|before, _, limit| *before == Some(10) && *limit == 2)
+            .returning(|_, _, _| Box::pin(async { Ok(vec![overview(9), overview(8)]) }));
+        let reporter = BundleReporter::new(storage, 100);
+
+        // when
+        let page = reporter
+            .bundles(Some(10), HeightRange::default(), 2)
+            .await
+            .unwrap();
+
+        // then
+        assert_eq!(page.next_before, Some(8));
+        assert_eq!(page.bundles[0].compression_ratio, Some(4.));
+    }
+
+    #[tokio::test]
+    async fn last_page_has_no_next_one() {
+        // given
+        let mut storage = MockStorage::new();
+        storage
+            .expect_bundles()
+            .returning(|_, _, _| Box::pin(async { Ok(vec![overview(1)]) }));
+        let reporter = BundleReporter::new(storage, 100);
+
+        // when
+        let page = reporter
+            .bundles(None, HeightRange::default(), 2)
+            .await
+            .unwrap();
+
+        // then
+        assert_eq!(page.next_before, None);
+    }
+
+    #[tokio::test]
+    async fn rejects_limits_above_the_request_limit() {
+        // given
+        let reporter = BundleReporter::new(MockStorage::new(), 10);
+
+        // when
+        let result = reporter.bundles(None, HeightRange::default(), 11).await;
+
+        // then
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,654 @@
+pub mod service {
+    use std::{
+        collections::VecDeque,
+        num::NonZeroU32,
+        sync::{
+            Arc, Mutex, PoisonError,
+            atomic::{AtomicBool, Ordering},
+        },
+    };
+
+    use futures::{FutureExt, StreamExt, stream::BoxStream};
+    use metrics::{
+        HealthCheck, HealthChecker, RegistersMetrics,
+        prometheus::{IntCounter, core::Collector},
+    };
+    use tracing::{info, warn};
+
+    use super::port::ObservedCommit;
+    use crate::{Error, Result, Runner};
+
+    /// Commits without a local block rechecked per run, bounding the calls to the Fuel node if many
+    /// pile up.
+    const UNVERIFIED_COMMITS_PER_RUN: usize = 100;
+
+    /// Watches the `CommitSubmitted` events of the state contract and compares every commit to the
+    /// block the Fuel node has at that height. A commit of any other hash, e.g. by a second
+    /// deployment or by hand, is flagged and makes the instance unhealthy until an operator
+    /// acknowledges it. Commits emitted while not subscribed are fetched once subscribed again. A
+    /// commit at a height the Fuel node has no block for yet is recorded as unverified and checked
+    /// again on every run.
+    pub struct CommitWatcher<L1, Fuel, Db, Clock> {
+        l1: L1,
+        fuel: Fuel,
+        storage: Db,
+        clock: Clock,
+        commit_interval: NonZeroU32,
+        // only accessed through `&mut self`, the mutex just makes the watcher `Sync`
+        subscription: Mutex<Option<BoxStream<'static, Result<ObservedCommit>>>>,
+        unchecked: VecDeque<ObservedCommit>,
+        foreign_commit_seen: Arc<AtomicBool>,
+        metrics: Metrics,
+    }
+
+    impl<L1, Fuel, Db, Clock> CommitWatcher<L1, Fuel, Db, Clock> {
+        pub fn new(
+            l1: L1,
+            fuel: Fuel,
+            storage: Db,
+            clock: Clock,
+            commit_interval: NonZeroU32,
+        ) -> Self {
+            Self {
+                l1,
+                fuel,
+                storage,
+                clock,
+                commit_interval,
+                subscription: Mutex::new(None),
+                unchecked: VecDeque::new(),
+                foreign_commit_seen: Arc::new(AtomicBool::new(false)),
+                metrics: Metrics::default(),
+            }
+        }
+
+        /// Unhealthy once a foreign commit was observed. Refreshed from the recorded commits on every
+        /// run, so that acknowledging them in storage clears it on all instances.
+        pub fn health_checker(&self) -> HealthChecker {
+            Box::new(NoForeignCommits(Arc::clone(&self.foreign_commit_seen)))
+        }
+    }
+
+    impl<L1, Fuel, Db, Clock> CommitWatcher<L1, Fuel, Db, Clock>
+    where
+        L1: crate::commit_watcher::port::l1::Api,
+        Fuel: crate::commit_watcher::port::fuel::Api,
+        Db: crate::commit_watcher::port::Storage,
+        Clock: crate::commit_watcher::port::Clock,
+    {
+        /// Moves the commits received so far into `unchecked`, subscribing if needed. A failed or
+        /// closed subscription is dropped so that the next run subscribes again.
+        async fn receive_commits(&mut self) -> Result<()> {
+            if self.subscription().is_none() {
+                // Subscribing first leaves no gap to the backfill, commits found by both are only
+                // recorded once.
+                let subscription = self.l1.subscribe_commits().await?;
+                self.catch_up().await?;
+                *self.subscription() = Some(subscription);
+                info!("subscribed to commits of the state contract");
+            }
+
+            let slot = self
+                .subscription
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner);
+            let subscription = slot.as_mut().expect("just subscribed");
+
+            while let Some(next) = subscription.next().now_or_never() {
+                match next {
+                    Some(Ok(commit)) => self.unchecked.push_back(commit),
+                    Some(Err(e)) => {
+                        *slot = None;
+                        return Err(e);
+                    }
+                    None => {
+                        *slot = None;
+                        return Err(Error::Network(
+                            "subscription to commits of the state contract closed".to_string(),
+                        ));
+                    }
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Queues the commits emitted since the latest recorded one, which no subscription may have
+        /// delivered, e.g. while reconnecting or restarting.
+        async fn catch_up(&mut self) -> Result<()> {
+            let Some(l1_block) = self.storage.latest_observed_commit_l1_block().await? else {
+                return Ok(());
+            };
+
+            let missed = self.l1.commits_since(l1_block).await?;
+            info!(
+                "fetched {} commit(s) of the state contract since L1 block {l1_block}",
+                missed.len()
+            );
+            self.unchecked.extend(missed);
+
+            Ok(())
+        }
+
+        fn subscription(&mut self) -> &mut Option<BoxStream<'static, Result<ObservedCommit>>> {
+            self.subscription
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+        }
+
+        async fn check(&self, commit: ObservedCommit) -> Result<()> {
+            let height = commit
+                .commit_height
+                .checked_mul(self.commit_interval.get())
+                .ok_or_else(|| {
+                    Error::Other(format!(
+                        "commit height {} is out of range of fuel block heights",
+                        commit.commit_height
+                    ))
+                })?;
+
+            let Some(block) = self.fuel.block_at_height(height).await? else {
+                // Waiting for the block would hold up every later commit, it is checked again on the
+                // next runs instead.
+                let newly_recorded = self
+                    .storage
+                    .record_unverified_commit(commit, self.clock.now())
+                    .await?;
+
+                if newly_recorded {
+                    self.metrics.unverifiable_commits.inc();
+                    warn!(
+                        "deferring check of commit of {} at block height: {height} in tx {}, the Fuel node has no block at that height yet",
+                        hex::encode(commit.block_hash),
+                        hex::encode(commit.tx_hash),
+                    );
+                }
+                return Ok(());
+            };
+
+            let foreign = block.id != commit.block_hash;
+            let newly_recorded = self
+                .storage
+                .record_observed_commit(commit, foreign, self.clock.now())
+                .await?;
+
+            if foreign {
+                self.foreign_commit_seen.store(true, Ordering::Relaxed);
+            }
+
+            if foreign && newly_recorded {
+                self.metrics.foreign_commits.inc();
+                warn!(
+                    "foreign commit of {} at block height: {height} in tx {}, expected {}",
+                    hex::encode(commit.block_hash),
+                    hex::encode(commit.tx_hash),
+                    hex::encode(block.id)
+                );
+            }
+
+            Ok(())
+        }
+    }
+
+    impl<L1, Fuel, Db, Clock> Runner for CommitWatcher<L1, Fuel, Db, Clock>
+    where
+        L1: crate::commit_watcher::port::l1::Api + Send + Sync,
+        Fuel: crate::commit_watcher::port::fuel::Api + Send + Sync,
+        Db: crate::commit_watcher::port::Storage + Send + Sync,
+        Clock: crate::commit_watcher::port::Clock + Send + Sync,
+    {
+        async fn run(&mut self) -> Result<()> {
+            let received = self.receive_commits().await;
+
+            for commit in self
+                .storage
+                .unverified_commits(UNVERIFIED_COMMITS_PER_RUN)
+                .await?
+            {
+                self.check(commit).await?;
+            }
+
+            // a commit stays queued until it was checked
+            while let Some(commit) = self.unchecked.front().copied() {
+                self.check(commit).await?;
+                self.unchecked.pop_front();
+            }
+
+            let foreign_commit_seen = self.storage.foreign_commit_observed().await?;
+            self.foreign_commit_seen
+                .store(foreign_commit_seen, Ordering::Relaxed);
+
+            received
+        }
+    }
+
+    impl<L1, Fuel, Db, Clock> RegistersMetrics for CommitWatcher<L1, Fuel, Db, Clock> {
+        fn metrics(&self) -> Vec<Box<dyn Collector>> {
+            vec![
+                Box::new(self.metrics.foreign_commits.clone()),
+                Box::new(self.metrics.unverifiable_commits.clone()),
+            ]
+        }
+    }
+
+    struct NoForeignCommits(Arc<AtomicBool>);
+
+    impl HealthCheck for NoForeignCommits {
+        fn healthy(&self) -> bool {
+            !self.0.load(Ordering::Relaxed)
+        }
+    }
+
+    #[derive(Clone)]
+    struct Metrics {
+        foreign_commits: IntCounter,
+        unverifiable_commits: IntCounter,
+    }
+
+    impl Default for Metrics {
+        fn default() -> Self {
+            let foreign_commits = IntCounter::new(
+                "foreign_commits_observed",
+                "Number of commits on L1 whose block hash differs from the Fuel block at that height",
+            )
+            .expect("metric config to be correct");
+
+            let unverifiable_commits = IntCounter::new(
+                "unverifiable_commits_observed",
+                "Number of commits on L1 whose check was deferred because the Fuel node had no block at their height",
+            )
+            .expect("metric config to be correct");
+
+            Self {
+                foreign_commits,
+                unverifiable_commits,
+            }
+        }
+    }
+}
+
+pub mod port {
+    use crate::types::{DateTime, Utc};
+
+    /// A `CommitSubmitted` event emitted by the state contract.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ObservedCommit {
+        pub commit_height: u32,
+        pub block_hash: [u8; 32],
+        pub tx_hash: [u8; 32],
+        pub log_index: u64,
+        pub l1_block: u64,
+    }
+
+    pub mod l1 {
+        use futures::stream::BoxStream;
+
+        use super::ObservedCommit;
+        use crate::Result;
+
+        #[allow(async_fn_in_trait)]
+        #[trait_variant::make(Send)]
+        #[cfg_attr(feature = "test-helpers", mockall::automock)]
+        pub trait Api: Sync {
+            /// Commits as they are included on L1. Commits removed by a reorg are left out.
+            async fn subscribe_commits(&self)
+            -> Result<BoxStream<'static, Result<ObservedCommit>>>;
+            /// Commits included in L1 blocks from `l1_block` on.
+            async fn commits_since(&self, l1_block: u64) -> Result<Vec<ObservedCommit>>;
+        }
+    }
+
+    pub mod fuel {
+        use crate::{Result, block_committer::port::fuel::FuelBlock};
+
+        #[allow(async_fn_in_trait)]
+        #[trait_variant::make(Send)]
+        #[cfg_attr(feature = "test-helpers", mockall::automock)]
+        pub trait Api: Sync {
+            async fn block_at_height(&self, height: u32) -> Result<Option<FuelBlock>>;
+        }
+    }
+
+    #[allow(async_fn_in_trait)]
+    #[trait_variant::make(Send)]
+    #[cfg_attr(feature = "test-helpers", mockall::automock)]
+    pub trait Storage: Sync {
+        /// Returns whether the commit wasn't recorded before, or only as unverified. Observing the
+        /// same commit again, e.g. when it is both fetched and received over a subscription, must
+        /// not fail.
+        async fn record_observed_commit(
+            &self,
+            commit: ObservedCommit,
+            foreign: bool,
+            observed_at: DateTime<Utc>,
+        ) -> crate::Result<bool>;
+        /// Records a commit that can't be checked yet, to be returned by `unverified_commits` until
+        /// it is recorded through `record_observed_commit`. Returns whether it wasn't recorded
+        /// before.
+        async fn record_unverified_commit(
+            &self,
+            commit: ObservedCommit,
+            observed_at: DateTime<Utc>,
+        ) -> crate::Result<bool>;
+        /// Up to `limit` unverified commits, oldest first.
+        async fn unverified_commits(&self, limit: usize) -> crate::Result<Vec<ObservedCommit>>;
+        /// L1 block of the latest recorded commit, unverified ones included.
+        async fn latest_observed_commit_l1_block(&self) -> crate::Result<Option<u64>>;
+        /// Whether any recorded commit is foreign and wasn't acknowledged.
+        async fn foreign_commit_observed(&self) -> crate::Result<bool>;
+    }
+
+    pub trait Clock {
+        fn now(&self) -> DateTime<Utc>;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{StreamExt, stream};
+    use mockall::predicate::eq;
+
+    use super::{
+        port::{Clock, MockStorage, ObservedCommit, fuel::MockApi as MockFuelApi, l1::MockApi},
+        service::CommitWatcher,
+    };
+    use crate::{
+        Runner,
+        block_committer::port::fuel::FuelBlock,
+        types::{DateTime, Utc},
+    };
+
+    struct TestClock;
+
+    impl Clock for TestClock {
+        fn now(&self) -> DateTime<Utc> {
+            DateTime::UNIX_EPOCH
+        }
+    }
+
+    fn commit(commit_height: u32, block_hash: [u8; 32]) -> ObservedCommit {
+        ObservedCommit {
+            commit_height,
+            block_hash,
+            tx_hash: [commit_height as u8; 32],
+            log_index: 0,
+            l1_block: 100,
+        }
+    }
+
+    fn l1_with(commits: Vec<ObservedCommit>) -> MockApi {
+        let mut l1 = MockApi::new();
+        l1.expect_subscribe_commits().return_once(move || {
+            Box::pin(async move {
+                let pending = stream::pending();
+                Ok(stream::iter(commits.into_iter().map(Ok))
+                    .chain(pending)
+                    .boxed())
+            })
+        });
+        l1
+    }
+
+    fn fuel_with(height: u32, id: [u8; 32]) -> MockFuelApi {
+        let mut fuel = MockFuelApi::new();
+        fuel.expect_block_at_height()
+            .with(eq(height))
+            .returning(move |height| Box::pin(async move { Ok(Some(FuelBlock { id, height })) }));
+        fuel
+    }
+
+    fn storage_with(latest_l1_block: Option<u64>, foreign_commit_observed: bool) -> MockStorage {
+        let mut storage = MockStorage::new();
+        storage
+            .expect_latest_observed_commit_l1_block()
+            .returning(move || Box::pin(async move { Ok(latest_l1_block) }));
+        storage
+            .expect_foreign_commit_observed()
+            .returning(move || Box::pin(async move { Ok(foreign_commit_observed) }));
+        storage
+            .expect_unverified_commits()
+            .returning(|_| Box::pin(async { Ok(vec![]) }));
+        storage
+    }
+
+    fn storage_with_no_commits(mut storage: MockStorage) -> MockStorage {
+        storage
+            .expect_latest_observed_commit_l1_block()
+            .returning(|| Box::pin(async { Ok(None) }));
+        storage
+            .expect_unverified_commits()
+            .returning(|_| Box::pin(async { Ok(vec![]) }));
+        storage
+    }
+
+    #[tokio::test]
+    async fn matching_commit_is_recorded_and_keeps_the_instance_healthy() {
+        // given
+        let l1 = l1_with(vec![commit(3, [1; 32])]);
+        let fuel = fuel_with(30, [1; 32]);
+        let mut storage = storage_with(None, false);
+        storage
+            .expect_record_observed_commit()
+            .withf(|commit, foreign, _| commit.commit_height == 3 && !foreign)
+            .return_once(|_, _, _| Box::pin(async { Ok(true) }))
+            .once();
+
+        let mut watcher = CommitWatcher::new(l1, fuel, storage, TestClock, 10.try_into().unwrap());
+        let health = watcher.health_checker();
+
+        // when
+        watcher.run().await.unwrap();
+
+        // then
+        assert!(health.healthy());
+    }
+
+    #[tokio::test]
+    async fn foreign_commit_makes_the_instance_unhealthy() {
+        // given
+        let l1 = l1_with(vec![commit(3, [2; 32])]);
+        let fuel = fuel_with(30, [1; 32]);
+        let mut storage = storage_with(None, true);
+        storage
+            .expect_record_observed_commit()
+            .withf(|commit, foreign, _| commit.block_hash == [2; 32] && *foreign)
+            .return_once(|_, _, _| Box::pin(async { Ok(true) }))
+            .once();
+
+        let mut watcher = CommitWatcher::new(l1, fuel, storage, TestClock, 10.try_into().unwrap());
+        let health = watcher.health_checker();
+
+        // when
+        watcher.run().await.unwrap();
+
+        // then
+        assert!(!health.healthy());
+    }
+
+    #[tokio::test]
+    async fn commits_missed_while_not_subscribed_are_checked() {
+        // given
+        let mut l1 = l1_with(vec![]);
+        l1.expect_commits_since()
+            .with(eq(90))
+            .return_once(|_| Box::pin(async { Ok(vec![commit(3, [2; 32])]) }))
+            .once();
+        let fuel = fuel_with(30, [1; 32]);
+        let mut storage = storage_with(Some(90), true);
+        storage
+            .expect_record_observed_commit()
+            .withf(|commit, foreign, _| commit.commit_height == 3 && *foreign)
+            .return_once(|_, _, _| Box::pin(async { Ok(true) }))
+            .once();
+
+        let mut watcher = CommitWatcher::new(l1, fuel, storage, TestClock, 10.try_into().unwrap());
+        let health = watcher.health_checker();
+
+        // when
+        watcher.run().await.unwrap();
+
+        // then
+        assert!(!health.healthy());
+    }
+
+    #[tokio::test]
+    async fn recorded_foreign_commit_keeps_the_instance_unhealthy_after_a_restart() {
+        // given
+        let mut l1 = l1_with(vec![]);
+        l1.expect_commits_since()
+            .return_once(|_| Box::pin(async { Ok(vec![]) }));
+        let storage = storage_with(Some(100), true);
+
+        let mut watcher = CommitWatcher::new(
+            l1,
+            MockFuelApi::new(),
+            storage,
+            TestClock,
+            10.try_into().unwrap(),
+        );
+        let health = watcher.health_checker();
+
+        // when
+        watcher.run().await.unwrap();
+
+        // then
+        assert!(!health.healthy());
+    }
+
+    #[tokio::test]
+    async fn commit_without_a_local_block_is_recorded_as_unverified() {
+        // given
+        let l1 = l1_with(vec![commit(3, [2; 32]), commit(4, [1; 32])]);
+        let mut fuel = fuel_with(40, [1; 32]);
+        fuel.expect_block_at_height()
+            .with(eq(30))
+            .returning(|_| Box::pin(async { Ok(None) }));
+        let mut storage = storage_with(None, false);
+        storage
+            .expect_record_unverified_commit()
+            .withf(|commit, _| commit.commit_height == 3)
+            .return_once(|_, _| Box::pin(async { Ok(true) }))
+            .once();
+        storage
+            .expect_record_observed_commit()
+            .withf(|commit, foreign, _| commit.commit_height == 4 && !foreign)
+            .return_once(|_, _, _| Box::pin(async { Ok(true) }))
+            .once();
+
+        let mut watcher = CommitWatcher::new(l1, fuel, storage, TestClock, 10.try_into().unwrap());
+        let health = watcher.health_checker();
+
+        // when
+        watcher.run().await.unwrap();
+
+        // then
+        assert!(health.healthy());
+    }
+
+    #[tokio::test]
+    async fn unverified_commit_is_checked_once_its_block_is_known() {
+        // given
+        let l1 = l1_with(vec![commit(3, [2; 32]), commit(4, [1; 32])]);
+        let mut fuel = fuel_with(40, [1; 32]);
+        let mut block_known = false;
+        fuel.expect_block_at_height()
+            .with(eq(30))
+            .times(2)
+            .returning(move |height| {
+                let block = block_known.then_some(FuelBlock {
+                    id: [1; 32],
+                    height,
+                });
+                block_known = true;
+                Box::pin(async move { Ok(block) })
+            });
+        let mut storage = MockStorage::new();
+        storage
+            .expect_latest_observed_commit_l1_block()
+            .returning(|| Box::pin(async { Ok(None) }));
+        let mut recorded_unverified = false;
+        storage
+            .expect_unverified_commits()
+            .times(2)
+            .returning(move |_| {
+                let unverified = if recorded_unverified {
+                    vec![commit(3, [2; 32])]
+                } else {
+                    vec![]
+                };
+                recorded_unverified = true;
+                Box::pin(async move { Ok(unverified) })
+            });
+        storage
+            .expect_record_unverified_commit()
+            .withf(|commit, _| commit.commit_height == 3)
+            .return_once(|_, _| Box::pin(async { Ok(true) }))
+            .once();
+        // the later commit is recorded first, so resubscribing wouldn't fetch the earlier one again
+        storage
+            .expect_record_observed_commit()
+            .withf(|commit, foreign, _| commit.commit_height == 4 && !foreign)
+            .return_once(|_, _, _| Box::pin(async { Ok(true) }))
+            .once();
+        storage
+            .expect_record_observed_commit()
+            .withf(|commit, foreign, _| commit.commit_height == 3 && *foreign)
+            .return_once(|_, _, _| Box::pin(async { Ok(true) }))
+            .once();
+        let mut checked_before = false;
+        storage
+            .expect_foreign_commit_observed()
+            .times(2)
+            .returning(move || {
+                let observed = checked_before;
+                checked_before = true;
+                Box::pin(async move { Ok(observed) })
+            });
+
+        let mut watcher = CommitWatcher::new(l1, fuel, storage, TestClock, 10.try_into().unwrap());
+        let health = watcher.health_checker();
+        watcher.run().await.unwrap();
+        let healthy_before = health.healthy();
+
+        // when
+        watcher.run().await.unwrap();
+
+        // then
+        assert!(healthy_before);
+        assert!(!health.healthy());
+    }
+
+    #[tokio::test]
+    async fn acknowledged_foreign_commits_make_the_instance_healthy_again() {
+        // given
+        let l1 = l1_with(vec![]);
+        let mut storage = MockStorage::new();
+        let mut acknowledged = false;
+        storage
+            .expect_foreign_commit_observed()
+            .times(2)
+            .returning(move || {
+                let observed = !acknowledged;
+                acknowledged = true;
+                Box::pin(async move { Ok(observed) })
+            });
+
+        let mut watcher = CommitWatcher::new(
+            l1,
+            MockFuelApi::new(),
+            storage_with_no_commits(storage),
+            TestClock,
+            10.try_into().unwrap(),
+        );
+        let health = watcher.health_checker();
+        watcher.run().await.unwrap();
+        let healthy_before = health.healthy();
+
+        // when
+        watcher.run().await.unwrap();
+
+        // then
+        assert!(!healthy_before);
+        assert!(health.healthy());
+    }
+}
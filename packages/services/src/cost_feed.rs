@@ -0,0 +1,334 @@
+pub mod service {
+    use metrics::{
+        RegistersMetrics,
+        prometheus::{
+            Histogram, HistogramOpts, IntCounter, IntGauge, Opts, core::Collector,
+            exponential_buckets,
+        },
+    };
+    use tracing::{info, warn};
+
+    use super::port::UndeliveredCost;
+    use crate::{Result, Runner};
+
+    /// Age of the oldest undelivered cost from which on every run warns about the backlog.
+    const STALE_AFTER_SECS: i64 = 60 * 60;
+
+    /// Pushes the cost of every finalized bundle to a sink, e.g. the DA gas price service of
+    /// fuel-core. The costs are queued in an outbox when they are finalized and only removed once
+    /// delivered, so every cost is delivered at least once and in the order of the bundles. A
+    /// failed delivery is retried on the next run without failing this one, so that an unreachable
+    /// sink shows in the backlog metrics rather than in the liveness of the instance.
+    pub struct CostFeed<Sink, Db, Clock> {
+        sink: Sink,
+        storage: Db,
+        clock: Clock,
+        batch_size: usize,
+        metrics: Metrics,
+    }
+
+    impl<Sink, Db, Clock> CostFeed<Sink, Db, Clock> {
+        pub fn new(sink: Sink, storage: Db, clock: Clock, batch_size: usize) -> Self {
+            Self {
+                sink,
+                storage,
+                clock,
+                batch_size,
+                metrics: Metrics::default(),
+            }
+        }
+    }
+
+    impl<Sink, Db, Clock> CostFeed<Sink, Db, Clock>
+    where
+        Sink: crate::cost_feed::port::Sink,
+        Db: crate::cost_feed::port::Storage,
+        Clock: crate::cost_feed::port::Clock,
+    {
+        /// Returns whether the cost was delivered. Only failing to access the storage is an error.
+        async fn deliver(&self, undelivered: UndeliveredCost) -> Result<bool> {
+            let bundle_id = undelivered.cost.id;
+
+            if let Err(e) = self.sink.deliver(&undelivered.cost).await {
+                self.metrics.delivery_failures.inc();
+                warn!(
+                    "failed to deliver the cost of bundle {bundle_id} (attempt {}): {e}",
+                    undelivered.attempts + 1
+                );
+                self.storage
+                    .record_cost_delivery_failure(bundle_id, e.to_string())
+                    .await?;
+
+                return Ok(false);
+            }
+
+            self.storage.mark_cost_delivered(bundle_id).await?;
+
+            let lag = self.clock.now() - undelivered.finalized_at;
+            self.metrics
+                .delivery_lag
+                .observe(lag.num_milliseconds().max(0) as f64 / 1000.);
+
+            info!("delivered the cost of bundle {bundle_id}");
+
+            Ok(true)
+        }
+
+        async fn update_backlog_metrics(&self) -> Result<()> {
+            let backlog = self.storage.undelivered_cost_backlog().await?;
+
+            self.metrics
+                .undelivered
+                .set(i64::try_from(backlog.count).unwrap_or(i64::MAX));
+
+            let oldest_age = backlog
+                .oldest_finalized_at
+                .map(|finalized_at| (self.clock.now() - finalized_at).num_seconds().max(0))
+                .unwrap_or(0);
+            self.metrics.oldest_undelivered_age.set(oldest_age);
+
+            if oldest_age >= STALE_AFTER_SECS {
+                warn!(
+                    "{} bundle cost(s) not delivered, the oldest was finalized {oldest_age}s ago",
+                    backlog.count
+                );
+            }
+
+            Ok(())
+        }
+    }
+
+    impl<Sink, Db, Clock> Runner for CostFeed<Sink, Db, Clock>
+    where
+        Sink: crate::cost_feed::port::Sink,
+        Db: crate::cost_feed::port::Storage,
+        Clock: crate::cost_feed::port::Clock + Send + Sync,
+    {
+        async fn run(&mut self) -> Result<()> {
+            let undelivered = self.storage.undelivered_costs(self.batch_size).await?;
+
+            let delivered = async {
+                for cost in undelivered {
+                    // the next costs are delivered after it, keeping them in order
+                    if !self.deliver(cost).await? {
+                        break;
+                    }
+                }
+                Ok(())
+            }
+            .await;
+
+            self.update_backlog_metrics().await?;
+
+            delivered
+        }
+    }
+
+    #[derive(Clone)]
+    struct Metrics {
+        delivery_lag: Histogram,
+        delivery_failures: IntCounter,
+        undelivered: IntGauge,
+        oldest_undelivered_age: IntGauge,
+    }
+
+    impl Default for Metrics {
+        fn default() -> Self {
+            let delivery_lag = Histogram::with_opts(
+                HistogramOpts::new(
+                    "cost_feed_delivery_lag_seconds",
+                    "Time between the finalization of a bundle and the delivery of its cost.",
+                )
+                .buckets(exponential_buckets(1.0, 2.0, 14).expect("to be correctly configured")),
+            )
+            .expect("cost_feed_delivery_lag_seconds metric to be correctly configured");
+
+            let delivery_failures = IntCounter::with_opts(Opts::new(
+                "cost_feed_delivery_failures",
+                "Number of failed attempts to deliver a bundle cost.",
+            ))
+            .expect("cost_feed_delivery_failures metric to be correctly configured");
+
+            let undelivered = IntGauge::with_opts(Opts::new(
+                "cost_feed_undelivered",
+                "Number of finalized bundle costs waiting to be delivered.",
+            ))
+            .expect("cost_feed_undelivered metric to be correctly configured");
+
+            let oldest_undelivered_age = IntGauge::with_opts(Opts::new(
+                "cost_feed_oldest_undelivered_seconds",
+                "Seconds since the finalization of the oldest bundle whose cost wasn't delivered yet.",
+            ))
+            .expect("cost_feed_oldest_undelivered_seconds metric to be correctly configured");
+
+            Self {
+                delivery_lag,
+                delivery_failures,
+                undelivered,
+                oldest_undelivered_age,
+            }
+        }
+    }
+
+    impl<Sink, Db, Clock> RegistersMetrics for CostFeed<Sink, Db, Clock> {
+        fn metrics(&self) -> Vec<Box<dyn Collector>> {
+            vec![
+                Box::new(self.metrics.delivery_lag.clone()),
+                Box::new(self.metrics.delivery_failures.clone()),
+                Box::new(self.metrics.undelivered.clone()),
+                Box::new(self.metrics.oldest_undelivered_age.clone()),
+            ]
+        }
+    }
+}
+
+pub mod port {
+    use crate::{
+        Result,
+        types::{BundleCost, DateTime, Utc},
+    };
+
+    #[derive(Debug)]
+    pub struct UndeliveredCost {
+        pub cost: BundleCost,
+        pub finalized_at: DateTime<Utc>,
+        /// Number of failed deliveries so far.
+        pub attempts: u32,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct CostBacklog {
+        pub count: u64,
+        pub oldest_finalized_at: Option<DateTime<Utc>>,
+    }
+
+    #[allow(async_fn_in_trait)]
+    #[trait_variant::make(Send)]
+    #[cfg_attr(feature = "test-helpers", mockall::automock)]
+    pub trait Sink: Sync {
+        /// Must only succeed once the cost was accepted. Receivers should expect duplicates and
+        /// deduplicate by bundle id.
+        async fn deliver(&self, cost: &BundleCost) -> Result<()>;
+    }
+
+    #[allow(async_fn_in_trait)]
+    #[trait_variant::make(Send)]
+    #[cfg_attr(feature = "test-helpers", mockall::automock)]
+    pub trait Storage: Sync {
+        /// Costs not delivered yet, lowest bundle id first.
+        async fn undelivered_costs(&self, limit: usize) -> Result<Vec<UndeliveredCost>>;
+        async fn undelivered_cost_backlog(&self) -> Result<CostBacklog>;
+        async fn mark_cost_delivered(&self, bundle_id: u64) -> Result<()>;
+        async fn record_cost_delivery_failure(&self, bundle_id: u64, error: String) -> Result<()>;
+    }
+
+    pub trait Clock {
+        fn now(&self) -> DateTime<Utc>;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::{
+        port::{Clock, CostBacklog, MockSink, MockStorage, UndeliveredCost},
+        service::CostFeed,
+    };
+    use crate::{
+        Error, Runner,
+        types::{BundleCost, DateTime, Utc},
+    };
+
+    struct TestClock;
+
+    impl Clock for TestClock {
+        fn now(&self) -> DateTime<Utc> {
+            DateTime::UNIX_EPOCH
+        }
+    }
+
+    fn undelivered(id: u64) -> UndeliveredCost {
+        UndeliveredCost {
+            cost: BundleCost {
+                id,
+                cost: 1000,
+                size: 100,
+                da_block_height: 10,
+                start_height: id * 10,
+                end_height: id * 10 + 9,
+            },
+            finalized_at: DateTime::UNIX_EPOCH,
+            attempts: 0,
+        }
+    }
+
+    fn storage_with(costs: Vec<u64>) -> MockStorage {
+        let mut storage = MockStorage::new();
+        storage.expect_undelivered_costs().return_once(move |_| {
+            Box::pin(async move { Ok(costs.into_iter().map(undelivered).collect()) })
+        });
+        storage
+            .expect_undelivered_cost_backlog()
+            .returning(|| Box::pin(async { Ok(CostBacklog::default()) }));
+        storage
+    }
+
+    #[tokio::test]
+    async fn costs_are_delivered_in_order_and_removed_from_the_outbox() {
+        // given
+        let mut storage = storage_with(vec![1, 2]);
+        let delivered_ids = Arc::new(Mutex::new(vec![]));
+        let marked = delivered_ids.clone();
+        storage
+            .expect_mark_cost_delivered()
+            .times(2)
+            .returning(move |id| {
+                marked.lock().unwrap().push(id);
+                Box::pin(async { Ok(()) })
+            });
+
+        let mut sink = MockSink::new();
+        let mut seq = mockall::Sequence::new();
+        for id in [1, 2] {
+            sink.expect_deliver()
+                .withf(move |cost| cost.id == id)
+                .times(1)
+                .in_sequence(&mut seq)
+                .returning(|_| Box::pin(async { Ok(()) }));
+        }
+
+        let mut feed = CostFeed::new(sink, storage, TestClock, 10);
+
+        // when
+        feed.run().await.unwrap();
+
+        // then
+        assert_eq!(*delivered_ids.lock().unwrap(), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn failed_delivery_is_recorded_and_stops_the_batch_without_failing_the_run() {
+        // given
+        let mut storage = storage_with(vec![1, 2]);
+        storage
+            .expect_record_cost_delivery_failure()
+            .withf(|id, error| *id == 1 && error.contains("connection refused"))
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(()) }));
+        // the mock fails the test if a cost is marked as delivered
+
+        let mut sink = MockSink::new();
+        sink.expect_deliver()
+            .times(1)
+            .returning(|_| Box::pin(async { Err(Error::Network("connection refused".into())) }));
+
+        let mut feed = CostFeed::new(sink, storage, TestClock, 10);
+
+        // when
+        let result = feed.run().await;
+
+        // then
+        assert!(result.is_ok());
+    }
+}
@@ -1,7 +1,94 @@
 pub mod service {
+    use serde::Serialize;
+    use utoipa::ToSchema;
 
+    use super::port::{CostAggregate, CostBucket, CostFilter};
     use crate::{Error, Result, types::BundleCost};
 
+    /// Aggregated costs of the finalized bundles, over the whole range or a single bucket.
+    #[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+    pub struct CostSummary {
+        /// Start of the bucket, absent for the totals of the whole range.
+        pub bucket_start: Option<String>,
+        pub bundles: u64,
+        /// In wei.
+        pub total_cost: u128,
+        /// Bundle data posted to L1, excluding blob padding.
+        pub bytes_posted: u64,
+        pub blocks_covered: u64,
+        /// In wei, rounded down.
+        pub avg_cost_per_byte: u128,
+        /// In wei, rounded down.
+        pub avg_cost_per_block: u128,
+        /// Share of the blob space taken by bundle data, between 0 and 1.
+        pub blob_utilization: f64,
+    }
+
+    impl From<CostAggregate> for CostSummary {
+        fn from(aggregate: CostAggregate) -> Self {
+            let per = |divisor: u64| {
+                aggregate
+                    .total_cost
+                    .checked_div(u128::from(divisor))
+                    .unwrap_or_default()
+            };
+
+            let blob_utilization = if aggregate.blob_bytes == 0 {
+                0.
+            } else {
+                aggregate.used_blob_bytes as f64 / aggregate.blob_bytes as f64
+            };
+
+            Self {
+                bucket_start: aggregate.bucket_start.map(|at| at.to_rfc3339()),
+                bundles: aggregate.bundles,
+                total_cost: aggregate.total_cost,
+                bytes_posted: aggregate.bytes_posted,
+                blocks_covered: aggregate.blocks_covered,
+                avg_cost_per_byte: per(aggregate.bytes_posted),
+                avg_cost_per_block: per(aggregate.blocks_covered),
+                blob_utilization,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+    pub struct CostAnalytics {
+        pub totals: CostSummary,
+        /// Oldest first, only filled when bucketing was requested. Buckets without finalized
+        /// bundles are omitted.
+        pub buckets: Vec<CostSummary>,
+    }
+
+    impl CostAnalytics {
+        /// One row per bucket, or a single row with the totals if there are no buckets.
+        pub fn to_csv(&self) -> String {
+            let header = "bucket_start,bundles,total_cost,bytes_posted,blocks_covered,\
+                          avg_cost_per_byte,avg_cost_per_block,blob_utilization\n";
+
+            let rows = if self.buckets.is_empty() {
+                std::slice::from_ref(&self.totals)
+            } else {
+                self.buckets.as_slice()
+            };
+
+            rows.iter().fold(header.to_string(), |mut csv, row| {
+                csv.push_str(&format!(
+                    "{},{},{},{},{},{},{},{:.4}\n",
+                    row.bucket_start.as_deref().unwrap_or_default(),
+                    row.bundles,
+                    row.total_cost,
+                    row.bytes_posted,
+                    row.blocks_covered,
+                    row.avg_cost_per_byte,
+                    row.avg_cost_per_block,
+                    row.blob_utilization
+                ));
+                csv
+            })
+        }
+    }
+
     pub struct CostReporter<Db> {
         storage: Db,
         request_limit: usize,
@@ -47,11 +134,134 @@ pub mod service {
 
             self.storage.get_latest_costs(limit).await
         }
+
+        /// Aggregates the costs of the finalized bundles matching `filter`, optionally per `bucket`
+        /// of finalization time.
+        pub async fn analytics(
+            &self,
+            filter: CostFilter,
+            bucket: Option<CostBucket>,
+        ) -> Result<CostAnalytics> {
+            filter.validate()?;
+
+            let aggregates = self.storage.cost_aggregates(filter, bucket).await?;
+
+            let totals =
+                aggregates
+                    .iter()
+                    .fold(CostAggregate::default(), |mut totals, aggregate| {
+                        totals.bundles += aggregate.bundles;
+                        totals.total_cost = totals.total_cost.saturating_add(aggregate.total_cost);
+                        totals.bytes_posted += aggregate.bytes_posted;
+                        totals.blocks_covered += aggregate.blocks_covered;
+                        totals.blob_bytes += aggregate.blob_bytes;
+                        totals.used_blob_bytes += aggregate.used_blob_bytes;
+                        totals
+                    });
+
+            let buckets = if bucket.is_some() {
+                if aggregates.len() > self.request_limit {
+                    return Err(Error::Other(format!(
+                        "range spans {} buckets, but limit is: {}",
+                        aggregates.len(),
+                        self.request_limit
+                    )));
+                }
+
+                aggregates.into_iter().map(CostSummary::from).collect()
+            } else {
+                vec![]
+            };
+
+            Ok(CostAnalytics {
+                totals: totals.into(),
+                buckets,
+            })
+        }
+
+        /// `None` until every transaction carrying the bundle is finalized.
+        pub async fn bundle_cost(&self, bundle_id: u32) -> Result<Option<BundleCost>> {
+            self.storage.finalized_bundle_cost(bundle_id).await
+        }
     }
 }
 
 pub mod port {
-    use crate::{Result, types::BundleCost};
+    use crate::{
+        Error, Result,
+        types::{BundleCost, DateTime, HeightRange, Utc},
+    };
+
+    /// Selects finalized bundles. Every bound is inclusive, except for `finalized_before`.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct CostFilter {
+        pub finalized_from: Option<DateTime<Utc>>,
+        pub finalized_before: Option<DateTime<Utc>>,
+        /// Compared to the DA height of the last transaction carrying the bundle.
+        pub from_da_height: Option<u64>,
+        pub to_da_height: Option<u64>,
+        /// Bundles with at least one block in the range.
+        pub l2_heights: HeightRange,
+    }
+
+    impl CostFilter {
+        pub(crate) fn validate(&self) -> Result<()> {
+            let empty_window = self
+                .finalized_from
+                .zip(self.finalized_before)
+                .is_some_and(|(from, before)| from >= before);
+            let empty_da_range = self
+                .from_da_height
+                .zip(self.to_da_height)
+                .is_some_and(|(from, to)| from > to);
+            let empty_l2_range = self
+                .l2_heights
+                .from
+                .zip(self.l2_heights.to)
+                .is_some_and(|(from, to)| from > to);
+
+            if empty_window || empty_da_range || empty_l2_range {
+                return Err(Error::Other(
+                    "range start must not be after its end".to_string(),
+                ));
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Groups bundles by when they were finalized, in UTC. Weeks start on Monday.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum CostBucket {
+        Hour,
+        Day,
+        Week,
+    }
+
+    impl CostBucket {
+        /// The unit as understood by Postgres' `date_trunc`.
+        pub fn unit(&self) -> &'static str {
+            match self {
+                Self::Hour => "hour",
+                Self::Day => "day",
+                Self::Week => "week",
+            }
+        }
+    }
+
+    /// Sums over the bundles of one bucket.
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct CostAggregate {
+        pub bucket_start: Option<DateTime<Utc>>,
+        pub bundles: u64,
+        pub total_cost: u128,
+        pub bytes_posted: u64,
+        pub blocks_covered: u64,
+        /// Size of the blobs the fragments of the bundles were posted in.
+        pub blob_bytes: u64,
+        /// Part of `blob_bytes` holding bundle data.
+        pub used_blob_bytes: u64,
+    }
 
     #[allow(async_fn_in_trait)]
     #[trait_variant::make(Send)]
@@ -63,5 +273,77 @@ pub mod port {
         ) -> Result<Vec<BundleCost>>;
 
         async fn get_latest_costs(&self, limit: usize) -> Result<Vec<BundleCost>>;
+
+        async fn finalized_bundle_cost(&self, bundle_id: u32) -> Result<Option<BundleCost>>;
+
+        /// One aggregate per bucket, oldest first, or a single one without bucketing. Nothing
+        /// is returned if no bundle matches.
+        async fn cost_aggregates(
+            &self,
+            filter: CostFilter,
+            bucket: Option<CostBucket>,
+        ) -> Result<Vec<CostAggregate>>;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        port::CostAggregate,
+        service::{CostAnalytics, CostSummary},
+    };
+    use crate::types::{DateTime, Utc};
+
+    fn aggregate(bucket_start: Option<DateTime<Utc>>) -> CostAggregate {
+        CostAggregate {
+            bucket_start,
+            bundles: 2,
+            total_cost: 1000,
+            bytes_posted: 300,
+            blocks_covered: 7,
+            blob_bytes: 400,
+            used_blob_bytes: 300,
+        }
+    }
+
+    #[test]
+    fn averages_are_rounded_down() {
+        // when
+        let summary = CostSummary::from(aggregate(None));
+
+        // then
+        assert_eq!(summary.avg_cost_per_byte, 3);
+        assert_eq!(summary.avg_cost_per_block, 142);
+        assert_eq!(summary.blob_utilization, 0.75);
+    }
+
+    #[test]
+    fn nothing_posted_has_no_averages() {
+        // when
+        let summary = CostSummary::from(CostAggregate::default());
+
+        // then
+        assert_eq!(summary.avg_cost_per_byte, 0);
+        assert_eq!(summary.avg_cost_per_block, 0);
+        assert_eq!(summary.blob_utilization, 0.);
+    }
+
+    #[test]
+    fn csv_has_a_row_per_bucket() {
+        // given
+        let analytics = CostAnalytics {
+            totals: aggregate(None).into(),
+            buckets: vec![aggregate(Some(DateTime::UNIX_EPOCH)).into()],
+        };
+
+        // when
+        let csv = analytics.to_csv();
+
+        // then
+        assert_eq!(
+            csv,
+            "bucket_start,bundles,total_cost,bytes_posted,blocks_covered,avg_cost_per_byte,avg_cost_per_block,blob_utilization\n\
+             1970-01-01T00:00:00+00:00,2,1000,300,7,3,142,0.7500\n"
+        );
     }
 }
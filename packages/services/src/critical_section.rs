@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use tokio::sync::{OwnedRwLockReadGuard, RwLock};
+
+/// Tracks work that must run to completion once started, such as broadcasting an L1 transaction
+/// and recording it in the database. Interrupting it in between would leave a transaction on L1
+/// that the committer knows nothing about.
+#[derive(Debug, Clone, Default)]
+pub struct CriticalSections {
+    closed: Arc<RwLock<bool>>,
+}
+
+/// Keeps the critical section open until dropped.
+#[derive(Debug)]
+pub struct CriticalSection {
+    _guard: OwnedRwLockReadGuard<bool>,
+}
+
+impl CriticalSections {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `None` once [`CriticalSections::close`] was called, in which case the work should
+    /// not be started.
+    pub async fn enter(&self) -> Option<CriticalSection> {
+        let guard = Arc::clone(&self.closed).read_owned().await;
+
+        (!*guard).then_some(CriticalSection { _guard: guard })
+    }
+
+    /// Waits for all entered sections to finish and refuses any new ones.
+    pub async fn close(&self) {
+        *self.closed.write().await = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::CriticalSections;
+
+    #[tokio::test]
+    async fn cannot_enter_once_closed() {
+        // given
+        let sections = CriticalSections::new();
+        sections.close().await;
+
+        // when
+        let section = sections.enter().await;
+
+        // then
+        assert!(section.is_none());
+    }
+
+    #[tokio::test]
+    async fn closing_waits_for_entered_sections() {
+        // given
+        let sections = CriticalSections::new();
+        let section = sections.enter().await.unwrap();
+
+        // when
+        let closing = tokio::time::timeout(Duration::from_millis(50), sections.close()).await;
+
+        // then
+        assert!(closing.is_err());
+        drop(section);
+        sections.close().await;
+        assert!(sections.enter().await.is_none());
+    }
+}
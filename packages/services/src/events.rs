@@ -0,0 +1,376 @@
+pub mod service {
+    use std::{
+        collections::VecDeque,
+        sync::{Arc, Mutex},
+    };
+
+    use serde::{Deserialize, Serialize};
+    use tokio::sync::Notify;
+    use tracing::warn;
+
+    use crate::{
+        Result, Runner,
+        types::{CollectNonEmpty, DateTime, Utc},
+    };
+
+    /// Events kept in memory while they cannot be recorded, e.g. because the database is down.
+    /// The oldest ones are dropped first.
+    const MAX_PENDING_EVENTS: usize = 10_000;
+
+    /// Milestones of blocks on their way to L1. Hashes are hex encoded with a `0x` prefix.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(tag = "type")]
+    pub enum Event {
+        BundleCreated {
+            bundle_id: u32,
+            start_height: u32,
+            end_height: u32,
+            num_fragments: usize,
+        },
+        FragmentsSubmitted {
+            tx_hash: String,
+            nonce: u32,
+            fragment_ids: Vec<u32>,
+        },
+        /// A pending blob or block commit transaction was replaced by one with higher fees.
+        TxReplaced {
+            replaced_tx_hash: String,
+            tx_hash: String,
+            nonce: u32,
+        },
+        TxIncluded {
+            tx_hash: String,
+            nonce: u32,
+            l1_block: u64,
+        },
+        TxFinalized {
+            tx_hash: String,
+            nonce: u32,
+            l1_block: u64,
+            /// In wei, as a decimal string.
+            total_fee: String,
+        },
+        TxFailed {
+            tx_hash: String,
+            nonce: u32,
+        },
+        /// The transaction left the mempool without being included.
+        TxSqueezedOut {
+            tx_hash: String,
+            nonce: u32,
+        },
+        /// The commit of the fuel block to the contract was finalized.
+        BlockCommitted {
+            block_height: u32,
+            tx_hash: String,
+        },
+    }
+
+    impl Event {
+        pub fn name(&self) -> &'static str {
+            match self {
+                Self::BundleCreated { .. } => "BundleCreated",
+                Self::FragmentsSubmitted { .. } => "FragmentsSubmitted",
+                Self::TxReplaced { .. } => "TxReplaced",
+                Self::TxIncluded { .. } => "TxIncluded",
+                Self::TxFinalized { .. } => "TxFinalized",
+                Self::TxFailed { .. } => "TxFailed",
+                Self::TxSqueezedOut { .. } => "TxSqueezedOut",
+                Self::BlockCommitted { .. } => "BlockCommitted",
+            }
+        }
+    }
+
+    pub fn hex_hash(hash: [u8; 32]) -> String {
+        format!("0x{}", hex::encode(hash))
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct RecordedEvent {
+        /// Increases with every recorded event, usable as the SSE `Last-Event-ID`.
+        pub id: u64,
+        pub recorded_at: DateTime<Utc>,
+        pub event: Event,
+    }
+
+    /// Lets services publish events without waiting for them to be recorded. The default bus
+    /// drops every event, only the one created with [`EventBus::new`] and shared with an
+    /// [`EventRecorder`] keeps them.
+    #[derive(Debug, Clone, Default)]
+    pub struct EventBus {
+        inner: Option<Arc<Inner>>,
+    }
+
+    #[derive(Debug, Default)]
+    struct Inner {
+        pending: Mutex<VecDeque<Event>>,
+        recorded: Notify,
+    }
+
+    impl EventBus {
+        pub fn new() -> Self {
+            Self {
+                inner: Some(Arc::default()),
+            }
+        }
+
+        pub fn publish(&self, event: Event) {
+            let Some(inner) = &self.inner else {
+                return;
+            };
+
+            let mut pending = inner.pending.lock().expect("lock not poisoned");
+            if pending.len() >= MAX_PENDING_EVENTS {
+                if let Some(dropped) = pending.pop_front() {
+                    warn!("too many unrecorded events, dropping {dropped:?}");
+                }
+            }
+            pending.push_back(event);
+        }
+
+        /// Completes once new events were recorded by this instance.
+        pub async fn recorded(&self) {
+            match &self.inner {
+                Some(inner) => inner.recorded.notified().await,
+                None => std::future::pending().await,
+            }
+        }
+
+        fn take_pending(&self) -> Vec<Event> {
+            self.inner
+                .as_ref()
+                .map(|inner| {
+                    inner
+                        .pending
+                        .lock()
+                        .expect("lock not poisoned")
+                        .drain(..)
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+
+        /// Puts events that failed to be recorded back in front of the ones published since.
+        fn restore(&self, events: Vec<Event>) {
+            let Some(inner) = &self.inner else {
+                return;
+            };
+
+            let mut pending = inner.pending.lock().expect("lock not poisoned");
+            for event in events.into_iter().rev() {
+                pending.push_front(event);
+            }
+            pending.truncate(MAX_PENDING_EVENTS);
+        }
+
+        fn notify_recorded(&self) {
+            if let Some(inner) = &self.inner {
+                inner.recorded.notify_waiters();
+            }
+        }
+    }
+
+    /// Stores the events published on the bus so that consumers can resume from the last one they
+    /// saw. The services publishing events only run on the leader, so every event is recorded
+    /// once. The storage has to make the ids visible in increasing order, so that resuming after
+    /// an id never skips an event.
+    pub struct EventRecorder<Db, Clock> {
+        storage: Db,
+        bus: EventBus,
+        clock: Clock,
+    }
+
+    impl<Db, Clock> EventRecorder<Db, Clock> {
+        pub fn new(storage: Db, bus: EventBus, clock: Clock) -> Self {
+            Self {
+                storage,
+                bus,
+                clock,
+            }
+        }
+    }
+
+    impl<Db, Clock> Runner for EventRecorder<Db, Clock>
+    where
+        Db: crate::events::port::Storage,
+        Clock: crate::events::port::Clock + Send + Sync,
+    {
+        async fn run(&mut self) -> Result<()> {
+            let Some(events) = self.bus.take_pending().into_iter().collect_nonempty() else {
+                return Ok(());
+            };
+
+            if let Err(e) = self
+                .storage
+                .record_events(events.clone(), self.clock.now())
+                .await
+            {
+                self.bus.restore(events.into());
+                return Err(e);
+            }
+
+            self.bus.notify_recorded();
+
+            Ok(())
+        }
+    }
+
+    /// Reads the recorded events in order.
+    pub struct EventFeed<Db> {
+        storage: Db,
+        bus: EventBus,
+        page_size: usize,
+    }
+
+    impl<Db> EventFeed<Db> {
+        pub fn new(storage: Db, bus: EventBus, page_size: usize) -> Self {
+            Self {
+                storage,
+                bus,
+                page_size,
+            }
+        }
+
+        /// Completes once this instance recorded new events. Events recorded by other replicas
+        /// are only noticed by polling.
+        pub async fn new_events(&self) {
+            self.bus.recorded().await
+        }
+    }
+
+    impl<Db> EventFeed<Db>
+    where
+        Db: crate::events::port::Storage,
+    {
+        /// Events recorded after the one with `last_id`, oldest first.
+        pub async fn events_after(&self, last_id: u64) -> Result<Vec<RecordedEvent>> {
+            self.storage.events_after(last_id, self.page_size).await
+        }
+
+        /// Id of the latest recorded event, so that new consumers only get the events to come.
+        pub async fn latest_id(&self) -> Result<u64> {
+            Ok(self.storage.latest_event_id().await?.unwrap_or_default())
+        }
+    }
+}
+
+pub mod port {
+    use super::service::{Event, RecordedEvent};
+    use crate::{
+        Result,
+        types::{DateTime, NonEmpty, Utc},
+    };
+
+    #[allow(async_fn_in_trait)]
+    #[trait_variant::make(Send)]
+    #[cfg_attr(feature = "test-helpers", mockall::automock)]
+    pub trait Storage: Send + Sync {
+        /// Records the events in the given order. Events recorded concurrently by other instances
+        /// must not get lower ids than these once they become visible.
+        async fn record_events(
+            &self,
+            events: NonEmpty<Event>,
+            recorded_at: DateTime<Utc>,
+        ) -> Result<()>;
+        async fn events_after(&self, last_id: u64, limit: usize) -> Result<Vec<RecordedEvent>>;
+        async fn latest_event_id(&self) -> Result<Option<u64>>;
+    }
+
+    pub trait Clock {
+        fn now(&self) -> DateTime<Utc>;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        port::{Clock, MockStorage},
+        service::{Event, EventBus, EventRecorder},
+    };
+    use crate::{
+        Error, Runner,
+        types::{DateTime, Utc},
+    };
+
+    struct TestClock;
+
+    impl Clock for TestClock {
+        fn now(&self) -> DateTime<Utc> {
+            DateTime::UNIX_EPOCH
+        }
+    }
+
+    fn failed(nonce: u32) -> Event {
+        Event::TxFailed {
+            tx_hash: "0x00".to_string(),
+            nonce,
+        }
+    }
+
+    #[tokio::test]
+    async fn events_are_recorded_in_order() {
+        // given
+        let bus = EventBus::new();
+        let mut storage = MockStorage::new();
+        storage
+            .expect_record_events()
+            .withf(|events, _| Vec::from(events.clone()) == vec![failed(1), failed(2)])
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(()) }));
+        let mut recorder = EventRecorder::new(storage, bus.clone(), TestClock);
+
+        bus.publish(failed(1));
+        bus.publish(failed(2));
+
+        // when
+        recorder.run().await.unwrap();
+        recorder.run().await.unwrap();
+
+        // then
+        // the mock validates that the events were recorded once
+    }
+
+    #[tokio::test]
+    async fn events_are_kept_until_recorded() {
+        // given
+        let bus = EventBus::new();
+        let mut storage = MockStorage::new();
+        let mut seq = mockall::Sequence::new();
+        storage
+            .expect_record_events()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Box::pin(async { Err(Error::Storage("db down".into())) }));
+        storage
+            .expect_record_events()
+            .withf(|events, _| Vec::from(events.clone()) == vec![failed(1), failed(2)])
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Box::pin(async { Ok(()) }));
+        let mut recorder = EventRecorder::new(storage, bus.clone(), TestClock);
+
+        bus.publish(failed(1));
+        let first_attempt = recorder.run().await;
+        bus.publish(failed(2));
+
+        // when
+        recorder.run().await.unwrap();
+
+        // then
+        assert!(first_attempt.is_err());
+    }
+
+    #[tokio::test]
+    async fn default_bus_drops_events() {
+        // given
+        let bus = EventBus::default();
+        let mut recorder = EventRecorder::new(MockStorage::new(), bus.clone(), TestClock);
+
+        // when
+        bus.publish(failed(1));
+
+        // then
+        // the mock fails the test if anything is recorded
+        recorder.run().await.unwrap();
+    }
+}
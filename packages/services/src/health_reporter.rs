@@ -1,37 +1,340 @@
 pub mod service {
+    use std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    };
+
     use metrics::HealthChecker;
     use serde::Serialize;
+    use tracing::warn;
+    use utoipa::ToSchema;
 
-    #[derive(Debug, Serialize)]
+    #[derive(Debug, Serialize, ToSchema)]
     pub struct HealthReport {
+        /// Up as long as any Fuel endpoint is.
         fuel_connection_up: bool,
-        eth_connection_healthy: bool,
+        /// Health of every configured Fuel endpoint.
+        #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+        fuel_endpoints: BTreeMap<String, bool>,
+        /// `None` if this instance doesn't connect to Ethereum.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        eth_connection_healthy: Option<bool>,
+        database_connection_up: bool,
+        /// `None` if this instance doesn't watch the commits of the state contract.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        no_foreign_commits: Option<bool>,
+        runners: BTreeMap<&'static str, RunnerReport>,
     }
 
     impl HealthReport {
+        /// Whether all services are making progress. Failing it means the instance needs a
+        /// restart.
+        pub fn live(&self) -> bool {
+            self.runners.values().all(|runner| runner.healthy)
+        }
+
+        /// Whether the connections to the Fuel node, Ethereum and the database are up.
+        pub fn ready(&self) -> bool {
+            self.fuel_connection_up
+                && self.eth_connection_healthy.unwrap_or(true)
+                && self.database_connection_up
+        }
+
+        /// Also fails once a commit not made by this committer was seen on L1. A restart won't fix
+        /// that, so it is left out of `live` and `ready`.
         pub fn healthy(&self) -> bool {
-            self.fuel_connection_up && self.eth_connection_healthy
+            self.live() && self.ready() && self.no_foreign_commits.unwrap_or(true)
         }
     }
 
-    pub struct HealthReporter {
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+    pub struct RunnerReport {
+        pub healthy: bool,
+        pub seconds_since_last_success: u64,
+        pub stopped: bool,
+    }
+
+    /// Liveness of a single service, updated by the loop running it.
+    #[derive(Debug, Clone)]
+    pub struct RunnerHealth {
+        name: &'static str,
+        max_time_since_success: Duration,
+        state: Arc<Mutex<RunnerState>>,
+    }
+
+    #[derive(Debug)]
+    struct RunnerState {
+        last_success: Instant,
+        stopped: bool,
+    }
+
+    impl RunnerHealth {
+        /// The runner is reported unhealthy if it hasn't succeeded within `max_time_since_success`
+        /// or if it stopped.
+        pub fn new(name: &'static str, max_time_since_success: Duration) -> Self {
+            Self {
+                name,
+                max_time_since_success,
+                state: Arc::new(Mutex::new(RunnerState {
+                    last_success: Instant::now(),
+                    stopped: false,
+                })),
+            }
+        }
+
+        pub fn name(&self) -> &'static str {
+            self.name
+        }
+
+        /// To be called after every successful run, or after a run that was skipped on purpose.
+        pub fn record_success(&self) {
+            self.state.lock().expect("lock not poisoned").last_success = Instant::now();
+        }
+
+        /// To be called once the runner's task is no longer running, be it due to a shutdown or a
+        /// panic.
+        pub fn mark_stopped(&self) {
+            self.state.lock().expect("lock not poisoned").stopped = true;
+        }
+
+        pub fn report(&self) -> RunnerReport {
+            let state = self.state.lock().expect("lock not poisoned");
+            let since_last_success = state.last_success.elapsed();
+
+            RunnerReport {
+                healthy: !state.stopped && since_last_success <= self.max_time_since_success,
+                seconds_since_last_success: since_last_success.as_secs(),
+                stopped: state.stopped,
+            }
+        }
+    }
+
+    pub struct HealthReporter<Db> {
         fuel_connection: HealthChecker,
-        eth_connection: HealthChecker,
+        fuel_endpoints: Vec<(String, HealthChecker)>,
+        eth_connection: Option<HealthChecker>,
+        commit_watcher: Option<HealthChecker>,
+        storage: Db,
+        runners: Vec<RunnerHealth>,
     }
 
-    impl HealthReporter {
-        pub fn new(fuel_health_check: HealthChecker, eth_health_check: HealthChecker) -> Self {
+    impl<Db> HealthReporter<Db>
+    where
+        Db: crate::health_reporter::port::Storage,
+    {
+        pub fn new(
+            fuel_health_check: HealthChecker,
+            eth_health_check: Option<HealthChecker>,
+            storage: Db,
+            runners: Vec<RunnerHealth>,
+        ) -> Self {
             Self {
                 fuel_connection: fuel_health_check,
+                fuel_endpoints: vec![],
                 eth_connection: eth_health_check,
+                commit_watcher: None,
+                storage,
+                runners,
+            }
+        }
+
+        /// Reported for information only, `fuel_health_check` decides about readiness.
+        pub fn with_fuel_endpoints(self, fuel_endpoints: Vec<(String, HealthChecker)>) -> Self {
+            Self {
+                fuel_endpoints,
+                ..self
             }
         }
 
-        pub fn report(&self) -> HealthReport {
+        pub fn with_commit_watcher(self, commit_watcher_health_check: HealthChecker) -> Self {
+            Self {
+                commit_watcher: Some(commit_watcher_health_check),
+                ..self
+            }
+        }
+
+        pub async fn report(&self) -> HealthReport {
+            let database_connection_up = match self.storage.ping().await {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("database health check failed: {e}");
+                    false
+                }
+            };
+
             HealthReport {
                 fuel_connection_up: self.fuel_connection.healthy(),
-                eth_connection_healthy: self.eth_connection.healthy(),
+                fuel_endpoints: self
+                    .fuel_endpoints
+                    .iter()
+                    .map(|(name, endpoint)| (name.clone(), endpoint.healthy()))
+                    .collect(),
+                eth_connection_healthy: self.eth_connection.as_ref().map(|eth| eth.healthy()),
+                database_connection_up,
+                no_foreign_commits: self
+                    .commit_watcher
+                    .as_ref()
+                    .map(|watcher| watcher.healthy()),
+                runners: self
+                    .runners
+                    .iter()
+                    .map(|runner| (runner.name(), runner.report()))
+                    .collect(),
             }
         }
     }
 }
+
+pub mod port {
+    use crate::Result;
+
+    #[allow(async_fn_in_trait)]
+    #[trait_variant::make(Send)]
+    #[cfg_attr(feature = "test-helpers", mockall::automock)]
+    pub trait Storage: Sync {
+        async fn ping(&self) -> Result<()>;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use metrics::HealthCheck;
+
+    use super::{
+        port::MockStorage,
+        service::{HealthReporter, RunnerHealth},
+    };
+    use crate::Error;
+
+    struct Connection(bool);
+
+    impl HealthCheck for Connection {
+        fn healthy(&self) -> bool {
+            self.0
+        }
+    }
+
+    fn storage(up: bool) -> MockStorage {
+        let mut storage = MockStorage::new();
+        storage.expect_ping().returning(move || {
+            Box::pin(async move {
+                if up {
+                    Ok(())
+                } else {
+                    Err(Error::Storage("connection refused".into()))
+                }
+            })
+        });
+        storage
+    }
+
+    #[tokio::test]
+    async fn healthy_when_connections_are_up_and_runners_progress() {
+        // given
+        let runner = RunnerHealth::new("Block Committer", Duration::from_secs(60));
+        let reporter = HealthReporter::new(
+            Box::new(Connection(true)),
+            None,
+            storage(true),
+            vec![runner.clone()],
+        );
+
+        // when
+        runner.record_success();
+        let report = reporter.report().await;
+
+        // then
+        assert!(report.live());
+        assert!(report.ready());
+        assert!(report.healthy());
+    }
+
+    #[tokio::test]
+    async fn not_ready_when_database_is_down() {
+        // given
+        let reporter =
+            HealthReporter::new(Box::new(Connection(true)), None, storage(false), vec![]);
+
+        // when
+        let report = reporter.report().await;
+
+        // then
+        assert!(report.live());
+        assert!(!report.ready());
+    }
+
+    #[tokio::test]
+    async fn not_live_when_runner_has_not_succeeded_in_time() {
+        // given
+        let runner = RunnerHealth::new("Block Committer", Duration::from_millis(10));
+        let reporter = HealthReporter::new(
+            Box::new(Connection(true)),
+            None,
+            storage(true),
+            vec![runner],
+        );
+
+        // when
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let report = reporter.report().await;
+
+        // then
+        assert!(!report.live());
+        assert!(report.ready());
+    }
+
+    #[tokio::test]
+    async fn unhealthy_but_live_and_ready_after_foreign_commit() {
+        // given
+        let reporter = HealthReporter::new(Box::new(Connection(true)), None, storage(true), vec![])
+            .with_commit_watcher(Box::new(Connection(false)));
+
+        // when
+        let report = reporter.report().await;
+
+        // then
+        assert!(report.live());
+        assert!(report.ready());
+        assert!(!report.healthy());
+    }
+
+    #[tokio::test]
+    async fn fuel_endpoints_down_do_not_affect_readiness() {
+        // given
+        let reporter = HealthReporter::new(Box::new(Connection(true)), None, storage(true), vec![])
+            .with_fuel_endpoints(vec![
+                ("http://fuel-1".to_string(), Box::new(Connection(false))),
+                ("http://fuel-2".to_string(), Box::new(Connection(true))),
+            ]);
+
+        // when
+        let report = reporter.report().await;
+
+        // then
+        assert!(report.ready());
+        assert!(report.healthy());
+    }
+
+    #[tokio::test]
+    async fn not_live_when_runner_stopped() {
+        // given
+        let runner = RunnerHealth::new("Block Committer", Duration::from_secs(60));
+        let reporter = HealthReporter::new(
+            Box::new(Connection(true)),
+            None,
+            storage(true),
+            vec![runner.clone()],
+        );
+
+        // when
+        runner.mark_stopped();
+        let report = reporter.report().await;
+
+        // then
+        assert!(!report.live());
+        assert!(runner.report().stopped);
+    }
+}
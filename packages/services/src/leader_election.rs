@@ -0,0 +1,450 @@
+pub mod service {
+    use std::{
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    };
+
+    use metrics::{
+        RegistersMetrics,
+        prometheus::{IntGaugeVec, Opts, core::Collector},
+    };
+    use tracing::{info, warn};
+
+    use crate::{Result, Runner, state_committer::service::ForcedActions};
+
+    /// Tells whether this instance holds a lease and may therefore run the services that mutate
+    /// the shared state it guards (submitting to L1, bundling, pruning).
+    #[derive(Debug, Clone)]
+    pub struct Leadership {
+        /// `None` if leader election is disabled and this instance always leads.
+        lease_valid_until: Option<Arc<Mutex<Option<Instant>>>>,
+    }
+
+    impl Leadership {
+        /// Leadership for a single instance deployment, always considered the leader.
+        pub fn unconditional() -> Self {
+            Self {
+                lease_valid_until: None,
+            }
+        }
+
+        fn elected() -> Self {
+            Self {
+                lease_valid_until: Some(Arc::new(Mutex::new(None))),
+            }
+        }
+
+        pub fn is_leader(&self) -> bool {
+            let Some(valid_until) = &self.lease_valid_until else {
+                return true;
+            };
+
+            valid_until
+                .lock()
+                .expect("lock not poisoned")
+                .is_some_and(|valid_until| Instant::now() < valid_until)
+        }
+
+        fn set(&self, valid_until: Option<Instant>) {
+            if let Some(lease) = &self.lease_valid_until {
+                *lease.lock().expect("lock not poisoned") = valid_until;
+            }
+        }
+    }
+
+    /// Keeps acquiring or renewing leases stored in the database, one per group of services that
+    /// must not run on several instances at once. Only one candidate can hold a lease at a time,
+    /// standbys take over once the leader stops renewing it. Since every lease is elected
+    /// separately, the services can be spread over several instances.
+    #[derive(Clone)]
+    pub struct LeaderElection<Db> {
+        storage: Db,
+        candidate_id: String,
+        lease_duration: Duration,
+        leases: Vec<(&'static str, Leadership)>,
+        forced_actions: Option<(&'static str, ForcedActions)>,
+        metrics: Metrics,
+    }
+
+    impl<Db> LeaderElection<Db> {
+        pub fn new(storage: Db, candidate_id: String, lease_duration: Duration) -> Self {
+            Self {
+                storage,
+                candidate_id,
+                lease_duration,
+                leases: vec![],
+                forced_actions: None,
+                metrics: Metrics::default(),
+            }
+        }
+
+        /// Actions forced by an operator are dropped whenever the leadership of `lease` changes,
+        /// so that they are never applied by a later term they weren't meant for.
+        pub fn with_forced_actions(
+            mut self,
+            lease: &'static str,
+            forced_actions: ForcedActions,
+        ) -> Self {
+            self.forced_actions = Some((lease, forced_actions));
+            self
+        }
+
+        /// Campaigns for `lease` from the next campaign on, the returned leadership tells whether
+        /// this instance holds it.
+        pub fn leadership(&mut self, lease: &'static str) -> Leadership {
+            if let Some((_, leadership)) = self.leases.iter().find(|(name, _)| *name == lease) {
+                return leadership.clone();
+            }
+
+            let leadership = Leadership::elected();
+            self.metrics.is_leader.with_label_values(&[lease]).set(0);
+            self.leases.push((lease, leadership.clone()));
+
+            leadership
+        }
+    }
+
+    impl<Db> LeaderElection<Db>
+    where
+        Db: crate::leader_election::port::Storage,
+    {
+        /// Acquires or renews every lease. A lease that cannot be renewed is given up and the
+        /// first error is returned once all leases were tried.
+        pub async fn campaign(&self) -> Result<()> {
+            let mut first_error = None;
+            for (lease, leadership) in &self.leases {
+                if let Err(e) = self.campaign_for(lease, leadership).await {
+                    first_error.get_or_insert(e);
+                }
+            }
+
+            first_error.map_or(Ok(()), Err)
+        }
+
+        async fn campaign_for(&self, lease: &'static str, leadership: &Leadership) -> Result<()> {
+            let was_leader = leadership.is_leader();
+
+            // Measured before the request so that the local view never outlives the lease
+            // stored in the database.
+            let attempt_started = Instant::now();
+            let acquired = match self
+                .storage
+                .acquire_or_renew_lease(lease, &self.candidate_id, self.lease_duration)
+                .await
+            {
+                Ok(acquired) => acquired,
+                Err(e) => {
+                    // We cannot tell whether the lease was renewed, so step down to be safe.
+                    self.update_leadership(lease, leadership, was_leader, None);
+                    return Err(e);
+                }
+            };
+
+            let valid_until = acquired.then(|| attempt_started + self.lease_duration);
+            self.update_leadership(lease, leadership, was_leader, valid_until);
+
+            Ok(())
+        }
+
+        fn update_leadership(
+            &self,
+            lease: &'static str,
+            leadership: &Leadership,
+            was_leader: bool,
+            valid_until: Option<Instant>,
+        ) {
+            leadership.set(valid_until);
+
+            let is_leader = valid_until.is_some();
+            if was_leader != is_leader {
+                self.clear_forced_actions(lease);
+            }
+
+            let candidate_id = &self.candidate_id;
+            match (was_leader, is_leader) {
+                (false, true) => info!("'{candidate_id}' became the leader of '{lease}'"),
+                (true, false) => warn!("'{candidate_id}' is no longer the leader of '{lease}'"),
+                _ => {}
+            }
+
+            self.metrics
+                .is_leader
+                .with_label_values(&[lease])
+                .set(i64::from(is_leader));
+        }
+
+        fn clear_forced_actions(&self, lease: &str) {
+            if let Some((_, forced_actions)) = self
+                .forced_actions
+                .as_ref()
+                .filter(|(forced_lease, _)| *forced_lease == lease)
+            {
+                forced_actions.clear();
+            }
+        }
+
+        /// Gives up every lease so that standbys can take over without waiting for them to expire.
+        pub async fn resign(&self) -> Result<()> {
+            for (lease, leadership) in &self.leases {
+                leadership.set(None);
+                self.clear_forced_actions(lease);
+                self.metrics.is_leader.with_label_values(&[lease]).set(0);
+            }
+
+            self.storage.release_leases(&self.candidate_id).await
+        }
+    }
+
+    impl<Db> Runner for LeaderElection<Db>
+    where
+        Db: crate::leader_election::port::Storage + Send + Sync,
+    {
+        async fn run(&mut self) -> Result<()> {
+            self.campaign().await
+        }
+    }
+
+    #[derive(Clone)]
+    struct Metrics {
+        is_leader: IntGaugeVec,
+    }
+
+    impl Default for Metrics {
+        fn default() -> Self {
+            let is_leader = IntGaugeVec::new(
+                Opts::new(
+                    "is_leader",
+                    "1 if this instance holds the lease, 0 otherwise.",
+                ),
+                &["lease"],
+            )
+            .expect("is_leader gauge to be correctly configured");
+
+            Self { is_leader }
+        }
+    }
+
+    impl<Db> RegistersMetrics for LeaderElection<Db> {
+        fn metrics(&self) -> Vec<Box<dyn Collector>> {
+            vec![Box::new(self.metrics.is_leader.clone())]
+        }
+    }
+}
+
+pub mod port {
+    use std::time::Duration;
+
+    use crate::Result;
+
+    #[allow(async_fn_in_trait)]
+    #[trait_variant::make(Send)]
+    #[cfg_attr(feature = "test-helpers", mockall::automock)]
+    pub trait Storage: Sync {
+        /// Takes the lease named `lease` if it is free or expired, or extends it if `candidate_id`
+        /// already holds it. Returns whether `candidate_id` holds the lease afterwards.
+        async fn acquire_or_renew_lease(
+            &self,
+            lease: &str,
+            candidate_id: &str,
+            lease_duration: Duration,
+        ) -> Result<bool>;
+        /// Releases every lease held by `candidate_id`.
+        async fn release_leases(&self, candidate_id: &str) -> Result<()>;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{port::MockStorage, service::LeaderElection};
+    use crate::{Error, state_committer::service::ForcedActions};
+
+    fn storage_granting(granted: bool) -> MockStorage {
+        let mut storage = MockStorage::new();
+        storage
+            .expect_acquire_or_renew_lease()
+            .returning(move |_, _, _| Box::pin(async move { Ok(granted) }));
+        storage
+    }
+
+    #[tokio::test]
+    async fn becomes_leader_when_lease_is_acquired() {
+        // given
+        let mut election =
+            LeaderElection::new(storage_granting(true), "a".into(), Duration::from_secs(30));
+        let leadership = election.leadership("bundler");
+        assert!(!leadership.is_leader());
+
+        // when
+        election.campaign().await.unwrap();
+
+        // then
+        assert!(leadership.is_leader());
+    }
+
+    #[tokio::test]
+    async fn stays_standby_when_lease_is_held_by_another() {
+        // given
+        let mut election =
+            LeaderElection::new(storage_granting(false), "b".into(), Duration::from_secs(30));
+        let leadership = election.leadership("bundler");
+
+        // when
+        election.campaign().await.unwrap();
+
+        // then
+        assert!(!leadership.is_leader());
+    }
+
+    #[tokio::test]
+    async fn steps_down_if_lease_cannot_be_renewed() {
+        // given
+        let mut storage = MockStorage::new();
+        let mut granted = true;
+        storage
+            .expect_acquire_or_renew_lease()
+            .times(2)
+            .returning(move |_, _, _| {
+                let result = if granted {
+                    Ok(true)
+                } else {
+                    Err(Error::Storage("connection lost".into()))
+                };
+                granted = false;
+                Box::pin(async move { result })
+            });
+        let mut election = LeaderElection::new(storage, "a".into(), Duration::from_secs(30));
+        let leadership = election.leadership("bundler");
+        election.campaign().await.unwrap();
+        assert!(leadership.is_leader());
+
+        // when
+        let result = election.campaign().await;
+
+        // then
+        assert!(result.is_err());
+        assert!(!leadership.is_leader());
+    }
+
+    #[tokio::test]
+    async fn leadership_lapses_once_the_lease_expires_locally() {
+        // given
+        let mut election = LeaderElection::new(
+            storage_granting(true),
+            "a".into(),
+            Duration::from_millis(10),
+        );
+        let leadership = election.leadership("bundler");
+        election.campaign().await.unwrap();
+
+        // when
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // then
+        assert!(!leadership.is_leader());
+    }
+
+    #[tokio::test]
+    async fn resigning_releases_the_lease() {
+        // given
+        let mut storage = storage_granting(true);
+        storage
+            .expect_release_leases()
+            .withf(|candidate_id| candidate_id == "a")
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+        let mut election = LeaderElection::new(storage, "a".into(), Duration::from_secs(30));
+        let bundler = election.leadership("bundler");
+        let pruner = election.leadership("pruner");
+        election.campaign().await.unwrap();
+
+        // when
+        election.resign().await.unwrap();
+
+        // then
+        assert!(!bundler.is_leader());
+        assert!(!pruner.is_leader());
+    }
+
+    #[tokio::test]
+    async fn drops_forced_actions_when_stepping_down() {
+        // given
+        let mut storage = MockStorage::new();
+        let mut granted = true;
+        storage
+            .expect_acquire_or_renew_lease()
+            .times(2)
+            .returning(move |_, _, _| {
+                let result = Ok(granted);
+                granted = false;
+                Box::pin(async move { result })
+            });
+        let forced_actions = ForcedActions::new();
+        let mut election = LeaderElection::new(storage, "a".into(), Duration::from_secs(30))
+            .with_forced_actions("state_committer", forced_actions.clone());
+        election.leadership("state_committer");
+        election.campaign().await.unwrap();
+        forced_actions.force_submission();
+        forced_actions.force_gas_bump();
+
+        // when
+        election.campaign().await.unwrap();
+
+        // then
+        assert!(!forced_actions.take_submission());
+        assert!(!forced_actions.take_gas_bump());
+    }
+
+    #[tokio::test]
+    async fn leases_are_elected_separately() {
+        // given
+        let mut storage = MockStorage::new();
+        storage
+            .expect_acquire_or_renew_lease()
+            .withf(|_, candidate_id, _| candidate_id == "a")
+            .times(2)
+            .returning(|lease, _, _| {
+                let granted = lease == "bundler";
+                Box::pin(async move { Ok(granted) })
+            });
+        let mut election = LeaderElection::new(storage, "a".into(), Duration::from_secs(30));
+        let bundler = election.leadership("bundler");
+        let committer = election.leadership("state_committer");
+
+        // when
+        election.campaign().await.unwrap();
+
+        // then
+        assert!(bundler.is_leader());
+        assert!(!committer.is_leader());
+    }
+
+    #[tokio::test]
+    async fn keeps_campaigning_for_the_other_leases_after_an_error() {
+        // given
+        let mut storage = MockStorage::new();
+        storage
+            .expect_acquire_or_renew_lease()
+            .times(2)
+            .returning(|lease, _, _| {
+                let result = if lease == "bundler" {
+                    Err(Error::Storage("timeout".into()))
+                } else {
+                    Ok(true)
+                };
+                Box::pin(async move { result })
+            });
+        let mut election = LeaderElection::new(storage, "a".into(), Duration::from_secs(30));
+        let bundler = election.leadership("bundler");
+        let committer = election.leadership("state_committer");
+
+        // when
+        let result = election.campaign().await;
+
+        // then
+        assert!(result.is_err());
+        assert!(!bundler.is_leader());
+        assert!(committer.is_leader());
+    }
+}
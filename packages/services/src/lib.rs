@@ -1,9 +1,18 @@
+pub mod admin;
 pub mod block_bundler;
 pub mod block_committer;
 pub mod block_importer;
+pub mod block_reporter;
+pub mod bundle_reporter;
+pub mod commit_watcher;
+pub mod cost_feed;
 pub mod cost_reporter;
+pub mod critical_section;
+pub mod events;
 pub mod fee_metrics_tracker;
 pub mod health_reporter;
+pub mod leader_election;
+pub mod runner_control;
 pub mod state_committer;
 pub mod state_listener;
 pub mod state_pruner;
@@ -35,6 +44,10 @@ pub enum Error {
     Storage(String),
     #[error("Block validation error: {0}")]
     BlockValidation(String),
+    #[error("No quorum: {0}")]
+    NoQuorum(String),
+    #[error("this instance is not the leader")]
+    NotLeader,
 }
 
 impl From<InvalidL1Height> for Error {
@@ -0,0 +1,192 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use tokio::sync::Notify;
+
+use crate::leader_election::service::Leadership;
+
+/// Lets operators pause, resume or wake up the runners of this instance.
+#[derive(Debug, Clone, Default)]
+pub struct RunnerControls {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    runners: BTreeMap<String, RunnerControl>,
+    // also applied to the runners registered later on
+    paused: BTreeSet<String>,
+}
+
+/// Control of a single runner, checked by the loop running it.
+#[derive(Debug, Clone)]
+pub struct RunnerControl {
+    paused: Arc<AtomicBool>,
+    wake: Arc<Notify>,
+    leadership: Leadership,
+}
+
+impl RunnerControls {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the runner under the slug of its name, e.g. `state-committer` for
+    /// `State Committer`.
+    pub fn register(&self, name: &str) -> RunnerControl {
+        self.register_for_leader(name, Leadership::unconditional())
+    }
+
+    /// Registers a runner that only runs while `leadership` is held.
+    pub fn register_for_leader(&self, name: &str, leadership: Leadership) -> RunnerControl {
+        let slug = slug(name);
+        let mut inner = self.inner.lock().expect("lock not poisoned");
+        let paused = inner.paused.contains(&slug);
+
+        inner
+            .runners
+            .entry(slug)
+            .or_insert_with(|| RunnerControl {
+                paused: Arc::new(AtomicBool::new(paused)),
+                wake: Arc::default(),
+                leadership,
+            })
+            .clone()
+    }
+
+    /// Looks up a runner by its slug or its name.
+    pub fn get(&self, name: &str) -> Option<RunnerControl> {
+        self.inner
+            .lock()
+            .expect("lock not poisoned")
+            .runners
+            .get(&slug(name))
+            .cloned()
+    }
+
+    /// Slugs of the registered runners.
+    pub fn names(&self) -> Vec<String> {
+        self.inner
+            .lock()
+            .expect("lock not poisoned")
+            .runners
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    /// Pauses the runners of the given slugs and resumes all others, including the ones not
+    /// registered yet.
+    pub fn set_paused(&self, paused: impl IntoIterator<Item = String>) {
+        let mut inner = self.inner.lock().expect("lock not poisoned");
+        inner.paused = paused.into_iter().collect();
+
+        let inner = &*inner;
+        for (slug, runner) in &inner.runners {
+            match (inner.paused.contains(slug), runner.is_paused()) {
+                (true, false) => runner.pause(),
+                (false, true) => runner.resume(),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl RunnerControl {
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Whether the runner runs on this instance, i.e. it isn't leader-gated or this instance is
+    /// the leader.
+    pub fn is_active(&self) -> bool {
+        self.leadership.is_leader()
+    }
+
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+        self.wake();
+    }
+
+    /// Cuts the wait until the next run short. Remembered if the runner is busy at the moment.
+    pub fn wake(&self) {
+        self.wake.notify_one();
+    }
+
+    pub async fn woken(&self) {
+        self.wake.notified().await;
+    }
+}
+
+/// Normalizes a runner name, e.g. `state-committer` for `State Committer`.
+pub fn slug(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == '_' || c == '-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RunnerControls;
+
+    #[test]
+    fn runners_are_found_by_slug_or_name() {
+        // given
+        let controls = RunnerControls::new();
+        let registered = controls.register("State Committer");
+
+        // when
+        controls.get("state-committer").unwrap().pause();
+
+        // then
+        assert!(registered.is_paused());
+        assert!(controls.get("State Committer").unwrap().is_paused());
+        assert!(controls.get("state_pruner").is_none());
+        assert_eq!(controls.names(), vec!["state-committer".to_string()]);
+    }
+
+    #[test]
+    fn stored_pauses_apply_to_registered_and_later_runners() {
+        // given
+        let controls = RunnerControls::new();
+        let committer = controls.register("State Committer");
+        let pruner = controls.register("State Pruner");
+        pruner.pause();
+
+        // when
+        controls.set_paused(["state-committer".to_string(), "block-importer".to_string()]);
+
+        // then
+        assert!(committer.is_paused());
+        assert!(!pruner.is_paused());
+        assert!(controls.register("Block Importer").is_paused());
+    }
+
+    #[tokio::test]
+    async fn wake_up_is_remembered_until_awaited() {
+        // given
+        let control = RunnerControls::new().register("State Pruner");
+
+        // when
+        control.wake();
+
+        // then
+        tokio::time::timeout(Duration::from_millis(50), control.woken())
+            .await
+            .expect("to be woken");
+    }
+}
@@ -1,6 +1,7 @@
 mod fee_algo;
 pub use fee_algo::{
-    Config as AlgoConfig, FeeMultiplierRange, FeeThresholds, SmaFeeAlgo, SmaPeriods,
+    Config as AlgoConfig, FeeDecision, FeeDecisionReason, FeeMultiplierRange, FeeThresholds,
+    SmaFeeAlgo, SmaPeriods,
 };
 pub mod port;
 pub mod service;
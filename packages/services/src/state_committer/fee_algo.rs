@@ -3,7 +3,9 @@ use std::{
     ops::RangeInclusive,
 };
 
+use serde::Serialize;
 use tracing::info;
+use utoipa::ToSchema;
 
 use crate::{
     Error, Result,
@@ -20,12 +22,30 @@ where
         num_l2_blocks_behind: u32,
         at_l1_height: u64,
     ) -> Result<bool> {
+        self.decide(num_blobs, num_l2_blocks_behind, at_l1_height)
+            .await
+            .map(|decision| decision.send)
+    }
+
+    /// Like [`SmaFeeAlgo::fees_acceptable`] but also tells why.
+    pub async fn decide(
+        &self,
+        num_blobs: u32,
+        num_l2_blocks_behind: u32,
+        at_l1_height: u64,
+    ) -> Result<FeeDecision> {
         if self.too_far_behind(num_l2_blocks_behind) {
             info!(
                 "Sending because we've fallen behind by {} which is more than the configured maximum of {}",
                 num_l2_blocks_behind, self.config.fee_thresholds.max_l2_blocks_behind
             );
-            return Ok(true);
+            return Ok(FeeDecision {
+                send: true,
+                reason: FeeDecisionReason::TooFarBehind {
+                    l2_blocks_behind: num_l2_blocks_behind,
+                    max_l2_blocks_behind: self.config.fee_thresholds.max_l2_blocks_behind.get(),
+                },
+            });
         }
 
         // opted out of validating that num_blobs <= 6, it's not this fn's problem if the caller
@@ -52,7 +72,13 @@ where
                 "Sending because: short term price {short_term_tx_fee} is deemed always acceptable since it is <= {}",
                 self.config.fee_thresholds.always_acceptable_fee
             );
-            return Ok(true);
+            return Ok(FeeDecision {
+                send: true,
+                reason: FeeDecisionReason::AlwaysAcceptable {
+                    short_term_tx_fee,
+                    always_acceptable_fee: self.config.fee_thresholds.always_acceptable_fee,
+                },
+            });
         }
 
         let long_term_tx_fee =
@@ -81,10 +107,45 @@ where
             );
         }
 
-        Ok(should_send)
+        Ok(FeeDecision {
+            send: should_send,
+            reason: FeeDecisionReason::ComparedToMaxUpperFee {
+                short_term_tx_fee,
+                long_term_tx_fee,
+                max_upper_tx_fee,
+            },
+        })
     }
 }
 
+/// Outcome of the fee algorithm, i.e. whether fragments should be sent at the current fees.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct FeeDecision {
+    pub send: bool,
+    pub reason: FeeDecisionReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FeeDecisionReason {
+    /// Sent regardless of the fees because the oldest fragment is too far behind.
+    TooFarBehind {
+        l2_blocks_behind: u32,
+        max_l2_blocks_behind: u32,
+    },
+    /// The short-term fee is low enough to always be acceptable.
+    AlwaysAcceptable {
+        short_term_tx_fee: u128,
+        always_acceptable_fee: u128,
+    },
+    /// Sent only if the short-term fee doesn't exceed the maximum derived from the long-term fee.
+    ComparedToMaxUpperFee {
+        short_term_tx_fee: u128,
+        long_term_tx_fee: u128,
+        max_upper_tx_fee: u128,
+    },
+}
+
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "test-helpers", derive(Default))]
 pub struct Config {
@@ -1,4 +1,11 @@
-use std::{num::NonZeroUsize, time::Duration};
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
 
 use itertools::Itertools;
 use metrics::{
@@ -7,9 +14,14 @@ use metrics::{
 };
 use tracing::info;
 
-use super::{AlgoConfig, fee_algo::SmaFeeAlgo};
+use super::{
+    AlgoConfig,
+    fee_algo::{FeeDecision, SmaFeeAlgo},
+};
 use crate::{
     Result, Runner,
+    critical_section::CriticalSections,
+    events::service::{Event, EventBus, hex_hash},
     state_committer::port::l1::Priority,
     types::{CollectNonEmpty, DateTime, L1Tx, NonEmpty, Utc, storage::BundleFragment},
 };
@@ -62,6 +74,74 @@ impl<L1, FuelApi, Db, Clock, D> RegistersMetrics for StateCommitter<L1, FuelApi,
     }
 }
 
+/// A fee decision along with when it was made.
+type TimedFeeDecision = (FeeDecision, DateTime<Utc>);
+
+/// The latest decision of the fee algorithm, shared so that it can be reported.
+#[derive(Debug, Clone, Default)]
+pub struct FeeDecisions {
+    latest: Arc<Mutex<Option<TimedFeeDecision>>>,
+}
+
+impl FeeDecisions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The decision and when it was made.
+    pub fn latest(&self) -> Option<TimedFeeDecision> {
+        self.latest.lock().expect("lock not poisoned").clone()
+    }
+
+    fn record(&self, decision: FeeDecision, decided_at: DateTime<Utc>) {
+        *self.latest.lock().expect("lock not poisoned") = Some((decision, decided_at));
+    }
+}
+
+/// Operator overrides, each applied once on the next run of the state committer.
+#[derive(Debug, Clone, Default)]
+pub struct ForcedActions {
+    submission: Arc<AtomicBool>,
+    gas_bump: Arc<AtomicBool>,
+}
+
+impl ForcedActions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Submits the pending fragments regardless of how many accumulated and of the fees.
+    pub fn force_submission(&self) {
+        self.submission.store(true, Ordering::Relaxed);
+    }
+
+    /// Replaces the pending transaction regardless of how long it has been pending and of the
+    /// fees.
+    pub fn force_gas_bump(&self) {
+        self.gas_bump.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn take_submission(&self) -> bool {
+        self.submission.swap(false, Ordering::Relaxed)
+    }
+
+    pub(crate) fn take_gas_bump(&self) -> bool {
+        self.gas_bump.swap(false, Ordering::Relaxed)
+    }
+
+    /// Puts back actions that were taken by a failed run so that the next run retries them.
+    fn restore(&self, submission: bool, gas_bump: bool) {
+        self.submission.fetch_or(submission, Ordering::Relaxed);
+        self.gas_bump.fetch_or(gas_bump, Ordering::Relaxed);
+    }
+
+    /// Drops the actions that haven't been applied yet.
+    pub fn clear(&self) {
+        self.submission.store(false, Ordering::Relaxed);
+        self.gas_bump.store(false, Ordering::Relaxed);
+    }
+}
+
 /// The `StateCommitter` is responsible for committing state fragments to L1.
 pub struct StateCommitter<L1, FuelApi, Db, Clock, FeeProvider> {
     l1_adapter: L1,
@@ -72,6 +152,10 @@ pub struct StateCommitter<L1, FuelApi, Db, Clock, FeeProvider> {
     startup_time: DateTime<Utc>,
     metrics: Metrics,
     fee_algo: SmaFeeAlgo<FeeProvider>,
+    critical_sections: CriticalSections,
+    fee_decisions: FeeDecisions,
+    forced_actions: ForcedActions,
+    events: EventBus,
 }
 
 impl<L1, FuelApi, Db, Clock, FeeProvider> StateCommitter<L1, FuelApi, Db, Clock, FeeProvider>
@@ -98,8 +182,37 @@ where
             clock,
             startup_time,
             metrics: Metrics::default(),
+            critical_sections: CriticalSections::default(),
+            fee_decisions: FeeDecisions::default(),
+            forced_actions: ForcedActions::default(),
+            events: EventBus::default(),
         }
     }
+
+    /// Submitting fragments and recording the transaction happen within a critical section so
+    /// that a shutdown cannot separate the two.
+    pub fn with_critical_sections(mut self, critical_sections: CriticalSections) -> Self {
+        self.critical_sections = critical_sections;
+        self
+    }
+
+    /// Every decision of the fee algorithm is recorded in `fee_decisions`.
+    pub fn with_fee_decisions(mut self, fee_decisions: FeeDecisions) -> Self {
+        self.fee_decisions = fee_decisions;
+        self
+    }
+
+    /// Lets operators override the accumulation and fee checks through `forced_actions`.
+    pub fn with_forced_actions(mut self, forced_actions: ForcedActions) -> Self {
+        self.forced_actions = forced_actions;
+        self
+    }
+
+    /// Every submission, and every replacement of a pending transaction, is announced on `events`.
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
+    }
 }
 
 impl<L1, FuelApi, Db, Clock, FeeProvider> StateCommitter<L1, FuelApi, Db, Clock, FeeProvider>
@@ -142,9 +255,14 @@ where
         let num_blobs =
             u32::try_from(fragments.len()).expect("not to send more than u32::MAX blobs");
 
-        self.fee_algo
-            .fees_acceptable(num_blobs, num_l2_blocks_behind, l1_height)
-            .await
+        let decision = self
+            .fee_algo
+            .decide(num_blobs, num_l2_blocks_behind, l1_height)
+            .await?;
+        let send = decision.send;
+        self.fee_decisions.record(decision, self.clock.now());
+
+        Ok(send)
     }
 
     fn oldest_l2_block_in_fragments(fragments: &NonEmpty<BundleFragment>) -> u32 {
@@ -180,6 +298,14 @@ where
         let data = fragments.clone().map(|f| f.fragment);
 
         let priority = self.determine_priority(&fragments).await?;
+
+        let Some(_section) = self.critical_sections.enter().await else {
+            info!("shutting down, not submitting fragments");
+            return Ok(());
+        };
+
+        let replaced_tx_hash = previous_tx.as_ref().map(|tx| tx.hash);
+
         match self
             .l1_adapter
             .submit_state_fragments(data, previous_tx, priority)
@@ -199,10 +325,25 @@ where
                     .join(", ");
 
                 let tx_hash = submitted_tx.hash;
+                let nonce = submitted_tx.nonce;
+                let submitted_ids = fragment_ids.iter().map(|id| id.as_u32()).collect();
                 self.storage
                     .record_pending_tx(submitted_tx, fragment_ids, self.clock.now())
                     .await?;
 
+                if let Some(replaced_tx_hash) = replaced_tx_hash {
+                    self.events.publish(Event::TxReplaced {
+                        replaced_tx_hash: hex_hash(replaced_tx_hash),
+                        tx_hash: hex_hash(tx_hash),
+                        nonce,
+                    });
+                }
+                self.events.publish(Event::FragmentsSubmitted {
+                    tx_hash: hex_hash(tx_hash),
+                    nonce,
+                    fragment_ids: submitted_ids,
+                });
+
                 tracing::info!("Submitted fragments {ids} with tx {}", hex::encode(tx_hash));
                 Ok(())
             }
@@ -281,9 +422,12 @@ where
         Ok((enough_fragments() || expired().await?) && self.fees_acceptable(fragments).await?)
     }
 
-    async fn submit_fragments_if_ready(&self) -> Result<()> {
+    async fn submit_fragments_if_ready(&self, forced: bool) -> Result<()> {
         if let Some(fragments) = self.next_fragments_to_submit().await? {
-            if self.should_submit_fragments(&fragments).await? {
+            if forced {
+                info!("submission forced by an operator, skipping the accumulation and fee checks");
+                self.submit_fragments(fragments, None).await?;
+            } else if self.should_submit_fragments(&fragments).await? {
                 self.submit_fragments(fragments, None).await?;
             }
         } else {
@@ -327,14 +471,25 @@ where
         })
     }
 
-    async fn resubmit_fragments_if_stalled(&self) -> Result<()> {
+    async fn resubmit_fragments_if_stalled(&self, forced: bool) -> Result<()> {
         let Some(previous_tx) = self.latest_pending_transaction().await? else {
+            if forced {
+                info!("ignoring the forced gas bump, there is no pending tx");
+            }
             return Ok(());
         };
 
         let elapsed = self.elapsed_since_tx_submitted(&previous_tx)?;
 
-        if elapsed >= self.config.gas_bump_timeout {
+        if forced {
+            info!(
+                "replacing tx {} because a gas bump was forced by an operator",
+                hex::encode(previous_tx.hash)
+            );
+
+            let fragments = self.fragments_submitted_by_tx(previous_tx.hash).await?;
+            self.submit_fragments(fragments, Some(previous_tx)).await?;
+        } else if elapsed >= self.config.gas_bump_timeout {
             info!(
                 "replacing tx {} because it was pending for {}s",
                 hex::encode(previous_tx.hash),
@@ -361,13 +516,34 @@ where
     FeeProvider: crate::fees::Api + Send + Sync,
 {
     async fn run(&mut self) -> Result<()> {
-        if self.storage.has_nonfinalized_txs().await? {
-            self.resubmit_fragments_if_stalled().await?;
-        } else {
-            self.submit_fragments_if_ready().await?;
+        // Taken on every run so that an override doesn't linger until it becomes applicable.
+        let forced_submission = self.forced_actions.take_submission();
+        let forced_gas_bump = self.forced_actions.take_gas_bump();
+
+        let has_nonfinalized_txs = match self.storage.has_nonfinalized_txs().await {
+            Ok(has_nonfinalized_txs) => has_nonfinalized_txs,
+            Err(e) => {
+                self.forced_actions
+                    .restore(forced_submission, forced_gas_bump);
+                return Err(e);
+            }
         };
 
-        Ok(())
+        if has_nonfinalized_txs {
+            if forced_submission {
+                info!("ignoring the forced submission, a previous tx is not finalized yet");
+            }
+            self.resubmit_fragments_if_stalled(forced_gas_bump)
+                .await
+                .inspect_err(|_| self.forced_actions.restore(false, forced_gas_bump))
+        } else {
+            if forced_gas_bump {
+                info!("ignoring the forced gas bump, there is no pending tx");
+            }
+            self.submit_fragments_if_ready(forced_submission)
+                .await
+                .inspect_err(|_| self.forced_actions.restore(forced_submission, false))
+        }
     }
 }
 
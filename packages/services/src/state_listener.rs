@@ -9,6 +9,7 @@ pub mod service {
 
     use crate::{
         Runner,
+        events::service::{Event, EventBus, hex_hash},
         types::{L1Tx, TransactionCostUpdate, TransactionState},
     };
 
@@ -18,6 +19,7 @@ pub mod service {
         num_blocks_to_finalize: u64,
         metrics: Metrics,
         clock: Clock,
+        events: EventBus,
     }
 
     impl<L1, Db, Clock> StateListener<L1, Db, Clock> {
@@ -34,8 +36,15 @@ pub mod service {
                 num_blocks_to_finalize,
                 metrics: Metrics::new(last_finalization_time_metric),
                 clock,
+                events: EventBus::default(),
             }
         }
+
+        /// Every observed change in the state of a blob transaction is announced on `events`.
+        pub fn with_events(mut self, events: EventBus) -> Self {
+            self.events = events;
+            self
+        }
     }
 
     impl<L1, Db, Clock> StateListener<L1, Db, Clock>
@@ -55,6 +64,10 @@ pub mod service {
 
             let mut cost_per_tx = vec![];
 
+            // published only once the changes are stored
+            let mut events = vec![];
+            let mut squeezed_out = vec![];
+
             for tx in non_finalized_txs {
                 if skip_nonces.contains(&tx.nonce) {
                     continue;
@@ -69,6 +82,7 @@ pub mod service {
                         (TransactionState::Pending | TransactionState::IncludedInBlock, true) => {
                             // not in the mempool anymore set it to failed
                             selective_change.push((tx.hash, tx.nonce, TransactionState::Failed));
+                            squeezed_out.push((tx.hash, tx.nonce));
 
                             info!(
                                 "blob tx {} not found in mempool. Setting to failed",
@@ -96,6 +110,10 @@ pub mod service {
                 if !tx_response.succeeded() {
                     // set tx to failed all txs with the same nonce to failed
                     noncewide_changes.push((tx.hash, tx.nonce, TransactionState::Failed));
+                    events.push(Event::TxFailed {
+                        tx_hash: hex_hash(tx.hash),
+                        nonce: tx.nonce,
+                    });
 
                     info!("failed blob tx {}", hex::encode(tx.hash));
                     continue;
@@ -112,6 +130,11 @@ pub mod service {
                             tx.nonce,
                             TransactionState::IncludedInBlock,
                         ));
+                        events.push(Event::TxIncluded {
+                            tx_hash: hex_hash(tx.hash),
+                            nonce: tx.nonce,
+                            l1_block: tx_response.block_number(),
+                        });
 
                         info!(
                             "blob tx {} included in block {}",
@@ -131,6 +154,12 @@ pub mod service {
                     total_fee: tx_response.total_fee(),
                     da_block_height: tx_response.block_number(),
                 });
+                events.push(Event::TxFinalized {
+                    tx_hash: hex_hash(tx.hash),
+                    nonce: tx.nonce,
+                    l1_block: tx_response.block_number(),
+                    total_fee: tx_response.total_fee().to_string(),
+                });
 
                 self.metrics.last_finalization_time.set(now.timestamp());
 
@@ -153,6 +182,7 @@ pub mod service {
             }
 
             selective_change.retain(|(_, nonce, _)| !skip_nonces.contains(nonce));
+            squeezed_out.retain(|(_, nonce)| !skip_nonces.contains(nonce));
             let selective_change: Vec<_> = selective_change
                 .into_iter()
                 .map(|(hash, _, state)| (hash, state))
@@ -162,6 +192,16 @@ pub mod service {
                 .update_tx_states_and_costs(selective_change, noncewide_changes, cost_per_tx)
                 .await?;
 
+            let squeezed_out = squeezed_out
+                .into_iter()
+                .map(|(hash, nonce)| Event::TxSqueezedOut {
+                    tx_hash: hex_hash(hash),
+                    nonce,
+                });
+            for event in events.into_iter().chain(squeezed_out) {
+                self.events.publish(event);
+            }
+
             Ok(())
         }
     }
@@ -1,32 +1,99 @@
 pub mod service {
+    use std::collections::BTreeMap;
+
     use serde::Serialize;
+    use tracing::warn;
+    use utoipa::ToSchema;
 
-    use crate::Result;
+    use crate::{
+        Result,
+        state_committer::{FeeDecision, service::FeeDecisions},
+        types::L1Tx,
+        wallet_balance_tracker::service::WalletBalances,
+    };
 
-    #[derive(Debug, Serialize, Default, PartialEq, Eq)]
+    #[derive(Debug, Serialize, Default, PartialEq, Eq, ToSchema)]
     pub struct StatusReport {
+        /// Whether a contract commit is in flight.
         pub status: Status,
+        /// `None` if the Fuel node couldn't be reached.
+        pub latest_fuel_height: Option<u32>,
+        pub latest_imported_height: Option<u32>,
+        pub latest_bundled_height: Option<u32>,
+        /// End of the highest bundle with a finalized fragment.
+        pub latest_finalized_fragment_height: Option<u32>,
+        /// Counted within the lookback window, older blocks are no longer bundled.
+        pub unbundled_blocks: u64,
+        /// Fragments within the lookback window not carried by any non-failed transaction.
+        pub unsubmitted_fragments: u64,
+        pub pending_blob_tx: Option<PendingTxReport>,
+        /// `None` until the state committer running in this instance has checked the fees.
+        pub last_fee_decision: Option<FeeDecisionReport>,
+        /// Balances in wei, keyed by the wallet's usage.
+        pub wallet_balances: BTreeMap<String, String>,
     }
 
-    #[derive(Serialize, Debug, Default, PartialEq, Eq)]
+    #[derive(Serialize, Debug, Default, PartialEq, Eq, ToSchema)]
     pub enum Status {
         #[default]
         Idle,
         Committing,
     }
 
-    pub struct StatusReporter<Db> {
+    #[derive(Debug, Serialize, PartialEq, Eq, ToSchema)]
+    pub struct PendingTxReport {
+        pub hash: String,
+        pub nonce: u32,
+        pub created_at: Option<String>,
+        pub pending_for_seconds: Option<u64>,
+    }
+
+    #[derive(Debug, Serialize, PartialEq, Eq, ToSchema)]
+    pub struct FeeDecisionReport {
+        #[serde(flatten)]
+        pub decision: FeeDecision,
+        pub decided_at: String,
+    }
+
+    pub struct StatusReporter<Db, FuelApi, Clock> {
         storage: Db,
+        fuel_api: FuelApi,
+        clock: Clock,
+        lookback_window: u32,
+        fee_decisions: FeeDecisions,
+        wallet_balances: WalletBalances,
     }
 
-    impl<Db> StatusReporter<Db> {
-        pub fn new(storage: Db) -> Self {
-            Self { storage }
+    impl<Db, FuelApi, Clock> StatusReporter<Db, FuelApi, Clock> {
+        pub fn new(storage: Db, fuel_api: FuelApi, clock: Clock, lookback_window: u32) -> Self {
+            Self {
+                storage,
+                fuel_api,
+                clock,
+                lookback_window,
+                fee_decisions: FeeDecisions::default(),
+                wallet_balances: WalletBalances::default(),
+            }
+        }
+
+        /// Reports the decisions of the state committer sharing `fee_decisions`.
+        pub fn with_fee_decisions(mut self, fee_decisions: FeeDecisions) -> Self {
+            self.fee_decisions = fee_decisions;
+            self
+        }
+
+        /// Reports the balances of the wallet balance tracker sharing `wallet_balances`.
+        pub fn with_wallet_balances(mut self, wallet_balances: WalletBalances) -> Self {
+            self.wallet_balances = wallet_balances;
+            self
         }
     }
-    impl<Db> StatusReporter<Db>
+
+    impl<Db, FuelApi, Clock> StatusReporter<Db, FuelApi, Clock>
     where
         Db: crate::status_reporter::port::Storage,
+        FuelApi: crate::status_reporter::port::fuel::Api,
+        Clock: crate::status_reporter::port::Clock,
     {
         pub async fn current_status(&self) -> Result<StatusReport> {
             let last_submission_completed = self
@@ -41,17 +108,104 @@ pub mod service {
                 Status::Idle
             };
 
-            Ok(StatusReport { status })
+            let latest_fuel_height = match self.fuel_api.latest_height().await {
+                Ok(height) => Some(height),
+                Err(e) => {
+                    warn!("could not get the latest fuel height: {e}");
+                    None
+                }
+            };
+            let latest_imported_height = self.storage.latest_imported_height().await?;
+
+            // Falls back to the imported height so that the counts are still reported while the
+            // Fuel node is unreachable.
+            let starting_height = latest_fuel_height
+                .or(latest_imported_height)
+                .unwrap_or_default()
+                .saturating_sub(self.lookback_window);
+
+            let pending_blob_tx = self
+                .storage
+                .latest_pending_blob_tx()
+                .await?
+                .map(|tx| self.pending_tx_report(tx));
+
+            Ok(StatusReport {
+                status,
+                latest_fuel_height,
+                latest_imported_height,
+                latest_bundled_height: self.storage.latest_bundled_height().await?,
+                latest_finalized_fragment_height: self
+                    .storage
+                    .latest_finalized_fragment_height()
+                    .await?,
+                unbundled_blocks: self.storage.unbundled_blocks(starting_height).await?,
+                unsubmitted_fragments: self.storage.unsubmitted_fragments(starting_height).await?,
+                pending_blob_tx,
+                last_fee_decision: self.fee_decisions.latest().map(|(decision, decided_at)| {
+                    FeeDecisionReport {
+                        decision,
+                        decided_at: decided_at.to_rfc3339(),
+                    }
+                }),
+                wallet_balances: self
+                    .wallet_balances
+                    .latest()
+                    .into_iter()
+                    .map(|(name, balance)| (name, balance.to_string()))
+                    .collect(),
+            })
+        }
+
+        fn pending_tx_report(&self, tx: L1Tx) -> PendingTxReport {
+            let pending_for_seconds = tx.created_at.and_then(|created_at| {
+                self.clock
+                    .now()
+                    .signed_duration_since(created_at)
+                    .to_std()
+                    .ok()
+                    .map(|pending_for| pending_for.as_secs())
+            });
+
+            PendingTxReport {
+                hash: format!("0x{}", hex::encode(tx.hash)),
+                nonce: tx.nonce,
+                created_at: tx.created_at.map(|at| at.to_rfc3339()),
+                pending_for_seconds,
+            }
         }
     }
 }
 
 pub mod port {
-    use crate::{Result, types::BlockSubmission};
+    use crate::{
+        Result,
+        types::{BlockSubmission, DateTime, L1Tx, Utc},
+    };
 
     #[allow(async_fn_in_trait)]
     #[trait_variant::make(Send)]
+    #[cfg_attr(feature = "test-helpers", mockall::automock)]
     pub trait Storage: Send + Sync {
         async fn submission_w_latest_block(&self) -> Result<Option<BlockSubmission>>;
+        async fn latest_imported_height(&self) -> Result<Option<u32>>;
+        async fn latest_bundled_height(&self) -> Result<Option<u32>>;
+        async fn latest_finalized_fragment_height(&self) -> Result<Option<u32>>;
+        async fn unbundled_blocks(&self, starting_height: u32) -> Result<u64>;
+        async fn unsubmitted_fragments(&self, starting_height: u32) -> Result<u64>;
+        async fn latest_pending_blob_tx(&self) -> Result<Option<L1Tx>>;
+    }
+
+    pub mod fuel {
+        #[allow(async_fn_in_trait)]
+        #[trait_variant::make(Send)]
+        #[cfg_attr(feature = "test-helpers", mockall::automock)]
+        pub trait Api: Send + Sync {
+            async fn latest_height(&self) -> crate::Result<u32>;
+        }
+    }
+
+    pub trait Clock {
+        fn now(&self) -> DateTime<Utc>;
     }
 }
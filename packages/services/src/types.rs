@@ -6,8 +6,10 @@ pub use non_empty::*;
 
 mod block_submission;
 mod bundle_cost;
+mod bundle_overview;
 mod fragment;
 mod fuel_block_committed_on_l1;
+mod height_range;
 mod l1_height;
 mod serial_id;
 mod state_submission;
@@ -17,8 +19,10 @@ pub mod storage;
 
 pub use block_submission::*;
 pub use bundle_cost::*;
+pub use bundle_overview::*;
 pub use fragment::*;
 pub use fuel_block_committed_on_l1::*;
+pub use height_range::*;
 pub use l1_height::*;
 pub use serial_id::*;
 pub use state_submission::*;
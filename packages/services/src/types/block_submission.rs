@@ -35,6 +35,15 @@ impl Default for BlockSubmissionTx {
     }
 }
 
+/// How the commit found on L1 differs from the submission we recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitMismatch {
+    /// Nothing is committed at the commit height.
+    Missing,
+    /// Another block hash is committed at the commit height.
+    Foreign { block_hash: [u8; 32] },
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BlockSubmission {
     pub id: Option<NonNegative<i32>>,
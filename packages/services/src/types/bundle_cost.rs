@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 #[derive(Debug, Clone)]
 pub struct TransactionCostUpdate {
@@ -7,7 +8,7 @@ pub struct TransactionCostUpdate {
     pub da_block_height: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct BundleCost {
     // the bundle id
     pub id: u64,
@@ -0,0 +1,37 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::L1Tx;
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct BundleOverview {
+    pub id: u32,
+    // starting height of the bundled block range
+    pub start_height: u32,
+    // ending height of the bundled block range (inclusive)
+    pub end_height: u32,
+    pub num_fragments: u32,
+    // fragments carried by a finalized transaction
+    pub num_finalized_fragments: u32,
+    // bytes of compressed bundle data carried by the fragments
+    pub compressed_size: u64,
+    // size of the bundled blocks, `None` once the blocks were pruned
+    pub uncompressed_size: Option<u64>,
+}
+
+impl BundleOverview {
+    pub fn compression_ratio(&self) -> Option<f64> {
+        let uncompressed_size = self.uncompressed_size?;
+        (self.compressed_size > 0).then(|| uncompressed_size as f64 / self.compressed_size as f64)
+    }
+}
+
+/// A fragment of a bundle along with every transaction that carried it, oldest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FragmentSubmissions {
+    pub id: u32,
+    pub idx: u32,
+    pub total_bytes: u32,
+    pub unused_bytes: u32,
+    pub transactions: Vec<L1Tx>,
+}
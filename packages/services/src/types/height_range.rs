@@ -0,0 +1,17 @@
+/// Inclusive range of fuel block heights, unbounded on the sides left as `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HeightRange {
+    pub from: Option<u32>,
+    pub to: Option<u32>,
+}
+
+impl HeightRange {
+    pub fn contains(&self, height: u32) -> bool {
+        self.from.is_none_or(|from| height >= from) && self.to.is_none_or(|to| height <= to)
+    }
+
+    /// Whether any height between `start` and `end` (inclusive) is in the range.
+    pub fn overlaps(&self, start: u32, end: u32) -> bool {
+        self.from.is_none_or(|from| end >= from) && self.to.is_none_or(|to| start <= to)
+    }
+}
@@ -1,5 +1,8 @@
 pub mod service {
-    use std::collections::HashMap;
+    use std::{
+        collections::{BTreeMap, HashMap},
+        sync::{Arc, Mutex},
+    };
 
     use metrics::{
         RegistersMetrics,
@@ -16,9 +19,34 @@ pub mod service {
         address: Address,
     }
 
+    /// The latest known balance of every tracked wallet, shared so that it can be reported.
+    #[derive(Debug, Clone, Default)]
+    pub struct WalletBalances {
+        latest: Arc<Mutex<BTreeMap<String, U256>>>,
+    }
+
+    impl WalletBalances {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Balances in wei, keyed by the wallet's usage.
+        pub fn latest(&self) -> BTreeMap<String, U256> {
+            self.latest.lock().expect("lock not poisoned").clone()
+        }
+
+        fn record(&self, name: &str, balance: U256) {
+            self.latest
+                .lock()
+                .expect("lock not poisoned")
+                .insert(name.to_owned(), balance);
+        }
+    }
+
     pub struct WalletBalanceTracker<Api> {
         api: Api,
         tracking: HashMap<String, Balance>,
+        balances: WalletBalances,
     }
 
     impl<Api> WalletBalanceTracker<Api>
@@ -29,9 +57,16 @@ pub mod service {
             Self {
                 api,
                 tracking: HashMap::default(),
+                balances: WalletBalances::default(),
             }
         }
 
+        /// Every balance update is recorded in `balances`.
+        pub fn with_balances(mut self, balances: WalletBalances) -> Self {
+            self.balances = balances;
+            self
+        }
+
         pub fn track_address(&mut self, name: &str, address: crate::types::Address) {
             self.tracking.insert(
                 name.to_owned(),
@@ -47,8 +82,9 @@ pub mod service {
         }
 
         pub async fn update_balance(&self) -> Result<()> {
-            for balance_tracker in self.tracking.values() {
+            for (name, balance_tracker) in &self.tracking {
                 let balance = self.api.balance(balance_tracker.address).await?;
+                self.balances.record(name, balance);
                 let balance_gwei = balance / U256::from(1_000_000_000);
                 balance_tracker.gauge.set(balance_gwei.to::<i64>());
             }
@@ -107,7 +143,7 @@ mod tests {
         },
     };
     use mockall::predicate::eq;
-    use service::WalletBalanceTracker;
+    use service::{WalletBalanceTracker, WalletBalances};
 
     use super::*;
     use crate::types::Address;
@@ -160,6 +196,29 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn shares_latest_balances() {
+        // given
+        let address = "0x0000000000000000000000000000000000000000"
+            .parse()
+            .unwrap();
+        let eth_adapter = has_balances([(address, "500000000000000000000")]);
+        let balances = WalletBalances::new();
+
+        let mut sut = WalletBalanceTracker::new(eth_adapter).with_balances(balances.clone());
+        sut.track_address("blob_poster", address);
+
+        // when
+        sut.update_balance().await.unwrap();
+
+        // then
+        let latest = balances.latest();
+        assert_eq!(
+            latest.get("blob_poster"),
+            Some(&U256::from_str("500000000000000000000").unwrap())
+        );
+    }
+
     fn has_balances(
         expectations: impl IntoIterator<Item = (Address, &'static str)>,
     ) -> crate::wallet_balance_tracker::port::l1::MockApi {
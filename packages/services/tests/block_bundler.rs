@@ -945,3 +945,49 @@ async fn bundles_immediately_if_enough_blocks() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn doesnt_store_the_bundle_once_the_leadership_is_lost() -> Result<()> {
+    use services::{
+        leader_election::{self, service::LeaderElection},
+        state_committer::port::Storage,
+    };
+
+    // given
+    let setup = test_helpers::Setup::init().await;
+    setup
+        .import_blocks(Blocks::WithHeights {
+            range: 0..=1,
+            block_size: 100,
+        })
+        .await;
+
+    let lost = LeaderElection::new(
+        leader_election::port::MockStorage::new(),
+        "a".into(),
+        Duration::from_secs(30),
+    )
+    .leadership("block_bundler");
+
+    let mut block_bundler = BlockBundler::new(
+        test_helpers::mocks::fuel::block_bundler_latest_height_is(1),
+        setup.db(),
+        TestClock::default(),
+        default_bundler_factory(),
+        BlockBundlerConfig {
+            bytes_to_accumulate: NonZeroUsize::MAX,
+            blocks_to_accumulate: NonZeroUsize::new(2).unwrap(),
+            ..BlockBundlerConfig::default()
+        },
+    )
+    .with_leadership(lost);
+
+    // when
+    block_bundler.run().await?;
+
+    // then
+    let fragments = setup.db().oldest_nonfinalized_fragments(0, 10).await?;
+    assert!(fragments.is_empty());
+
+    Ok(())
+}
@@ -1,7 +1,14 @@
+use std::time::Duration;
+
+use metrics::RegistersMetrics;
+use mockall::predicate::eq;
 use services::{
     Runner,
-    block_committer::{port::Storage, service::BlockCommitter},
-    types::{TransactionResponse, TransactionState, Utc},
+    block_committer::{
+        port::Storage,
+        service::{BlockCommitter, ReconciliationConfig},
+    },
+    types::{BlockSubmissionTx, TransactionResponse, TransactionState, Utc},
 };
 use test_helpers::mocks::{
     fuel::{given_a_block, given_fetcher},
@@ -215,3 +222,458 @@ async fn updates_submission_state_to_finalized() {
 
     assert!(pending_txs.is_empty());
 }
+
+#[tokio::test]
+async fn bumps_gas_of_commit_pending_longer_than_timeout() {
+    // given
+    let setup = test_helpers::Setup::init().await;
+
+    let latest_block = given_a_block(4);
+    let fuel_adapter = given_fetcher(vec![latest_block]);
+
+    setup.add_submissions(vec![0, 2, 4]).await;
+    setup.test_clock().advance_time(Duration::from_secs(61));
+
+    let mut l1 = FullL1Mock::new();
+    l1.block_committer_l1_api
+        .expect_get_block_number()
+        .returning(|| Box::pin(async { Ok(4u32.into()) }));
+    l1.block_committer_l1_api
+        .expect_get_transaction_response()
+        .with(eq([4; 32]))
+        .returning(|_| Box::pin(async { Ok(None) }));
+    l1.block_committer_contract
+        .expect_submit()
+        .withf(|_, height, previous_tx| {
+            *height == 4
+                && previous_tx
+                    .as_ref()
+                    .is_some_and(|tx| tx.hash == [4; 32] && tx.nonce == 4)
+        })
+        .return_once(|_, _, _| {
+            Box::pin(async {
+                Ok(BlockSubmissionTx {
+                    hash: [5; 32],
+                    nonce: 4,
+                    ..Default::default()
+                })
+            })
+        })
+        .once();
+
+    let mut block_committer = BlockCommitter::new(
+        l1,
+        setup.db(),
+        fuel_adapter,
+        setup.test_clock(),
+        2.try_into().unwrap(),
+        1,
+    )
+    .with_gas_bump_timeout(Duration::from_secs(60));
+
+    // when
+    block_committer.run().await.unwrap();
+
+    // then
+    let latest_submission = setup
+        .db()
+        .submission_w_latest_block()
+        .await
+        .unwrap()
+        .expect("submission to exist");
+    let mut pending_hashes = setup
+        .db()
+        .get_pending_block_submission_txs(latest_submission.id.expect("submission to have id"))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|tx| tx.hash)
+        .collect::<Vec<_>>();
+    pending_hashes.sort();
+
+    assert_eq!(pending_hashes, vec![[4; 32], [5; 32]]);
+}
+
+#[tokio::test]
+async fn does_not_bump_gas_of_included_commit() {
+    // given
+    let setup = test_helpers::Setup::init().await;
+
+    let latest_height = 4;
+    let latest_block = given_a_block(latest_height);
+    let fuel_adapter = given_fetcher(vec![latest_block]);
+
+    setup.add_submissions(vec![0, 2, 4]).await;
+    setup.test_clock().advance_time(Duration::from_secs(61));
+
+    let tx_response = TransactionResponse::new(latest_height as u64, true, 100, 0);
+    let l1 = expects_transaction_response(latest_height, [4; 32], Some(tx_response));
+
+    let mut block_committer = BlockCommitter::new(
+        l1,
+        setup.db(),
+        fuel_adapter,
+        setup.test_clock(),
+        2.try_into().unwrap(),
+        1,
+    )
+    .with_gas_bump_timeout(Duration::from_secs(60));
+
+    // when
+    block_committer.run().await.unwrap();
+
+    // then
+    // MockL1 verifies that submit was not called
+}
+
+fn reconciliation(recommit_current_epoch: bool) -> ReconciliationConfig {
+    ReconciliationConfig {
+        interval: Duration::from_secs(600),
+        lookback: 10,
+        recommit_current_epoch,
+        num_commit_slots: 240.try_into().unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn reconciliation_flags_missing_and_foreign_commits() {
+    // given
+    let setup = test_helpers::Setup::init().await;
+
+    let latest_block = given_a_block(4);
+    let fuel_adapter = given_fetcher(vec![latest_block]);
+
+    setup.add_submissions(vec![0, 2, 4]).await;
+    for hash in [[0; 32], [2; 32], [4; 32]] {
+        setup
+            .db()
+            .update_block_submission_tx(hash, TransactionState::Finalized(Utc::now()))
+            .await
+            .unwrap();
+    }
+    let submissions = setup.db().recent_block_submissions(10).await.unwrap();
+    let committed_hash = |height| {
+        submissions
+            .iter()
+            .find(|s| s.block_height == height)
+            .unwrap()
+            .block_hash
+    };
+
+    let mut l1 = FullL1Mock::new();
+    let onchain = [
+        (0, Some(committed_hash(0))),
+        // nothing committed
+        (1, None),
+        // someone else committed
+        (2, Some([9; 32])),
+    ];
+    for (commit_height, block_hash) in onchain {
+        l1.block_committer_contract
+            .expect_block_hash_at_commit_height()
+            .with(eq(commit_height))
+            .return_once(move |_| Box::pin(async move { Ok(block_hash) }))
+            .once();
+    }
+    l1.block_committer_contract.expect_submit().never();
+
+    let registry = metrics::prometheus::Registry::new();
+    let mut block_committer = BlockCommitter::new(
+        l1,
+        setup.db(),
+        fuel_adapter,
+        setup.test_clock(),
+        2.try_into().unwrap(),
+        1,
+    )
+    .with_reconciliation(reconciliation(false));
+    block_committer.register_metrics(&registry);
+
+    // when
+    block_committer.run().await.unwrap();
+
+    // then
+    let mismatches = registry
+        .gather()
+        .into_iter()
+        .find(|metric| metric.get_name() == "block_commit_mismatches")
+        .expect("block_commit_mismatches metric not found")
+        .get_metric()[0]
+        .get_gauge()
+        .get_value();
+    assert_eq!(mismatches, 2.0);
+}
+
+#[tokio::test]
+async fn reconciliation_recommits_current_epoch_if_missing() {
+    // given
+    let setup = test_helpers::Setup::init().await;
+
+    let latest_block = given_a_block(4);
+    let fuel_adapter = given_fetcher(vec![latest_block]);
+
+    setup.add_submissions(vec![4]).await;
+    setup
+        .db()
+        .update_block_submission_tx([4; 32], TransactionState::Finalized(Utc::now()))
+        .await
+        .unwrap();
+
+    let mut l1 = FullL1Mock::new();
+    l1.block_committer_contract
+        .expect_block_hash_at_commit_height()
+        .with(eq(2))
+        .return_once(|_| Box::pin(async { Ok(None) }));
+    l1.block_committer_contract
+        .expect_submit()
+        .withf(|_, height, previous_tx| *height == 4 && previous_tx.is_none())
+        .return_once(|_, _, _| {
+            Box::pin(async {
+                Ok(BlockSubmissionTx {
+                    hash: [5; 32],
+                    nonce: 5,
+                    ..Default::default()
+                })
+            })
+        })
+        .once();
+    l1.block_committer_l1_api
+        .expect_get_block_number()
+        .returning(|| Box::pin(async { Ok(4u32.into()) }));
+    l1.block_committer_l1_api
+        .expect_get_transaction_response()
+        .with(eq([5; 32]))
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    let mut block_committer = BlockCommitter::new(
+        l1,
+        setup.db(),
+        fuel_adapter,
+        setup.test_clock(),
+        2.try_into().unwrap(),
+        1,
+    )
+    .with_reconciliation(reconciliation(true));
+
+    // when
+    block_committer.run().await.unwrap();
+
+    // then
+    let submission = setup
+        .db()
+        .submission_w_latest_block()
+        .await
+        .unwrap()
+        .expect("submission to exist");
+    assert!(!submission.completed);
+
+    let pending_hashes = setup
+        .db()
+        .get_pending_block_submission_txs(submission.id.expect("submission to have id"))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|tx| tx.hash)
+        .collect::<Vec<_>>();
+    assert_eq!(pending_hashes, vec![[5; 32]]);
+}
+
+#[tokio::test]
+async fn reconciliation_skips_commits_whose_slot_was_reused() {
+    // given
+    let setup = test_helpers::Setup::init().await;
+
+    let latest_block = given_a_block(4);
+    let fuel_adapter = given_fetcher(vec![latest_block]);
+
+    setup.add_submissions(vec![0, 2, 4]).await;
+    for hash in [[0; 32], [2; 32], [4; 32]] {
+        setup
+            .db()
+            .update_block_submission_tx(hash, TransactionState::Finalized(Utc::now()))
+            .await
+            .unwrap();
+    }
+    let submissions = setup.db().recent_block_submissions(10).await.unwrap();
+    let committed_hash = |height| {
+        submissions
+            .iter()
+            .find(|s| s.block_height == height)
+            .unwrap()
+            .block_hash
+    };
+
+    let mut l1 = FullL1Mock::new();
+    // with two slots the commit at height 2 reused the slot of the one at height 0
+    l1.block_committer_contract
+        .expect_block_hash_at_commit_height()
+        .with(eq(0))
+        .never();
+    for commit_height in [1, 2] {
+        let block_hash = committed_hash(commit_height * 2);
+        l1.block_committer_contract
+            .expect_block_hash_at_commit_height()
+            .with(eq(commit_height))
+            .return_once(move |_| Box::pin(async move { Ok(Some(block_hash)) }))
+            .once();
+    }
+
+    let registry = metrics::prometheus::Registry::new();
+    let mut block_committer = BlockCommitter::new(
+        l1,
+        setup.db(),
+        fuel_adapter,
+        setup.test_clock(),
+        2.try_into().unwrap(),
+        1,
+    )
+    .with_reconciliation(ReconciliationConfig {
+        num_commit_slots: 2.try_into().unwrap(),
+        ..reconciliation(false)
+    });
+    block_committer.register_metrics(&registry);
+
+    // when
+    block_committer.run().await.unwrap();
+
+    // then
+    let mismatches = registry
+        .gather()
+        .into_iter()
+        .find(|metric| metric.get_name() == "block_commit_mismatches")
+        .expect("block_commit_mismatches metric not found")
+        .get_metric()[0]
+        .get_gauge()
+        .get_value();
+    assert_eq!(mismatches, 0.0);
+}
+
+#[tokio::test]
+async fn failed_reconciliation_does_not_hold_up_commits() {
+    // given
+    let setup = test_helpers::Setup::init().await;
+
+    setup.add_submissions(vec![0]).await;
+    setup
+        .db()
+        .update_block_submission_tx([0; 32], TransactionState::Finalized(Utc::now()))
+        .await
+        .unwrap();
+
+    let latest_block = given_a_block(4);
+    let fuel_adapter = given_fetcher(vec![latest_block]);
+
+    let mut l1 = expects_contract_submission(latest_block, [1; 32]);
+    l1.block_committer_contract
+        .expect_block_hash_at_commit_height()
+        .returning(|_| Box::pin(async { Err(services::Error::Network("boom".to_string())) }));
+
+    let registry = metrics::prometheus::Registry::new();
+    let mut block_committer = BlockCommitter::new(
+        l1,
+        setup.db(),
+        fuel_adapter,
+        setup.test_clock(),
+        2.try_into().unwrap(),
+        1,
+    )
+    .with_reconciliation(reconciliation(false));
+    block_committer.register_metrics(&registry);
+
+    // when
+    block_committer.run().await.unwrap();
+
+    // then
+    // MockL1 validates that the block was submitted
+    let failures = registry
+        .gather()
+        .into_iter()
+        .find(|metric| metric.get_name() == "block_commit_reconciliation_failures")
+        .expect("block_commit_reconciliation_failures metric not found")
+        .get_metric()[0]
+        .get_counter()
+        .get_value();
+    assert_eq!(failures, 1.0);
+}
+
+#[tokio::test]
+async fn submits_block_if_quorum_of_fuel_nodes_agrees_on_its_id() {
+    // given
+    let setup = test_helpers::Setup::init().await;
+
+    let latest_block = given_a_block(10);
+    let fuel_adapter = given_fetcher(vec![latest_block]);
+    let other_fuel_nodes = vec![
+        given_fetcher(vec![latest_block]),
+        given_fetcher(vec![given_a_block(10)]),
+    ];
+
+    let l1 = expects_contract_submission(latest_block, [0; 32]);
+    let mut block_committer = BlockCommitter::new(
+        l1,
+        setup.db(),
+        fuel_adapter,
+        setup.test_clock(),
+        2.try_into().unwrap(),
+        1,
+    )
+    .with_block_id_quorum(other_fuel_nodes, 2.try_into().unwrap());
+
+    // when
+    block_committer.run().await.unwrap();
+
+    // then
+    // MockL1 validates the expected calls are made
+}
+
+#[tokio::test]
+async fn refuses_to_submit_block_without_quorum_on_its_id() {
+    // given
+    let setup = test_helpers::Setup::init().await;
+
+    let latest_block = given_a_block(10);
+    let fuel_adapter = given_fetcher(vec![latest_block]);
+    let other_fuel_nodes = vec![
+        given_fetcher(vec![given_a_block(10)]),
+        given_fetcher(vec![latest_block]),
+    ];
+
+    let mut l1 = FullL1Mock::new();
+    l1.block_committer_contract.expect_submit().never();
+
+    let mut block_committer = BlockCommitter::new(
+        l1,
+        setup.db(),
+        fuel_adapter,
+        setup.test_clock(),
+        2.try_into().unwrap(),
+        1,
+    )
+    .with_block_id_quorum(other_fuel_nodes, 3.try_into().unwrap());
+    let registry = metrics::prometheus::Registry::new();
+    block_committer.register_metrics(&registry);
+
+    // when
+    let result = block_committer.run().await;
+
+    // then
+    assert!(matches!(result, Err(services::Error::NoQuorum(_))));
+    assert!(
+        setup
+            .db()
+            .submission_w_latest_block()
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let quorum_failures = registry
+        .gather()
+        .into_iter()
+        .find(|family| family.get_name() == "block_commit_quorum_failures")
+        .expect("metric to be registered");
+    assert_eq!(
+        quorum_failures.get_metric()[0].get_counter().get_value(),
+        1.0
+    );
+}
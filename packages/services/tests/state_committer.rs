@@ -5,7 +5,7 @@ use metrics::{RegistersMetrics, prometheus};
 use services::{
     Result, Runner, StateCommitter, StateCommitterConfig,
     fees::Fees,
-    state_committer::{AlgoConfig, FeeThresholds, SmaPeriods},
+    state_committer::{AlgoConfig, FeeThresholds, SmaPeriods, service::ForcedActions},
     types::{Fragment, FragmentsSubmitted, L1Tx, NonEmpty},
 };
 use test_helpers::{mocks, noop_fees, preconfigured_fees};
@@ -335,6 +335,172 @@ async fn resubmits_fragments_when_gas_bump_timeout_exceeded() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn forced_submission_skips_accumulation() -> Result<()> {
+    // given
+    let setup = test_helpers::Setup::init().await;
+
+    let fragments = setup.insert_fragments(0, 2).await;
+
+    let mut l1_mock_submit = test_helpers::mocks::l1::expects_state_submissions([(
+        Some(NonEmpty::from_vec(fragments.clone()).unwrap()),
+        L1Tx::default(),
+    )]);
+    l1_mock_submit
+        .expect_current_height()
+        .returning(|| Box::pin(async { Ok(0) }));
+
+    let fuel_mock = test_helpers::mocks::fuel::latest_height_is(0);
+    let forced_actions = ForcedActions::new();
+    let mut state_committer = StateCommitter::new(
+        l1_mock_submit,
+        fuel_mock,
+        setup.db(),
+        StateCommitterConfig {
+            lookback_window: 1000,
+            fragment_accumulation_timeout: Duration::from_secs(60),
+            fragments_to_accumulate: 10.try_into().unwrap(),
+            ..Default::default()
+        },
+        setup.test_clock(),
+        noop_fees(),
+    )
+    .with_forced_actions(forced_actions.clone());
+
+    forced_actions.force_submission();
+
+    // when
+    state_committer.run().await?;
+    state_committer.run().await?;
+
+    // then
+    // Mocks validate that the fragments were sent once, the second run no longer being forced
+    Ok(())
+}
+
+#[tokio::test]
+async fn forced_submission_is_retried_if_the_run_fails() -> Result<()> {
+    // given
+    let setup = test_helpers::Setup::init().await;
+
+    let fragments = setup.insert_fragments(0, 2).await;
+
+    let mut l1_mock_submit = services::state_committer::port::l1::MockApi::new();
+    let mut sequence = mockall::Sequence::new();
+    l1_mock_submit
+        .expect_submit_state_fragments()
+        .once()
+        .in_sequence(&mut sequence)
+        .return_once(|_, _, _| {
+            Box::pin(async { Err(services::Error::Network("connection lost".to_string())) })
+        });
+    let expected = NonEmpty::from_vec(fragments.clone()).unwrap();
+    l1_mock_submit
+        .expect_submit_state_fragments()
+        .withf(move |data, _, _| data == &expected)
+        .once()
+        .in_sequence(&mut sequence)
+        .return_once(|fragments, _, _| {
+            Box::pin(async move {
+                Ok((
+                    L1Tx::default(),
+                    FragmentsSubmitted {
+                        num_fragments: fragments.len().try_into().unwrap(),
+                    },
+                ))
+            })
+        });
+    l1_mock_submit
+        .expect_current_height()
+        .returning(|| Box::pin(async { Ok(0) }));
+
+    let fuel_mock = test_helpers::mocks::fuel::latest_height_is(0);
+    let forced_actions = ForcedActions::new();
+    let mut state_committer = StateCommitter::new(
+        l1_mock_submit,
+        fuel_mock,
+        setup.db(),
+        StateCommitterConfig {
+            lookback_window: 1000,
+            fragment_accumulation_timeout: Duration::from_secs(60),
+            fragments_to_accumulate: 10.try_into().unwrap(),
+            ..Default::default()
+        },
+        setup.test_clock(),
+        noop_fees(),
+    )
+    .with_forced_actions(forced_actions.clone());
+
+    forced_actions.force_submission();
+
+    // when
+    let failed_run = state_committer.run().await;
+    state_committer.run().await?;
+
+    // then
+    assert!(failed_run.is_err());
+    // Mocks validate that the fragments were sent on the second run, still being forced
+    Ok(())
+}
+
+#[tokio::test]
+async fn forced_gas_bump_resubmits_before_timeout() -> Result<()> {
+    // given
+    let setup = test_helpers::Setup::init().await;
+
+    let fragments = setup.insert_fragments(0, 5).await;
+
+    let mut l1_mock_submit = test_helpers::mocks::l1::expects_state_submissions([
+        (
+            Some(NonEmpty::from_vec(fragments.clone()).unwrap()),
+            L1Tx {
+                hash: [6; 32],
+                nonce: 0,
+                ..Default::default()
+            },
+        ),
+        (
+            Some(NonEmpty::from_vec(fragments.clone()).unwrap()),
+            L1Tx {
+                hash: [7; 32],
+                nonce: 1,
+                ..Default::default()
+            },
+        ),
+    ]);
+    l1_mock_submit
+        .expect_current_height()
+        .returning(|| Box::pin(async { Ok(0) }));
+
+    let fuel_mock = test_helpers::mocks::fuel::latest_height_is(0);
+    let forced_actions = ForcedActions::new();
+    let mut state_committer = StateCommitter::new(
+        l1_mock_submit,
+        fuel_mock,
+        setup.db(),
+        StateCommitterConfig {
+            lookback_window: 1000,
+            fragment_accumulation_timeout: Duration::from_secs(60),
+            fragments_to_accumulate: 5.try_into().unwrap(),
+            gas_bump_timeout: Duration::from_secs(60),
+            ..Default::default()
+        },
+        setup.test_clock(),
+        noop_fees(),
+    )
+    .with_forced_actions(forced_actions.clone());
+
+    state_committer.run().await?;
+    forced_actions.force_gas_bump();
+
+    // when
+    state_committer.run().await?;
+
+    // then
+    // Mocks validate that the fragments were sent again without waiting for the gas bump timeout
+    Ok(())
+}
+
 #[tokio::test]
 async fn sends_transaction_when_short_term_fee_favorable() -> Result<()> {
     // given
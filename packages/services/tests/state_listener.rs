@@ -626,3 +626,45 @@ async fn finalized_replacement_tx_will_leave_no_pending_tx(
 
     Ok(())
 }
+
+#[tokio::test]
+async fn finalization_is_published_as_an_event() -> Result<()> {
+    use services::events::{
+        port::Storage,
+        service::{Event, EventBus, EventRecorder},
+    };
+
+    // given
+    let setup = test_helpers::Setup::init().await;
+    let _ = setup.insert_fragments(0, 1).await;
+
+    let tx_hash = [1; 32];
+    setup.send_fragments(tx_hash, 0).await;
+
+    let l1_mock = mocks::l1::txs_finished(1, 0, [(tx_hash, TxStatus::Success)]);
+
+    let events = EventBus::new();
+    let mut listener = StateListener::new(
+        l1_mock,
+        setup.db(),
+        1,
+        setup.test_clock(),
+        IntGauge::new("test", "test").unwrap(),
+    )
+    .with_events(events.clone());
+    let mut recorder = EventRecorder::new(setup.db(), events, setup.test_clock());
+
+    // when
+    listener.run().await?;
+    recorder.run().await?;
+
+    // then
+    let recorded = setup.db().events_after(0, 10).await?;
+    assert_eq!(recorded.len(), 1);
+    assert!(matches!(
+        &recorded[0].event,
+        Event::TxFinalized { tx_hash: hash, nonce: 0, l1_block: 0, .. } if *hash == format!("0x{}", hex::encode(tx_hash))
+    ));
+
+    Ok(())
+}
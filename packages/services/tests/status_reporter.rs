@@ -1,12 +1,15 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use clock::TestClock;
 use rand::Rng;
 use services::{
-    status_reporter::service::{Status, StatusReport, StatusReporter},
-    types::{BlockSubmission, BlockSubmissionTx},
+    Runner, StateCommitter, StateCommitterConfig,
+    state_committer::{FeeDecisionReason, service::FeeDecisions},
+    status_reporter::service::{Status, StatusReporter},
+    types::{BlockSubmission, BlockSubmissionTx, L1Tx},
 };
 use storage::PostgresProcess;
+use test_helpers::{Blocks, mocks, noop_fees};
 
 #[tokio::test]
 async fn status_depends_on_last_submission() {
@@ -34,18 +37,18 @@ async fn status_depends_on_last_submission() {
                 .unwrap();
             }
 
-            let status_reporter = StatusReporter::new(db);
+            let status_reporter = StatusReporter::new(
+                db,
+                mocks::fuel::status_reporter_latest_height_is(0),
+                TestClock::default(),
+                100,
+            );
 
             // when
             let status = status_reporter.current_status().await.unwrap();
 
             // then
-            assert_eq!(
-                status,
-                StatusReport {
-                    status: expected_app_status
-                }
-            );
+            assert_eq!(status.status, expected_app_status);
         }
     };
 
@@ -56,3 +59,76 @@ async fn status_depends_on_last_submission() {
     // has no entry
     test(None, Status::Idle).await;
 }
+
+#[tokio::test]
+async fn reports_the_blob_pipeline() {
+    // given
+    let setup = test_helpers::Setup::init().await;
+    setup.insert_fragments(0, 2).await;
+
+    let mut l1_mock = mocks::l1::expects_state_submissions(vec![(
+        None,
+        L1Tx {
+            hash: [1; 32],
+            nonce: 7,
+            ..Default::default()
+        },
+    )]);
+    l1_mock
+        .expect_current_height()
+        .return_once(move || Box::pin(async { Ok(0) }));
+    let fee_decisions = FeeDecisions::new();
+    StateCommitter::new(
+        l1_mock,
+        mocks::fuel::latest_height_is(0),
+        setup.db(),
+        StateCommitterConfig {
+            fragments_to_accumulate: 1.try_into().unwrap(),
+            ..Default::default()
+        },
+        setup.test_clock(),
+        noop_fees(),
+    )
+    .with_fee_decisions(fee_decisions.clone())
+    .run()
+    .await
+    .unwrap();
+
+    setup
+        .import_blocks(Blocks::WithHeights {
+            range: 1..=1,
+            block_size: 100,
+        })
+        .await;
+    setup.test_clock().advance_time(Duration::from_secs(60));
+
+    let status_reporter = StatusReporter::new(
+        setup.db(),
+        mocks::fuel::status_reporter_latest_height_is(2),
+        setup.test_clock(),
+        100,
+    )
+    .with_fee_decisions(fee_decisions);
+
+    // when
+    let status = status_reporter.current_status().await.unwrap();
+
+    // then
+    assert_eq!(status.latest_fuel_height, Some(2));
+    assert_eq!(status.latest_imported_height, Some(1));
+    assert_eq!(status.latest_bundled_height, Some(0));
+    assert_eq!(status.latest_finalized_fragment_height, None);
+    assert_eq!(status.unbundled_blocks, 1);
+    assert_eq!(status.unsubmitted_fragments, 0);
+
+    let pending_tx = status.pending_blob_tx.unwrap();
+    assert_eq!(pending_tx.nonce, 7);
+    assert_eq!(pending_tx.pending_for_seconds, Some(60));
+
+    let fee_decision = status.last_fee_decision.unwrap();
+    assert!(fee_decision.decision.send);
+    assert!(matches!(
+        fee_decision.decision.reason,
+        FeeDecisionReason::AlwaysAcceptable { .. }
+    ));
+}
@@ -71,7 +71,8 @@ pub mod mocks {
         impl services::block_committer::port::l1::Contract for FullL1Mock {
             delegate! {
                 to self.block_committer_contract {
-                    async fn submit(&self, hash: [u8;32], height: u32) -> services::Result<BlockSubmissionTx>;
+                    async fn submit(&self, hash: [u8;32], height: u32, previous_tx: Option<BlockSubmissionTx>) -> services::Result<BlockSubmissionTx>;
+                    async fn block_hash_at_commit_height(&self, commit_height: u32) -> services::Result<Option<[u8; 32]>>;
                     fn commit_interval(&self) -> NonZeroU32;
                 }
             }
@@ -117,8 +118,10 @@ pub mod mocks {
             };
             l1.block_committer_contract
                 .expect_submit()
-                .withf(move |hash, height| *hash == block.id && *height == block.height)
-                .return_once(move |_, _| Box::pin(async { Ok(submission_tx) }))
+                .withf(move |hash, height, previous_tx| {
+                    *hash == block.id && *height == block.height && previous_tx.is_none()
+                })
+                .return_once(move |_, _, _| Box::pin(async { Ok(submission_tx) }))
                 .once();
 
             l1.block_committer_l1_api
@@ -402,6 +405,17 @@ pub mod mocks {
             fuel_mock
         }
 
+        pub fn status_reporter_latest_height_is(
+            height: u32,
+        ) -> services::status_reporter::port::fuel::MockApi {
+            let mut fuel_mock = services::status_reporter::port::fuel::MockApi::default();
+            fuel_mock
+                .expect_latest_height()
+                .returning(move || Box::pin(async move { Ok(height) }));
+
+            fuel_mock
+        }
+
         pub fn given_fetcher(
             available_blocks: Vec<FuelBlock>,
         ) -> services::block_committer::port::fuel::MockApi {